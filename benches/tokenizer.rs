@@ -0,0 +1,65 @@
+//! Tokenizer throughput on generated programs of growing size. Run with
+//! `cargo bench --bench tokenizer`; time per char should stay flat as the
+//! programs grow.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use visual_tho::interpreter::languages::visualg::VisuAlg;
+use visual_tho::interpreter::tokenizer::Tokenizer;
+
+/// A program with `blocks` copies of a body using every kind of token.
+fn generate(blocks:usize) -> String {
+    let mut code = String::from("algoritmo \"gerado\"\nvar\n   i, total : inteiro\n   media : real\n   nome : caractere\ninicio\n");
+
+    for block in 0..blocks {
+        code += &format!(
+            "   // bloco {block}\n\
+             \x20  para i de 1 ate {block} passo 2 faca\n\
+             \x20     total <- total + i * 3 mod 7\n\
+             \x20     se (total >= 10) e nao (i = 5) entao\n\
+             \x20        media := total / 2.5 ^ 2\n\
+             \x20        escreval(\"total: \\\"\", total:4, \" {{ok}}\")\n\
+             \x20     senao\n\
+             \x20        nome <- \"valor \" /* comentario\n\
+             \x20        de bloco */\n\
+             \x20     fimse\n\
+             \x20  fimpara\n"
+        );
+    }
+
+    code + "fimalgoritmo\n"
+}
+
+fn measure(code:&str) -> Duration {
+    let mut tokenizer = Tokenizer::new(Box::new(VisuAlg {}));
+    let runs = 5;
+
+    let start = Instant::now();
+    for _ in 0..runs {
+        tokenizer.tokenize(black_box(code));
+        black_box(tokenizer.tokens());
+    }
+    start.elapsed() / runs
+}
+
+fn main() {
+    println!("{:>10} {:>10} {:>12} {:>10}", "chars", "tokens", "time", "ns/char");
+
+    for blocks in [100, 1_000, 10_000, 50_000] {
+        let code = generate(blocks);
+        let chars = code.chars().count();
+
+        let mut tokenizer = Tokenizer::new(Box::new(VisuAlg {}));
+        tokenizer.tokenize(&code);
+
+        let elapsed = measure(&code);
+        println!(
+            "{:>10} {:>10} {:>12.2?} {:>10.1}",
+            chars,
+            tokenizer.tokens().len(),
+            elapsed,
+            elapsed.as_nanos() as f64 / chars as f64
+        );
+    }
+}
@@ -1 +1 @@
-pub mod window;
\ No newline at end of file
+pub mod window;
@@ -1,11 +1,66 @@
+use std::io;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
 use eframe::egui;
 
+use crate::interpreter::console::channel::{ChannelIo, ConsoleChannel, ConsoleEvent};
+use crate::interpreter::debug::breakpoint::{Breakpoint, Breakpoints};
+use crate::interpreter::debug::channel::{ChannelDebugger, DebugChannel};
+use crate::interpreter::debug::debugger::Debugger;
+use crate::interpreter::debug::handler::Command;
+use crate::interpreter::debug::state::{DebugState, VariableState};
+use crate::interpreter::encoding::SourceFormat;
+use crate::interpreter::interpreter::Interpreter;
+use crate::interpreter::language::Language;
+use crate::interpreter::languages;
+use crate::interpreter::random::SeededRandom;
+use crate::interpreter::runner;
+
+/// Program running in the background, talking to the window through a
+/// [`ConsoleChannel`]. The thread returns the rendered diagnostics on error.
+struct Execution {
+    console: ConsoleChannel,
+    /// Set when the program runs under the debugger.
+    debugger: Option<DebugChannel>,
+    handle: JoinHandle<Result<(), String>>,
+}
+
 #[derive(Default)]
 pub struct Window {
     enabled: bool,
     code: String,
 
     title: String,
+
+    /// File being edited, saved back in `format` when `keep_format` is set
+    /// and as plain UTF-8 otherwise.
+    path: Option<String>,
+    format: SourceFormat,
+    keep_format: bool,
+    status: String,
+    /// Path typed to open another file.
+    open_input: String,
+
+    /// Name of the language chosen by the user, detected from the file
+    /// and the code when `None`.
+    language: Option<String>,
+
+    /// Seed for `rand` and `randi`, typed by the user. Empty draws
+    /// different numbers on every run.
+    seed: String,
+
+    /// Lines the debugger stops at, kept between runs.
+    breakpoints: Breakpoints,
+    /// Breakpoint being typed, as `12` or `12 se i = 3`.
+    breakpoint_input: String,
+    /// Where the debugged program is stopped, `None` while it runs.
+    paused: Option<DebugState>,
+
+    output: String,
+    input: String,
+    waiting_input: bool,
+    execution: Option<Execution>,
 }
 
 impl Window {
@@ -16,10 +71,315 @@ impl Window {
     pub fn set_title(&mut self, title : &str) {
         self.title = String::from(title);
     }
+
+    pub fn set_code(&mut self, code : &str) {
+        self.code = String::from(code);
+    }
+
+    /// Force a language by name, see [`languages::by_name`], or detect it
+    /// with `None`.
+    pub fn set_language(&mut self, name:Option<&str>) {
+        self.language = name.map(String::from);
+    }
+
+    /// Load a file in any supported encoding for editing.
+    pub fn open(&mut self, path:&str) -> io::Result<()> {
+        let (code, format) = SourceFormat::decode(&std::fs::read(path)?);
+
+        self.code = code;
+        self.path = Some(String::from(path));
+        self.open_input = String::from(path);
+        self.format = format;
+        self.keep_format = true;
+
+        self.status = format!("aberto como {}", self.language().name());
+        if format.is_transcoded() {
+            self.status += &format!(", convertido de {} para edição", format.encoding().name());
+        }
+        Ok(())
+    }
+
+    /// Open the file typed or dropped on the window, reporting failure in
+    /// the status line.
+    fn open_file(&mut self, path:&str) {
+        if let Err(e) = self.open(path) {
+            self.status = format!("não foi possível abrir '{}': {}", path, e);
+        }
+    }
+
+    /// Path field to open a file from, the language detected from its
+    /// extension and content unless one is chosen.
+    fn file_bar(&mut self, ui:&mut egui::Ui) {
+        let running = self.is_running();
+
+        ui.horizontal(|ui| {
+            ui.label("arquivo");
+            let input = ui.add(egui::TextEdit::singleline(&mut self.open_input).hint_text("programa.alg ou programa.por"));
+            let entered = input.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+            if ui.add_enabled(!running, egui::Button::new("Abrir")).clicked() || (entered && !running) {
+                let path = self.open_input.trim().to_string();
+                if !path.is_empty() {
+                    self.open_file(&path);
+                }
+            }
+        });
+    }
+
+    fn save(&mut self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+
+        let format = if self.keep_format { self.format } else { SourceFormat::default() };
+        let result = std::fs::write(path, format.encode(&self.code));
+
+        self.status = match result {
+            Err(e) => format!("não foi possível salvar '{}': {}", path, e),
+            Ok(()) if !format.can_encode(&self.code) => format!(
+                "salvo em {}, mas alguns caracteres não existem nessa codificação e foram substituídos",
+                format.encoding().name()
+            ),
+            Ok(()) => format!("salvo em {}", format.encoding().name()),
+        };
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.execution.is_some()
+    }
+
+    /// Language the program is run in: the chosen one, else the detected.
+    fn language(&self) -> Box<dyn Language> {
+        self.language.as_deref()
+            .and_then(languages::by_name)
+            .unwrap_or_else(|| languages::detect(self.path.as_deref(), &self.code))
+    }
+
+    /// Run the program, under the debugger when `debug` is set. The
+    /// debugger stops at the first breakpoint, or before the first
+    /// statement when there is none.
+    fn start(&mut self, debug:bool) {
+        let mut random = match self.seed.trim() {
+            "" => SeededRandom::from_time(),
+            seed => match seed.parse() {
+                Ok(seed) => SeededRandom::new(seed),
+                Err(_) => {
+                    self.status = format!("semente inválida '{}', use um inteiro de 0 a {}", seed, u64::MAX);
+                    return;
+                }
+            },
+        };
+
+        let (mut io, console) = ChannelIo::new();
+        let code = self.code.clone();
+        let path = self.path.clone();
+        let chosen = self.language.clone();
+
+        let (handler, debugger) = match debug {
+            true => {
+                let (handler, channel) = ChannelDebugger::new();
+                (Some(handler), Some(channel))
+            },
+            false => (None, None),
+        };
+        let breakpoints = self.breakpoints.clone();
+
+        let spawned = thread::Builder::new().stack_size(runner::stack_size(runner::DEFAULT_MAX_DEPTH)).spawn(move || {
+            let language = chosen.as_deref()
+                .and_then(languages::by_name)
+                .unwrap_or_else(|| languages::detect(path.as_deref(), &code));
+            let mut interpreter = Interpreter::from_code(language, code);
+
+            let result = match handler {
+                Some(handler) => {
+                    let mut debugger = Debugger::new(Box::new(handler));
+                    if !breakpoints.is_empty() {
+                        debugger = debugger.with_breakpoints(breakpoints).starting_with(Command::Continue);
+                    }
+                    interpreter.debug_with(&mut io, &mut random, debugger)
+                },
+                None => interpreter.run_with_random(&mut io, &mut random),
+            };
+            result.map(|_| ()).map_err(|errors| interpreter.render(&errors))
+        });
+
+        self.output.clear();
+        self.waiting_input = false;
+        self.paused = None;
+        match spawned {
+            Ok(handle) => self.execution = Some(Execution { console, debugger, handle }),
+            Err(e) => self.status = format!("não foi possível executar: {}", e),
+        }
+    }
+
+    /// Signatures of the functions the program's language provides, each
+    /// described on hover.
+    fn builtin_hints(&self, ui:&mut egui::Ui) {
+        let language = self.language();
+
+        egui::CollapsingHeader::new(format!("Funções do {}", language.name())).show(ui, |ui| {
+            let builtins = language.builtins();
+            if builtins.is_empty() {
+                ui.label("nenhuma função disponível sem inclua");
+            }
+            for builtin in builtins {
+                ui.monospace(builtin.hint()).on_hover_text(builtin.description());
+            }
+        });
+    }
+
+    fn poll(&mut self) {
+        let Some(execution) = &self.execution else {
+            return;
+        };
+
+        for event in execution.console.poll() {
+            match event {
+                ConsoleEvent::Write(text) => self.output.push_str(&text),
+                ConsoleEvent::Clear => self.output.clear(),
+                ConsoleEvent::ReadRequest => self.waiting_input = true,
+            }
+        }
+
+        if let Some(state) = execution.debugger.as_ref().and_then(|d| d.poll()) {
+            self.paused = Some(state);
+        }
+
+        if execution.handle.is_finished() {
+            if let Some(execution) = self.execution.take() {
+                match execution.handle.join() {
+                    Ok(Err(errors)) => self.output += &format!("\n{}", errors),
+                    Err(_) => self.output += "\nerro interno ao executar o programa",
+                    Ok(Ok(())) => {},
+                }
+            }
+            self.waiting_input = false;
+            self.paused = None;
+        }
+    }
+
+    /// Give the debugged program `command`; any but `Pause` resumes it.
+    fn command(&mut self, command:Command) {
+        if let Some(channel) = self.execution.as_ref().and_then(|e| e.debugger.as_ref()) {
+            channel.send(command);
+        }
+        if command != Command::Pause {
+            self.paused = None;
+        }
+    }
+
+    /// Breakpoints to add and remove, sent to the program if it is being
+    /// debugged.
+    fn breakpoint_list(&mut self, ui:&mut egui::Ui) {
+        let mut changed = false;
+
+        ui.horizontal(|ui| {
+            ui.label("parada");
+            let input = ui.add(egui::TextEdit::singleline(&mut self.breakpoint_input).hint_text("12 se i = 3"));
+            let entered = input.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+            if ui.button("Adicionar").clicked() || entered {
+                match Breakpoint::parse(&self.breakpoint_input, self.language().as_ref()) {
+                    Ok(breakpoint) => {
+                        self.breakpoints.add(breakpoint);
+                        self.breakpoint_input.clear();
+                        changed = true;
+                    },
+                    Err(message) => self.status = message,
+                }
+            }
+        });
+
+        let mut removed = None;
+        ui.horizontal_wrapped(|ui| {
+            for breakpoint in self.breakpoints.list() {
+                ui.button(format!("linha {} ✖", breakpoint)).clicked().then(|| removed = Some(breakpoint.line()));
+            }
+        });
+        if let Some(line) = removed {
+            changed |= self.breakpoints.remove(line);
+        }
+
+        if changed {
+            if let Some(channel) = self.execution.as_ref().and_then(|e| e.debugger.as_ref()) {
+                channel.set_breakpoints(self.breakpoints.clone());
+            }
+        }
+    }
+
+    /// Controls of a debugged run, and where it is stopped.
+    fn debug_panel(&mut self, ui:&mut egui::Ui) {
+        let paused = self.paused.is_some();
+
+        ui.horizontal(|ui| {
+            let commands = [
+                ("Continuar", Command::Continue, paused),
+                ("Entrar", Command::StepInto, paused),
+                ("Pular", Command::StepOver, paused),
+                ("Sair", Command::StepOut, paused),
+                ("Pausar", Command::Pause, !paused),
+                ("Parar", Command::Stop, true),
+            ];
+            for (label, command, enabled) in commands {
+                ui.add_enabled(enabled, egui::Button::new(label)).clicked().then(|| self.command(command));
+            }
+        });
+
+        let Some(state) = &self.paused else {
+            return;
+        };
+
+        let source = self.code.lines().nth(state.line() - 1).unwrap_or_default().trim();
+        ui.label(format!("{}: linha {}", state.pause(), state.line()));
+        ui.monospace(source);
+
+        egui::CollapsingHeader::new("Chamadas").default_open(true).show(ui, |ui| {
+            for call in state.calls().iter().rev() {
+                ui.label(format!("{}, chamado na linha {}", call.name(), call.call().start().line()));
+            }
+            ui.label("programa principal");
+        });
+
+        if let Some(call) = state.calls().last() {
+            Self::variable_grid(ui, &format!("Locais de {}", call.name()), state.locals());
+        }
+        Self::variable_grid(ui, "Globais", state.globals());
+    }
+
+    fn variable_grid(ui:&mut egui::Ui, title:&str, variables:&[VariableState]) {
+        egui::CollapsingHeader::new(title).default_open(true).show(ui, |ui| {
+            egui::Grid::new(title).striped(true).show(ui, |ui| {
+                for variable in variables {
+                    for (name, value) in variable.elements() {
+                        ui.monospace(name);
+                        ui.label(variable.kind().to_string());
+                        ui.monospace(value.to_string());
+                        ui.end_row();
+                    }
+                }
+            });
+        });
+    }
+
+    fn submit_input(&mut self) {
+        if let Some(execution) = &self.execution {
+            self.output += &format!("{}\n", self.input);
+            execution.console.send_line(&self.input);
+        }
+        self.input.clear();
+        self.waiting_input = false;
+    }
 }
 
 impl eframe::App for Window {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.poll();
+
+        let dropped = ctx.input(|i| i.raw.dropped_files.iter().find_map(|f| f.path.clone()));
+        if let Some(path) = dropped.filter(|_| !self.is_running()) {
+            self.open_file(&path.to_string_lossy());
+        }
+
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.label("Teste de texto");
 
@@ -29,25 +389,84 @@ impl eframe::App for Window {
             );
 
             if self.enabled {
+                self.file_bar(ui);
                 ui.text_edit_multiline(&mut self.code);
 
-                ui.button("Submit").clicked().then(|| {
-                    println!("Clicked");
-                    self.code = String::from("");
+                let running = self.is_running();
+                ui.horizontal(|ui| {
+                    ui.add_enabled(!running, egui::Button::new("Executar")).clicked().then(|| {
+                        self.start(false);
+                    });
+                    ui.add_enabled(!running, egui::Button::new("Depurar")).clicked().then(|| {
+                        self.start(true);
+                    });
+
+                    ui.label("semente");
+                    ui.add(egui::TextEdit::singleline(&mut self.seed).desired_width(80.0).hint_text("aleatória"));
+
+                    egui::ComboBox::from_id_salt("linguagem")
+                        .selected_text(self.language.as_deref().unwrap_or("automática"))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.language, None, "automática");
+                            for name in languages::names() {
+                                ui.selectable_value(&mut self.language, Some(name.to_string()), name);
+                            }
+                        });
+
+                    if self.path.is_some() {
+                        ui.button("Salvar").clicked().then(|| {
+                            self.save();
+                        });
+
+                        if self.format.is_transcoded() {
+                            let label = format!("Manter {}", self.format.encoding().name());
+                            ui.checkbox(&mut self.keep_format, label);
+                        }
+                    }
+                });
+
+                if !self.status.is_empty() {
+                    ui.label(&self.status);
+                }
+
+                self.builtin_hints(ui);
+                self.breakpoint_list(ui);
+
+                if self.execution.as_ref().is_some_and(|e| e.debugger.is_some()) {
+                    self.debug_panel(ui);
+                }
+
+                ui.separator();
+
+                egui::ScrollArea::vertical().stick_to_bottom(true).show(ui, |ui| {
+                    ui.monospace(&self.output);
                 });
+
+                if self.waiting_input {
+                    let response = ui.text_edit_singleline(&mut self.input);
+                    response.request_focus();
+
+                    if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        self.submit_input();
+                    }
+                }
             }
         });
+
+        if self.is_running() {
+            ctx.request_repaint_after(Duration::from_millis(50));
+        }
     }
 }
 
 pub fn run_window(window: Window) -> Result<(), eframe::Error> {
     eframe::run_native(
-        &window.title,
+        &window.title.clone(),
         eframe::NativeOptions::default(),
         Box::new(
             |_cc| std::result::Result::Ok(
-                Box::new(Window::default())
+                Box::new(window)
             )
         ),
     )
-}
\ No newline at end of file
+}
@@ -0,0 +1,205 @@
+use crate::interpreter::runner::MAX_DEPTH_LIMIT;
+
+pub const USAGE:&str = "\
+uso: visual_tho <comando> [opções]
+
+comandos:
+  run <arquivo>       executa o programa
+  debug <arquivo>     executa o programa passo a passo, com comandos do
+                      depurador digitados no terminal ('ajuda' lista todos)
+  tokens <arquivo>    lista os tokens com posição e tipo
+  ast <arquivo>       mostra a árvore sintática
+  check <arquivo>     só verifica o programa, sai com erro se houver problemas
+  gui [arquivo]       abre a janela, com o arquivo se informado
+  help                mostra esta mensagem
+
+opções:
+  --lang <nome>         linguagem do programa: visualg ou portugol
+                        (padrão: detectada pela extensão e pelo conteúdo)
+  --stdin-file <arq>    respostas para o leia, uma por linha
+  --max-depth <n>       chamadas de procedimento e funcao em andamento ao
+                        mesmo tempo antes de parar o programa, até
+                        10000 (padrão: 256)
+  --seed <n>            semente dos números de rand e randi, para repetir
+                        a mesma execução (padrão: tirada do relógio)
+  --record-random <arq> grava no arquivo os números aleatórios sorteados
+  --replay-random <arq> usa os números gravados por --record-random, na
+                        mesma ordem
+  --break <linha>       ponto de parada do debug, pode ser repetido; com
+                        condição, '--break \"12 se i = 3\"' só para quando
+                        ela é verdadeira
+  --json                saída em JSON, para ferramentas
+";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+    Run { file:String },
+    Debug { file:String },
+    Tokens { file:String },
+    Ast { file:String },
+    Check { file:String },
+    Gui { file:Option<String> },
+    Help,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Args {
+    command: Command,
+    language: Option<String>,
+    stdin_file: Option<String>,
+    max_depth: Option<usize>,
+    seed: Option<u64>,
+    record_random: Option<String>,
+    replay_random: Option<String>,
+    breakpoints: Vec<String>,
+    json: bool,
+}
+
+impl Command {
+    pub fn name(&self) -> &str {
+        match self {
+            Command::Run { .. } => "run",
+            Command::Debug { .. } => "debug",
+            Command::Tokens { .. } => "tokens",
+            Command::Ast { .. } => "ast",
+            Command::Check { .. } => "check",
+            Command::Gui { .. } => "gui",
+            Command::Help => "help",
+        }
+    }
+}
+
+impl Args {
+    /// Parse the arguments after the program name. With none, the window
+    /// is opened.
+    pub fn parse(args:impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut language = None;
+        let mut stdin_file = None;
+        let mut max_depth = None;
+        let mut seed = None;
+        let mut record_random = None;
+        let mut replay_random = None;
+        let mut breakpoints = vec![];
+        let mut json = false;
+        let mut positional = vec![];
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--lang" => language = Some(Self::value(&arg, args.next())?),
+                "--stdin-file" => stdin_file = Some(Self::value(&arg, args.next())?),
+                "--max-depth" => {
+                    let value = Self::value(&arg, args.next())?;
+                    match value.parse() {
+                        Ok(depth) if depth > 0 && depth <= MAX_DEPTH_LIMIT => max_depth = Some(depth),
+                        _ => return Err(format!(
+                            "valor inválido '{}' para '{}', use um inteiro de 1 a {}",
+                            value, arg, MAX_DEPTH_LIMIT
+                        )),
+                    }
+                },
+                "--seed" => {
+                    let value = Self::value(&arg, args.next())?;
+                    seed = Some(value.parse().map_err(|_| {
+                        format!("valor inválido '{}' para '{}', use um inteiro de 0 a {}", value, arg, u64::MAX)
+                    })?);
+                },
+                "--record-random" => record_random = Some(Self::value(&arg, args.next())?),
+                "--replay-random" => replay_random = Some(Self::value(&arg, args.next())?),
+                "--break" => breakpoints.push(Self::value(&arg, args.next())?),
+                "--json" => json = true,
+                "-h" | "--help" => positional.insert(0, String::from("help")),
+                _ if arg.starts_with("--") => return Err(format!("opção desconhecida '{}'", arg)),
+                _ => positional.push(arg),
+            }
+        }
+
+        if seed.is_some() && replay_random.is_some() {
+            return Err(String::from("use '--seed' ou '--replay-random', não os dois"));
+        }
+
+        let mut positional = positional.into_iter();
+        let name = positional.next();
+        let file = positional.next();
+
+        if let Some(extra) = positional.next() {
+            return Err(format!("argumento inesperado '{}'", extra));
+        }
+
+        let needs_file = |file:Option<String>| {
+            file.ok_or_else(|| format!("o comando '{}' precisa de um arquivo", name.as_deref().unwrap_or_default()))
+        };
+
+        let command = match name.as_deref() {
+            None | Some("gui") => Command::Gui { file },
+            Some("run") => Command::Run { file: needs_file(file)? },
+            Some("debug") => Command::Debug { file: needs_file(file)? },
+            Some("tokens") => Command::Tokens { file: needs_file(file)? },
+            Some("ast") => Command::Ast { file: needs_file(file)? },
+            Some("check") => Command::Check { file: needs_file(file)? },
+            Some("help") => Command::Help,
+            Some(other) => return Err(format!("comando desconhecido '{}'", other)),
+        };
+
+        if json && matches!(command, Command::Debug { .. }) {
+            return Err(String::from("o comando 'debug' é interativo e não tem saída em JSON"));
+        }
+        if !breakpoints.is_empty() && !matches!(command, Command::Debug { .. }) {
+            return Err(String::from("'--break' só vale para o comando 'debug'"));
+        }
+
+        Ok(Self { command, language, stdin_file, max_depth, seed, record_random, replay_random, breakpoints, json })
+    }
+
+    fn value(option:&str, value:Option<String>) -> Result<String, String> {
+        value.ok_or_else(|| format!("a opção '{}' precisa de um valor", option))
+    }
+
+    pub fn command(&self) -> &Command {
+        &self.command
+    }
+
+    /// Name of the language, see [`crate::interpreter::languages::by_name`].
+    /// Detected from the file when not given.
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
+    /// Print a [`super::json::Report`] instead of human-readable text.
+    pub fn is_json(&self) -> bool {
+        self.json
+    }
+
+    /// Limit of nested calls, see [`crate::interpreter::runner::Runner::with_max_depth`].
+    pub fn max_depth(&self) -> Option<usize> {
+        self.max_depth
+    }
+
+    /// Seed of the numbers `rand` and `randi` draw, see
+    /// [`crate::interpreter::random::SeededRandom`].
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// File to write every random number of the run to.
+    pub fn record_random(&self) -> Option<&str> {
+        self.record_random.as_deref()
+    }
+
+    /// File of recorded random numbers to run with instead of drawing new
+    /// ones.
+    pub fn replay_random(&self) -> Option<&str> {
+        self.replay_random.as_deref()
+    }
+
+    /// Breakpoints of `debug` as written, see
+    /// [`crate::interpreter::debug::breakpoint::Breakpoint::parse`].
+    pub fn breakpoints(&self) -> &Vec<String> {
+        &self.breakpoints
+    }
+
+    /// File whose lines answer `leia`, instead of the terminal.
+    pub fn stdin_file(&self) -> Option<&str> {
+        self.stdin_file.as_deref()
+    }
+}
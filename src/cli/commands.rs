@@ -0,0 +1,296 @@
+use std::io::{self, IsTerminal, Read};
+use std::process::ExitCode;
+use std::thread;
+
+use crate::app;
+use crate::interpreter::console::handler::IoHandler;
+use crate::interpreter::console::memory::MemoryIo;
+use crate::interpreter::console::scripted::ScriptedIo;
+use crate::interpreter::console::terminal::TerminalIo;
+use crate::interpreter::debug::breakpoint::{Breakpoint, Breakpoints};
+use crate::interpreter::debug::debugger::Debugger;
+use crate::interpreter::debug::handler::Command as DebugCommand;
+use crate::interpreter::debug::terminal::TerminalDebugger;
+use crate::interpreter::diagnostics::codes;
+use crate::interpreter::diagnostics::diagnostic::Diagnostic;
+use crate::interpreter::interpreter::{Interpreter, StageResult};
+use crate::interpreter::languages;
+use crate::interpreter::random::{RandomSource, Recorder, Replay, SeededRandom};
+use crate::interpreter::runner::{self, RunOutcome, DEFAULT_MAX_DEPTH};
+use crate::interpreter::tokens::schema::TokenTypes;
+use crate::interpreter::tokens::span::Span;
+
+use super::args::{Args, Command, USAGE};
+use super::json::Report;
+
+/// Exit code for programs with errors.
+const FAILURE:u8 = 1;
+/// Exit code for bad arguments.
+pub const USAGE_ERROR:u8 = 2;
+
+/// Run the command and report through stdout and stderr.
+pub fn execute(args:&Args) -> ExitCode {
+    let file = match args.command() {
+        Command::Help => {
+            print!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        },
+        Command::Gui { file } => return gui(file.as_deref(), args.language()),
+        Command::Run { file } | Command::Debug { file } | Command::Tokens { file } | Command::Ast { file } | Command::Check { file } => file,
+    };
+
+    // The stages run on a thread with the native stack the call limit needs.
+    let depth = args.max_depth().unwrap_or(DEFAULT_MAX_DEPTH);
+    let spawned = thread::Builder::new().stack_size(runner::stack_size(depth)).spawn({
+        let args = args.clone();
+        let file = file.clone();
+        move || stages(&args, &file)
+    });
+
+    match spawned.map(|handle| handle.join()) {
+        Ok(Ok(code)) => code,
+        Ok(Err(_)) => ExitCode::from(FAILURE),
+        Err(e) => {
+            eprintln!("não foi possível iniciar a execução: {}", e);
+            ExitCode::from(FAILURE)
+        },
+    }
+}
+
+/// Load `file` and run the stages of the command on it.
+fn stages(args:&Args, file:&str) -> ExitCode {
+    let mut interpreter = match args.language() {
+        Some(name) => match languages::by_name(name) {
+            Some(language) => Interpreter::from_file(language, file.to_string()),
+            None => return unknown_language(name),
+        },
+        None => Interpreter::for_file(file.to_string()),
+    };
+    if let Some(depth) = args.max_depth() {
+        interpreter.set_max_depth(depth);
+    }
+
+    if args.is_json() {
+        return json(&mut interpreter, args, file);
+    }
+    if let Command::Debug { .. } = args.command() {
+        return debug(&mut interpreter, args);
+    }
+
+    let result = match args.command() {
+        Command::Run { .. } => run(&mut interpreter, args),
+        Command::Tokens { .. } => tokens(&mut interpreter),
+        Command::Ast { .. } => interpreter.parse().map(|program| println!("{:#?}", program)),
+        _ => interpreter.check().map(|_| ()),
+    };
+
+    report(&interpreter, result)
+}
+
+fn run(interpreter:&mut Interpreter, args:&Args) -> StageResult<()> {
+    let outcome = match args.stdin_file() {
+        Some(path) => run_random(interpreter, &mut ScriptedIo::new(&read_input(path)?), args, None),
+        None => run_random(interpreter, &mut TerminalIo, args, None),
+    };
+    outcome.map(|_| ())
+}
+
+/// Run under a [`TerminalDebugger`], stopping at the first `--break`, or
+/// before the first statement when there is none.
+fn debug(interpreter:&mut Interpreter, args:&Args) -> ExitCode {
+    if let Err(errors) = interpreter.check() {
+        return report(interpreter, Err(errors));
+    }
+    let Some(language) = languages::instance_of(interpreter.language()) else {
+        return unknown_language(interpreter.language().name());
+    };
+
+    let mut breakpoints = Breakpoints::new();
+    for text in args.breakpoints() {
+        match Breakpoint::parse(text, language.as_ref()) {
+            Ok(breakpoint) => breakpoints.add(breakpoint),
+            Err(message) => {
+                eprintln!("erro: ponto de parada '{}' inválido: {}", text, message.trim_end());
+                return ExitCode::from(USAGE_ERROR);
+            },
+        }
+    }
+
+    let code = interpreter.code().unwrap_or_default().to_string();
+    let mut debugger = Debugger::new(Box::new(TerminalDebugger::new(language, &code)));
+    if !breakpoints.is_empty() {
+        debugger = debugger.with_breakpoints(breakpoints).starting_with(DebugCommand::Continue);
+    }
+
+    let result = match args.stdin_file() {
+        Some(path) => read_input(path).and_then(|input| {
+            run_random(interpreter, &mut ScriptedIo::new(&input), args, Some(debugger))
+        }),
+        None => run_random(interpreter, &mut TerminalIo, args, Some(debugger)),
+    };
+    report(interpreter, result.map(|_| ()))
+}
+
+/// Run with the random numbers the options ask for: replayed from
+/// `--replay-random`, else drawn from `--seed` or the clock, and written to
+/// `--record-random` once the run ends, even in error.
+fn run_random(
+    interpreter:&mut Interpreter,
+    io:&mut dyn IoHandler,
+    args:&Args,
+    debugger:Option<Debugger>
+) -> StageResult<RunOutcome> {
+    let mut source:Box<dyn RandomSource> = match args.replay_random() {
+        Some(path) => Box::new(read_replay(path)?),
+        None => Box::new(args.seed().map_or_else(SeededRandom::from_time, SeededRandom::new)),
+    };
+
+    let execute = |random:&mut dyn RandomSource| match debugger {
+        Some(debugger) => interpreter.debug_with(io, random, debugger),
+        None => interpreter.run_with_random(io, random),
+    };
+
+    let Some(path) = args.record_random() else {
+        return execute(source.as_mut());
+    };
+
+    let mut recorder = Recorder::new(source.as_mut());
+    let result = execute(&mut recorder);
+
+    if let Err(e) = std::fs::write(path, recorder.log()) {
+        let mut errors = result.err().unwrap_or_default();
+        errors.push(Diagnostic::error(
+            codes::FILE_UNWRITABLE,
+            format!("não foi possível gravar os números aleatórios em '{}': {}", path, e),
+            Span::default()
+        ));
+        return Err(errors);
+    }
+    result
+}
+
+fn read_replay(path:&str) -> StageResult<Replay> {
+    let unreadable = |message:String| vec![Diagnostic::error(
+        codes::FILE_UNREADABLE,
+        format!("não foi possível ler a gravação '{}': {}", path, message),
+        Span::default()
+    )];
+
+    let log = std::fs::read_to_string(path).map_err(|e| unreadable(e.to_string()))?;
+    Replay::parse(&log).map_err(unreadable)
+}
+
+fn read_input(path:&str) -> StageResult<String> {
+    std::fs::read_to_string(path).map_err(|e| vec![Diagnostic::error(
+        codes::FILE_UNREADABLE,
+        format!("não foi possível ler o arquivo de entrada '{}': {}", path, e),
+        Span::default()
+    )])
+}
+
+/// Print a [`Report`] instead of human-readable text. Runs capture their
+/// output, reading `leia` answers from `--stdin-file` or, when piped, from
+/// stdin.
+fn json(interpreter:&mut Interpreter, args:&Args, file:&str) -> ExitCode {
+    let mut output = String::new();
+    let mut outcome = None;
+
+    let result = match args.command() {
+        Command::Run { .. } => {
+            let input = match args.stdin_file() {
+                Some(path) => read_input(path),
+                None => Ok(piped_stdin()),
+            };
+            input.and_then(|input| {
+                let mut io = MemoryIo::new(&input);
+                let result = run_random(interpreter, &mut io, args, None);
+                output = io.output().to_string();
+                result.map(|finished| outcome = Some(finished))
+            })
+        },
+        Command::Tokens { .. } => interpreter.tokenize().map(|_| ()),
+        Command::Ast { .. } => interpreter.parse().map(|_| ()),
+        _ => interpreter.check().map(|_| ()),
+    };
+
+    let errors = result.err().unwrap_or_default();
+    let exit_code = if errors.is_empty() { 0 } else { FAILURE };
+    let diagnostics = interpreter.warnings().iter().chain(&errors).collect();
+
+    let report = Report::new(args.command().name(), file, exit_code, diagnostics);
+    let report = match args.command() {
+        Command::Tokens { .. } => report.with_tokens(interpreter.tokens().map(|t| t.as_slice())),
+        Command::Ast { .. } => report.with_ast(interpreter.program()),
+        Command::Run { .. } => report.with_run(&output, outcome.as_ref()),
+        _ => report,
+    };
+
+    println!("{}", report.to_json());
+    ExitCode::from(exit_code)
+}
+
+/// Stdin when it is not a terminal, so a JSON run never waits on the user.
+fn piped_stdin() -> String {
+    let mut input = String::new();
+    if !io::stdin().is_terminal() {
+        let _ = io::stdin().read_to_string(&mut input);
+    }
+    input
+}
+
+fn tokens(interpreter:&mut Interpreter) -> StageResult<()> {
+    let result = interpreter.tokenize().map(|_| ());
+
+    // Print what was tokenized even when there are lexical errors.
+    for token in interpreter.tokens().into_iter().flatten() {
+        if token.schema() == &TokenTypes::None && token.value().trim().is_empty() {
+            continue;
+        }
+        println!("{}\t{}\t{:?}", token.span(), token.schema(), token.value());
+    }
+    result
+}
+
+fn report(interpreter:&Interpreter, result:StageResult<()>) -> ExitCode {
+    eprint!("{}", interpreter.render(interpreter.warnings()));
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(errors) => {
+            eprint!("{}", interpreter.render(&errors));
+            ExitCode::from(FAILURE)
+        }
+    }
+}
+
+fn unknown_language(name:&str) -> ExitCode {
+    eprintln!("linguagem desconhecida '{}', use uma de: {}", name, languages::names().join(", "));
+    ExitCode::from(USAGE_ERROR)
+}
+
+fn gui(file:Option<&str>, language:Option<&str>) -> ExitCode {
+    let mut window = app::window::Window::default();
+    window.set_title("VisualTho");
+
+    if let Some(name) = language {
+        if languages::by_name(name).is_none() {
+            return unknown_language(name);
+        }
+        window.set_language(Some(name));
+    }
+
+    if let Some(file) = file {
+        if let Err(e) = window.open(file) {
+            eprintln!("não foi possível abrir '{}': {}", file, e);
+            return ExitCode::from(FAILURE);
+        }
+    }
+
+    match app::window::run_window(window) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("erro ao abrir a janela: {}", e);
+            ExitCode::from(FAILURE)
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use serde::Serialize;
+
+use crate::interpreter::ast::program::Program;
+use crate::interpreter::diagnostics::diagnostic::Diagnostic;
+use crate::interpreter::runner::RunOutcome;
+use crate::interpreter::tokens::token::Token;
+use crate::interpreter::value::Value;
+
+/// Bumped whenever a field is renamed or removed, or its meaning changes.
+/// Adding fields keeps the version.
+pub const FORMAT_VERSION:u32 = 1;
+
+/// Everything a command produced, printed as a single JSON document.
+#[derive(Serialize)]
+pub struct Report<'a> {
+    version: u32,
+    command: &'a str,
+    file: &'a str,
+    success: bool,
+    exit_code: u8,
+    diagnostics: Vec<&'a Diagnostic>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tokens: Option<&'a [Token]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ast: Option<&'a Program>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    run: Option<RunReport<'a>>,
+}
+
+impl<'a> Report<'a> {
+    pub fn new(command:&'a str, file:&'a str, exit_code:u8, diagnostics:Vec<&'a Diagnostic>) -> Self {
+        Self {
+            version: FORMAT_VERSION,
+            command,
+            file,
+            success: exit_code == 0,
+            exit_code,
+            diagnostics,
+            tokens: None,
+            ast: None,
+            run: None,
+        }
+    }
+
+    pub fn with_tokens(mut self, tokens:Option<&'a [Token]>) -> Self {
+        self.tokens = tokens;
+        self
+    }
+
+    pub fn with_ast(mut self, ast:Option<&'a Program>) -> Self {
+        self.ast = ast;
+        self
+    }
+
+    pub fn with_run(mut self, output:&'a str, outcome:Option<&'a RunOutcome>) -> Self {
+        let variables = outcome.map(|outcome| {
+            outcome.variables().iter()
+                .map(|(name, value)| VariableReport { name, value })
+                .collect()
+        });
+        self.run = Some(RunReport { output, variables });
+        self
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("report fields always serialize")
+    }
+}
+
+/// `variables` is `null` when the run stopped on an error.
+#[derive(Serialize)]
+struct RunReport<'a> {
+    output: &'a str,
+    variables: Option<Vec<VariableReport<'a>>>,
+}
+
+#[derive(Serialize)]
+struct VariableReport<'a> {
+    name: &'a str,
+    value: &'a Value,
+}
@@ -0,0 +1,127 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::interpreter::tokens::span::Span;
+
+use super::types::Identifier;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Expression {
+    pub kind: ExpressionKind,
+    pub span: Span,
+}
+
+impl Expression {
+    pub fn new(kind:ExpressionKind, span:Span) -> Self {
+        Self { kind, span }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExpressionKind {
+    Literal(Literal),
+    Variable(Identifier),
+    /// Element of an array, one index per dimension.
+    Index {
+        name: Identifier,
+        indices: Vec<Expression>,
+    },
+    Call {
+        name: Identifier,
+        arguments: Vec<Expression>,
+    },
+    Unary {
+        operator: UnaryOperator,
+        operand: Box<Expression>,
+    },
+    Binary {
+        operator: BinaryOperator,
+        left: Box<Expression>,
+        right: Box<Expression>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Literal {
+    Integer(i64),
+    Real(f64),
+    String(String),
+    Boolean(bool),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnaryOperator {
+    Negate,
+    Plus,
+    Not,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    /// `div` or `\`, truncating integer division.
+    IntegerDivide,
+    /// `mod` or `%`.
+    Modulo,
+    /// `/` of languages where dividing two integers truncates, like
+    /// Portugol Studio; real otherwise.
+    Quotient,
+    Power,
+
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+
+    And,
+    Or,
+    Xor,
+}
+
+impl fmt::Display for UnaryOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(
+            match self {
+                UnaryOperator::Negate => "-",
+                UnaryOperator::Plus => "+",
+                UnaryOperator::Not => "nao",
+            }
+        )
+    }
+}
+
+impl fmt::Display for BinaryOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(
+            match self {
+                BinaryOperator::Add => "+",
+                BinaryOperator::Subtract => "-",
+                BinaryOperator::Multiply => "*",
+                BinaryOperator::Divide => "/",
+                BinaryOperator::IntegerDivide => "div",
+                BinaryOperator::Modulo => "mod",
+                BinaryOperator::Quotient => "/",
+                BinaryOperator::Power => "^",
+                BinaryOperator::Equal => "=",
+                BinaryOperator::NotEqual => "<>",
+                BinaryOperator::Less => "<",
+                BinaryOperator::LessEqual => "<=",
+                BinaryOperator::Greater => ">",
+                BinaryOperator::GreaterEqual => ">=",
+                BinaryOperator::And => "e",
+                BinaryOperator::Or => "ou",
+                BinaryOperator::Xor => "xou",
+            }
+        )
+    }
+}
@@ -0,0 +1,4 @@
+pub mod program;
+pub mod statement;
+pub mod expression;
+pub mod types;
@@ -0,0 +1,50 @@
+use serde::Serialize;
+
+use crate::interpreter::tokens::span::Span;
+
+use super::statement::Statement;
+use super::types::{Identifier, TypeSpec};
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Program {
+    pub name: Option<String>,
+    pub declarations: Vec<VarDeclaration>,
+    pub subprograms: Vec<Subprogram>,
+    pub body: Vec<Statement>,
+    pub span: Span,
+}
+
+/// `a, b, c: inteiro`
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct VarDeclaration {
+    pub names: Vec<Identifier>,
+    pub type_spec: TypeSpec,
+    pub span: Span,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubprogramKind {
+    Procedimento,
+    Funcao,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Subprogram {
+    pub kind: SubprogramKind,
+    pub name: Identifier,
+    pub parameters: Vec<Parameter>,
+    pub return_type: Option<TypeSpec>,
+    pub declarations: Vec<VarDeclaration>,
+    pub body: Vec<Statement>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Parameter {
+    pub name: Identifier,
+    pub type_spec: TypeSpec,
+    /// Declared with `var`, passed by reference.
+    pub by_reference: bool,
+    pub span: Span,
+}
@@ -0,0 +1,124 @@
+use serde::Serialize;
+
+use crate::interpreter::tokens::span::Span;
+
+use super::expression::Expression;
+use super::types::Identifier;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Statement {
+    pub kind: StatementKind,
+    pub span: Span,
+}
+
+impl Statement {
+    pub fn new(kind:StatementKind, span:Span) -> Self {
+        Self { kind, span }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatementKind {
+    Assign {
+        target: Target,
+        value: Expression,
+    },
+
+    /// `escreva`/`escreval`, `newline` is set for the latter.
+    Write {
+        arguments: Vec<WriteArgument>,
+        newline: bool,
+    },
+
+    Read {
+        targets: Vec<Target>,
+    },
+
+    /// Procedure call used as a statement.
+    Call {
+        name: Identifier,
+        arguments: Vec<Expression>,
+    },
+
+    If {
+        condition: Expression,
+        then_branch: Vec<Statement>,
+        else_branch: Option<Vec<Statement>>,
+    },
+
+    While {
+        condition: Expression,
+        body: Vec<Statement>,
+    },
+
+    For {
+        variable: Identifier,
+        start: Box<Expression>,
+        end: Box<Expression>,
+        step: Option<Box<Expression>>,
+        body: Vec<Statement>,
+    },
+
+    /// `repita ... ate <condition>`, or `repita ... fimrepita` when the
+    /// condition is absent.
+    Repeat {
+        body: Vec<Statement>,
+        condition: Option<Expression>,
+    },
+
+    Choose {
+        subject: Expression,
+        cases: Vec<Case>,
+        default: Option<Vec<Statement>>,
+    },
+
+    Return {
+        value: Option<Expression>,
+    },
+
+    Break,
+}
+
+/// Variable, or element of an array, written by an assignment or `leia`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Target {
+    #[serde(flatten)]
+    pub name: Identifier,
+    /// Empty unless the target is an array element.
+    pub indices: Vec<Expression>,
+}
+
+impl Target {
+    pub fn new(name:Identifier, indices:Vec<Expression>) -> Self {
+        Self { name, indices }
+    }
+
+    /// From the name to the last index.
+    pub fn span(&self) -> Span {
+        self.indices.last().map_or(self.name.span, |index| self.name.span.join(&index.span))
+    }
+}
+
+/// Value written by `escreva`, with the optional `:width:precision` format.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct WriteArgument {
+    pub value: Expression,
+    pub width: Option<Expression>,
+    pub precision: Option<Expression>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Case {
+    pub labels: Vec<CaseLabel>,
+    pub body: Vec<Statement>,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaseLabel {
+    Value(Expression),
+    Range(Expression, Expression),
+}
@@ -0,0 +1,109 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::interpreter::tokens::span::Span;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Identifier {
+    pub name: String,
+    pub span: Span,
+}
+
+impl Identifier {
+    pub fn new(name:String, span:Span) -> Self {
+        Self { name, span }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TypeKind {
+    Inteiro,
+    Real,
+    Caractere,
+    Logico,
+}
+
+impl TypeKind {
+    pub fn from_name(name:&str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "inteiro" => Some(TypeKind::Inteiro),
+            "real" | "numerico" => Some(TypeKind::Real),
+            "caractere" | "caracter" | "literal" => Some(TypeKind::Caractere),
+            "logico" => Some(TypeKind::Logico),
+            _ => None
+        }
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, TypeKind::Inteiro | TypeKind::Real)
+    }
+
+    /// Whether a value of type `value` can be stored in a variable of this
+    /// type; `inteiro` is promoted to `real`.
+    pub fn accepts(&self, value:TypeKind) -> bool {
+        *self == value || (*self == TypeKind::Real && value == TypeKind::Inteiro)
+    }
+}
+
+impl fmt::Display for TypeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(
+            match self {
+                TypeKind::Inteiro => "inteiro",
+                TypeKind::Real => "real",
+                TypeKind::Caractere => "caractere",
+                TypeKind::Logico => "logico",
+            }
+        )
+    }
+}
+
+/// Index range of one array dimension, as `1..10`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Bounds {
+    pub lower: i64,
+    pub upper: i64,
+    pub span: Span,
+}
+
+impl Bounds {
+    /// Number of indices in the range, zero when it is empty.
+    pub fn len(&self) -> usize {
+        usize::try_from(self.upper.saturating_sub(self.lower).saturating_add(1)).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.upper < self.lower
+    }
+
+    pub fn contains(&self, index:i64) -> bool {
+        (self.lower..=self.upper).contains(&index)
+    }
+}
+
+impl fmt::Display for Bounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.lower, self.upper)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TypeSpec {
+    /// Type of the value, or of each element of an array.
+    pub kind: TypeKind,
+    /// One range per dimension of an array, empty for other variables.
+    pub dimensions: Vec<Bounds>,
+    pub span: Span,
+}
+
+impl TypeSpec {
+    pub fn scalar(kind:TypeKind, span:Span) -> Self {
+        Self { kind, dimensions: vec![], span }
+    }
+
+    pub fn is_array(&self) -> bool {
+        !self.dimensions.is_empty()
+    }
+}
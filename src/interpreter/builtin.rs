@@ -0,0 +1,46 @@
+use super::ast::types::TypeKind;
+use super::random::RandomSource;
+use super::semantic::symbols::{ParameterType, Signature};
+use super::value::Value;
+
+/// A function the language provides without a declaration, as `raizq` or
+/// `randi`.
+pub trait Builtin {
+    fn name(&self) -> &str;
+
+    /// Name and type of each parameter. A `real` parameter also takes an
+    /// `inteiro`.
+    fn parameters(&self) -> Vec<(&str, TypeKind)>;
+
+    fn return_type(&self) -> TypeKind;
+
+    /// What the function computes, in a sentence shown with the hint.
+    fn description(&self) -> &str;
+
+    /// Result for arguments already checked against
+    /// [`Builtin::parameters`], or the message of why they are invalid, as
+    /// for `raizq(-1)`.
+    fn call(&self, arguments:&[Value], random:&mut dyn RandomSource) -> Result<Value, String>;
+
+    /// What the checker verifies calls against.
+    fn signature(&self) -> Signature {
+        let parameters = self.parameters().into_iter()
+            .map(|(_, kind)| ParameterType { kind, by_reference: false, dimensions: 0 })
+            .collect();
+        Signature::new(self.name(), parameters, Some(self.return_type()))
+    }
+
+    /// Declaration as VisuAlg would write it, as
+    /// `copia(c: caractere; p: inteiro; n: inteiro): caractere`.
+    fn hint(&self) -> String {
+        let parameters:Vec<String> = self.parameters().into_iter()
+            .map(|(name, kind)| format!("{}: {}", name, kind))
+            .collect();
+
+        if parameters.is_empty() {
+            format!("{}: {}", self.name(), self.return_type())
+        } else {
+            format!("{}({}): {}", self.name(), parameters.join("; "), self.return_type())
+        }
+    }
+}
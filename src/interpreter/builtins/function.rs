@@ -0,0 +1,74 @@
+use crate::interpreter::ast::types::TypeKind;
+use crate::interpreter::builtin::Builtin;
+use crate::interpreter::random::RandomSource;
+use crate::interpreter::value::Value;
+
+type Body = fn(&[Value], &mut dyn RandomSource) -> Result<Value, String>;
+
+/// A [`Builtin`] described by plain data and a function pointer, as every
+/// standard one is.
+pub struct Function {
+    name: &'static str,
+    parameters: &'static [(&'static str, TypeKind)],
+    return_type: TypeKind,
+    description: &'static str,
+    body: Body,
+}
+
+impl Function {
+    pub fn new(
+        name:&'static str,
+        parameters:&'static [(&'static str, TypeKind)],
+        return_type:TypeKind,
+        description:&'static str,
+        body:Body
+    ) -> Self {
+        Self { name, parameters, return_type, description, body }
+    }
+
+    /// Boxed, ready for a library list.
+    pub fn boxed(self) -> Box<dyn Builtin> {
+        Box::new(self)
+    }
+}
+
+impl Builtin for Function {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn parameters(&self) -> Vec<(&str, TypeKind)> {
+        self.parameters.to_vec()
+    }
+
+    fn return_type(&self) -> TypeKind {
+        self.return_type
+    }
+
+    fn description(&self) -> &str {
+        self.description
+    }
+
+    fn call(&self, arguments:&[Value], random:&mut dyn RandomSource) -> Result<Value, String> {
+        (self.body)(arguments, random)
+    }
+}
+
+/// Numeric argument, either `inteiro` or `real`.
+pub fn number(arguments:&[Value], index:usize) -> f64 {
+    arguments.get(index).and_then(|v| v.as_number()).unwrap_or_default()
+}
+
+pub fn integer(arguments:&[Value], index:usize) -> i64 {
+    match arguments.get(index) {
+        Some(Value::Inteiro(value)) => *value,
+        _ => 0,
+    }
+}
+
+pub fn text(arguments:&[Value], index:usize) -> &str {
+    match arguments.get(index) {
+        Some(Value::Caractere(value)) => value,
+        _ => "",
+    }
+}
@@ -0,0 +1,72 @@
+use crate::interpreter::ast::types::TypeKind::{Inteiro, Real};
+use crate::interpreter::builtin::Builtin;
+use crate::interpreter::value::Value;
+
+use super::function::{number, Function};
+
+/// Arithmetic and trigonometry. Angles are in radians.
+pub fn functions() -> Vec<Box<dyn Builtin>> {
+    vec![
+        Function::new("abs", &[("valor", Real)], Real, "valor absoluto", |a, _| {
+            finite("abs", number(a, 0).abs())
+        }).boxed(),
+        Function::new("raizq", &[("valor", Real)], Real, "raiz quadrada", |a, _| {
+            let value = number(a, 0);
+            if value < 0.0 {
+                return Err(format!("raizq de número negativo: {}", value));
+            }
+            finite("raizq", value.sqrt())
+        }).boxed(),
+        Function::new("quad", &[("valor", Real)], Real, "valor elevado ao quadrado", |a, _| {
+            finite("quad", number(a, 0).powi(2))
+        }).boxed(),
+        Function::new("exp", &[("base", Real), ("expoente", Real)], Real, "base elevada ao expoente", |a, _| {
+            finite("exp", number(a, 0).powf(number(a, 1)))
+        }).boxed(),
+        Function::new("log", &[("valor", Real)], Real, "logaritmo na base 10", |a, _| {
+            finite("log", positive("log", number(a, 0))?.log10())
+        }).boxed(),
+        Function::new("logn", &[("valor", Real)], Real, "logaritmo natural, na base e", |a, _| {
+            finite("logn", positive("logn", number(a, 0))?.ln())
+        }).boxed(),
+        Function::new("sen", &[("valor", Real)], Real, "seno do ângulo em radianos", |a, _| {
+            finite("sen", number(a, 0).sin())
+        }).boxed(),
+        Function::new("cos", &[("valor", Real)], Real, "cosseno do ângulo em radianos", |a, _| {
+            finite("cos", number(a, 0).cos())
+        }).boxed(),
+        Function::new("tan", &[("valor", Real)], Real, "tangente do ângulo em radianos", |a, _| {
+            finite("tan", number(a, 0).tan())
+        }).boxed(),
+        Function::new("arctan", &[("valor", Real)], Real, "ângulo em radianos cuja tangente é o valor", |a, _| {
+            finite("arctan", number(a, 0).atan())
+        }).boxed(),
+        Function::new("int", &[("valor", Real)], Inteiro, "parte inteira, sem arredondar", |a, _| {
+            let value = number(a, 0).trunc();
+            if !value.is_finite() || value < i64::MIN as f64 || value >= i64::MAX as f64 {
+                return Err(format!("int de {} não cabe em um inteiro", number(a, 0)));
+            }
+            Ok(Value::Inteiro(value as i64))
+        }).boxed(),
+        Function::new("pi", &[], Real, "o número pi, 3.14159...", |_, _| {
+            Ok(Value::Real(std::f64::consts::PI))
+        }).boxed(),
+    ]
+}
+
+fn positive(name:&str, value:f64) -> Result<f64, String> {
+    if value > 0.0 {
+        Ok(value)
+    } else {
+        Err(format!("{} precisa de um valor maior que zero, recebeu {}", name, value))
+    }
+}
+
+/// Fail instead of returning an infinite or undefined number.
+fn finite(name:&str, value:f64) -> Result<Value, String> {
+    if value.is_finite() {
+        Ok(Value::Real(value))
+    } else {
+        Err(format!("resultado de {} não é um número real finito", name))
+    }
+}
@@ -0,0 +1,14 @@
+pub mod function;
+pub mod math;
+pub mod random;
+pub mod text;
+
+use super::builtin::Builtin;
+
+/// Functions every VisuAlg program can call, in the order hints list them.
+pub fn visualg() -> Vec<Box<dyn Builtin>> {
+    let mut all = math::functions();
+    all.extend(random::functions());
+    all.extend(text::functions());
+    all
+}
@@ -0,0 +1,22 @@
+use crate::interpreter::ast::types::TypeKind::{Inteiro, Real};
+use crate::interpreter::builtin::Builtin;
+use crate::interpreter::value::Value;
+
+use super::function::{integer, Function};
+
+/// Random numbers, drawn from the run's
+/// [`crate::interpreter::random::RandomSource`].
+pub fn functions() -> Vec<Box<dyn Builtin>> {
+    vec![
+        Function::new("rand", &[], Real, "número aleatório de 0 até menos de 1", |_, random| {
+            Ok(Value::Real(random.real()?))
+        }).boxed(),
+        Function::new("randi", &[("limite", Inteiro)], Inteiro, "inteiro aleatório de 0 até limite - 1", |a, random| {
+            let limit = integer(a, 0);
+            if limit <= 0 {
+                return Err(format!("randi precisa de um limite maior que zero, recebeu {}", limit));
+            }
+            Ok(Value::Inteiro(random.below(limit as u64)? as i64))
+        }).boxed(),
+    ]
+}
@@ -0,0 +1,72 @@
+use crate::interpreter::ast::types::TypeKind::{Caractere, Inteiro, Real};
+use crate::interpreter::builtin::Builtin;
+use crate::interpreter::value::Value;
+
+use super::function::{integer, text, Function};
+
+/// Text handling. Positions count characters and start at 1.
+pub fn functions() -> Vec<Box<dyn Builtin>> {
+    vec![
+        Function::new("compr", &[("c", Caractere)], Inteiro, "quantidade de caracteres do texto", |a, _| {
+            Ok(Value::Inteiro(text(a, 0).chars().count() as i64))
+        }).boxed(),
+        Function::new(
+            "copia",
+            &[("c", Caractere), ("p", Inteiro), ("n", Inteiro)],
+            Caractere,
+            "n caracteres do texto a partir da posição p",
+            |a, _| {
+                let (start, count) = (integer(a, 1), integer(a, 2));
+                if start < 1 {
+                    return Err(format!("copia a partir da posição {}, mas a primeira é 1", start));
+                }
+                if count < 0 {
+                    return Err(format!("copia de {} caracteres, a quantidade não pode ser negativa", count));
+                }
+                Ok(Value::Caractere(text(a, 0).chars().skip(start as usize - 1).take(count as usize).collect()))
+            }
+        ).boxed(),
+        Function::new("maiusc", &[("c", Caractere)], Caractere, "texto em letras maiúsculas", |a, _| {
+            Ok(Value::Caractere(text(a, 0).to_uppercase()))
+        }).boxed(),
+        Function::new("minusc", &[("c", Caractere)], Caractere, "texto em letras minúsculas", |a, _| {
+            Ok(Value::Caractere(text(a, 0).to_lowercase()))
+        }).boxed(),
+        Function::new(
+            "pos",
+            &[("subc", Caractere), ("c", Caractere)],
+            Inteiro,
+            "posição onde subc aparece primeiro em c, ou 0 se não aparece",
+            |a, _| {
+                let (part, whole) = (text(a, 0), text(a, 1));
+                let position = match whole.find(part) {
+                    Some(byte) if !part.is_empty() => whole[..byte].chars().count() as i64 + 1,
+                    _ => 0,
+                };
+                Ok(Value::Inteiro(position))
+            }
+        ).boxed(),
+        Function::new("asc", &[("c", Caractere)], Inteiro, "código do primeiro caractere do texto", |a, _| {
+            text(a, 0).chars().next()
+                .map(|c| Value::Inteiro(c as i64))
+                .ok_or_else(|| String::from("asc de texto vazio"))
+        }).boxed(),
+        Function::new("carac", &[("c", Inteiro)], Caractere, "caractere com o código informado", |a, _| {
+            let code = integer(a, 0);
+            u32::try_from(code).ok()
+                .and_then(char::from_u32)
+                .map(|c| Value::Caractere(c.to_string()))
+                .ok_or_else(|| format!("{} não é o código de um caractere", code))
+        }).boxed(),
+        Function::new("numpcarac", &[("n", Real)], Caractere, "número escrito como texto", |a, _| {
+            Ok(Value::Caractere(a.first().map(|v| v.to_string()).unwrap_or_default()))
+        }).boxed(),
+        Function::new("caracpnum", &[("c", Caractere)], Real, "número escrito no texto", |a, _| {
+            let written = text(a, 0);
+            written.trim().replace(',', ".").parse::<f64>().ok()
+                .filter(|n| n.is_finite())
+                .map(Value::Real)
+                .ok_or_else(|| format!("'{}' não é um número", written))
+        }).boxed(),
+    ]
+}
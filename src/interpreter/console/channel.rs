@@ -0,0 +1,76 @@
+use std::io;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use super::handler::IoHandler;
+
+/// What the running program asks of the console on the other end.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConsoleEvent {
+    Write(String),
+    Clear,
+    /// The program is blocked on `leia` until a line is sent back.
+    ReadRequest,
+}
+
+/// Console driven from another thread, e.g. the GUI running the program in
+/// the background.
+pub struct ChannelIo {
+    events:Sender<ConsoleEvent>,
+    input:Receiver<String>,
+}
+
+/// The other end of a [`ChannelIo`].
+pub struct ConsoleChannel {
+    events:Receiver<ConsoleEvent>,
+    input:Sender<String>,
+}
+
+impl ChannelIo {
+    pub fn new() -> (ChannelIo, ConsoleChannel) {
+        let (event_sender, event_receiver) = mpsc::channel();
+        let (input_sender, input_receiver) = mpsc::channel();
+
+        (
+            ChannelIo { events: event_sender, input: input_receiver },
+            ConsoleChannel { events: event_receiver, input: input_sender },
+        )
+    }
+
+    fn send(&self, event:ConsoleEvent) -> io::Result<()> {
+        self.events.send(event).map_err(|_| Self::disconnected())
+    }
+
+    fn disconnected() -> io::Error {
+        io::Error::new(io::ErrorKind::BrokenPipe, "console desconectado")
+    }
+}
+
+impl IoHandler for ChannelIo {
+    fn write(&mut self, text:&str) -> io::Result<()> {
+        self.send(ConsoleEvent::Write(text.to_string()))
+    }
+
+    /// Dropping the [`ConsoleChannel`] ends the input.
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        if self.send(ConsoleEvent::ReadRequest).is_err() {
+            return Ok(None);
+        }
+        Ok(self.input.recv().ok())
+    }
+
+    fn clear_screen(&mut self) -> io::Result<()> {
+        self.send(ConsoleEvent::Clear)
+    }
+}
+
+impl ConsoleChannel {
+    /// Events sent since the last call, without blocking.
+    pub fn poll(&self) -> Vec<ConsoleEvent> {
+        self.events.try_iter().collect()
+    }
+
+    /// Answer a pending `leia`.
+    pub fn send_line(&self, line:&str) -> bool {
+        self.input.send(line.to_string()).is_ok()
+    }
+}
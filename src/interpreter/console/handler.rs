@@ -0,0 +1,13 @@
+use std::io;
+
+/// Console used by `escreva`, `escreval`, `leia` and `limpatela`, so the
+/// same program can run on a terminal, in the GUI or under a test.
+pub trait IoHandler {
+    fn write(&mut self, text:&str) -> io::Result<()>;
+
+    /// Next input line without its line break, or `None` once the input is
+    /// exhausted.
+    fn read_line(&mut self) -> io::Result<Option<String>>;
+
+    fn clear_screen(&mut self) -> io::Result<()>;
+}
@@ -0,0 +1,54 @@
+use std::collections::VecDeque;
+use std::io;
+
+use super::handler::IoHandler;
+
+/// Scripted input and captured output, for tests and automated grading.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryIo {
+    input:VecDeque<String>,
+    output:String,
+    /// Length of `output` at the last `limpatela`.
+    cleared_at:usize,
+}
+
+impl MemoryIo {
+    /// Each line of `input` answers one `leia`.
+    pub fn new(input:&str) -> Self {
+        Self {
+            input: input.lines().map(String::from).collect(),
+            output: String::new(),
+            cleared_at: 0,
+        }
+    }
+
+    pub fn push_input(&mut self, line:&str) {
+        self.input.push_back(line.to_string());
+    }
+
+    /// Everything written, including text erased by `limpatela`.
+    pub fn output(&self) -> &str {
+        &self.output
+    }
+
+    /// What is left on screen since the last `limpatela`.
+    pub fn screen(&self) -> &str {
+        &self.output[self.cleared_at..]
+    }
+}
+
+impl IoHandler for MemoryIo {
+    fn write(&mut self, text:&str) -> io::Result<()> {
+        self.output.push_str(text);
+        Ok(())
+    }
+
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        Ok(self.input.pop_front())
+    }
+
+    fn clear_screen(&mut self) -> io::Result<()> {
+        self.cleared_at = self.output.len();
+        Ok(())
+    }
+}
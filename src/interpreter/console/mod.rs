@@ -0,0 +1,5 @@
+pub mod handler;
+pub mod terminal;
+pub mod memory;
+pub mod channel;
+pub mod scripted;
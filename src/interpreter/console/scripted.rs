@@ -0,0 +1,33 @@
+use std::collections::VecDeque;
+use std::io;
+
+use super::handler::IoHandler;
+use super::terminal::TerminalIo;
+
+/// Input answered from a fixed list of lines, output shown on the terminal.
+/// Runs end with an input error once the lines are used up.
+#[derive(Clone, Debug, Default)]
+pub struct ScriptedIo {
+    input:VecDeque<String>,
+}
+
+impl ScriptedIo {
+    /// Each line of `input` answers one `leia`.
+    pub fn new(input:&str) -> Self {
+        Self { input: input.lines().map(String::from).collect() }
+    }
+}
+
+impl IoHandler for ScriptedIo {
+    fn write(&mut self, text:&str) -> io::Result<()> {
+        TerminalIo.write(text)
+    }
+
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        Ok(self.input.pop_front())
+    }
+
+    fn clear_screen(&mut self) -> io::Result<()> {
+        TerminalIo.clear_screen()
+    }
+}
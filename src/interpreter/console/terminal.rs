@@ -0,0 +1,27 @@
+use std::io::{self, BufRead, Write};
+
+use super::handler::IoHandler;
+
+/// Process stdin and stdout.
+#[derive(Default)]
+pub struct TerminalIo;
+
+impl IoHandler for TerminalIo {
+    fn write(&mut self, text:&str) -> io::Result<()> {
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(text.as_bytes())?;
+        stdout.flush()
+    }
+
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        let mut line = String::new();
+        if io::stdin().lock().read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(line.trim_end_matches(['\n', '\r']).to_string()))
+    }
+
+    fn clear_screen(&mut self) -> io::Result<()> {
+        self.write("\x1B[2J\x1B[H")
+    }
+}
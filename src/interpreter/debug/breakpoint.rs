@@ -0,0 +1,122 @@
+use std::fmt;
+
+use crate::interpreter::ast::expression::Expression;
+use crate::interpreter::diagnostics::renderer::DiagnosticRenderer;
+use crate::interpreter::language::Language;
+use crate::interpreter::languages;
+use crate::interpreter::syntaxer::Syntaxer;
+use crate::interpreter::tokenizer::Tokenizer;
+
+/// Stop before the statements that start on `line`, only where
+/// `condition` holds when it has one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Breakpoint {
+    line: usize,
+    /// The condition as written, and as parsed.
+    condition: Option<(String, Expression)>,
+}
+
+impl Breakpoint {
+    pub fn new(line:usize) -> Self {
+        Self { line, condition: None }
+    }
+
+    /// Breakpoint written as `12` or `12 se i = 3`, the condition an
+    /// expression of `language`. The error explains what is wrong, with
+    /// parse errors rendered against the condition.
+    pub fn parse(text:&str, language:&dyn Language) -> Result<Self, String> {
+        let text = text.trim();
+        let (line, condition) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+
+        let line = match line.parse() {
+            Ok(line) if line > 0 => line,
+            _ => return Err(format!("linha inválida '{}' no ponto de parada, use um inteiro positivo", line)),
+        };
+
+        let condition = condition.trim();
+        if condition.is_empty() {
+            return Ok(Self::new(line));
+        }
+        let Some(condition) = condition.strip_prefix("se ").map(str::trim) else {
+            return Err(format!("escreva a condição do ponto de parada como '{} se <condição>'", line));
+        };
+        Self::conditional(line, condition, language)
+    }
+
+    /// Breakpoint that only stops where `condition`, an expression of
+    /// `language`, is `verdadeiro`.
+    pub fn conditional(line:usize, condition:&str, language:&dyn Language) -> Result<Self, String> {
+        let Some(language) = languages::instance_of(language) else {
+            return Err(format!("a linguagem {} não aceita condições em pontos de parada", language.name()));
+        };
+
+        let mut tokenizer = Tokenizer::new(language);
+        tokenizer.tokenize(condition);
+        let expression = Syntaxer::new(&tokenizer).parse_expression().map_err(|errors| {
+            DiagnosticRenderer::new(condition, None).render_all(&errors)
+        })?;
+
+        Ok(Self { line, condition: Some((condition.to_string(), expression)) })
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn condition(&self) -> Option<&Expression> {
+        self.condition.as_ref().map(|(_, expression)| expression)
+    }
+
+    pub fn condition_text(&self) -> Option<&str> {
+        self.condition.as_ref().map(|(text, _)| text.as_str())
+    }
+}
+
+/// Written as [`Breakpoint::parse`] reads it.
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.condition_text() {
+            Some(condition) => write!(f, "{} se {}", self.line, condition),
+            None => write!(f, "{}", self.line),
+        }
+    }
+}
+
+/// Breakpoints of a run, at most one per line, kept in line order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Breakpoints {
+    list: Vec<Breakpoint>,
+}
+
+impl Breakpoints {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `breakpoint`, replacing the one on its line.
+    pub fn add(&mut self, breakpoint:Breakpoint) {
+        match self.list.binary_search_by_key(&breakpoint.line, |b| b.line) {
+            Ok(index) => self.list[index] = breakpoint,
+            Err(index) => self.list.insert(index, breakpoint),
+        }
+    }
+
+    /// Remove the breakpoint on `line`, returning whether there was one.
+    pub fn remove(&mut self, line:usize) -> bool {
+        let before = self.list.len();
+        self.list.retain(|b| b.line != line);
+        self.list.len() != before
+    }
+
+    pub fn at(&self, line:usize) -> Option<&Breakpoint> {
+        self.list.iter().find(|b| b.line == line)
+    }
+
+    pub fn list(&self) -> &Vec<Breakpoint> {
+        &self.list
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+}
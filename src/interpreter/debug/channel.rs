@@ -0,0 +1,80 @@
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+
+use super::breakpoint::Breakpoints;
+use super::handler::{Command, DebugHandler};
+use super::state::DebugState;
+
+/// What the other end sends to the running program.
+enum Request {
+    Command(Command),
+    Breakpoints(Breakpoints),
+}
+
+/// Debugger driven from another thread, e.g. the GUI running the program
+/// in the background.
+pub struct ChannelDebugger {
+    states:Sender<DebugState>,
+    requests:Receiver<Request>,
+}
+
+/// The other end of a [`ChannelDebugger`]. Dropping it stops the program.
+pub struct DebugChannel {
+    states:Receiver<DebugState>,
+    requests:Sender<Request>,
+}
+
+impl ChannelDebugger {
+    pub fn new() -> (ChannelDebugger, DebugChannel) {
+        let (state_sender, state_receiver) = mpsc::channel();
+        let (request_sender, request_receiver) = mpsc::channel();
+
+        (
+            ChannelDebugger { states: state_sender, requests: request_receiver },
+            DebugChannel { states: state_receiver, requests: request_sender },
+        )
+    }
+}
+
+impl DebugHandler for ChannelDebugger {
+    fn paused(&mut self, state:&DebugState, breakpoints:&mut Breakpoints) -> Command {
+        if self.states.send(state.clone()).is_err() {
+            return Command::Stop;
+        }
+
+        loop {
+            match self.requests.recv() {
+                Ok(Request::Command(command)) => return command,
+                Ok(Request::Breakpoints(changed)) => *breakpoints = changed,
+                Err(_) => return Command::Stop,
+            }
+        }
+    }
+
+    fn poll(&mut self, breakpoints:&mut Breakpoints) -> Option<Command> {
+        loop {
+            match self.requests.try_recv() {
+                Ok(Request::Command(command @ (Command::Pause | Command::Stop))) => return Some(command),
+                Ok(Request::Command(_)) => {},
+                Ok(Request::Breakpoints(changed)) => *breakpoints = changed,
+                Err(TryRecvError::Empty) => return None,
+                Err(TryRecvError::Disconnected) => return Some(Command::Stop),
+            }
+        }
+    }
+}
+
+impl DebugChannel {
+    /// State of the latest pause since the last call, without blocking.
+    pub fn poll(&self) -> Option<DebugState> {
+        self.states.try_iter().last()
+    }
+
+    pub fn send(&self, command:Command) -> bool {
+        self.requests.send(Request::Command(command)).is_ok()
+    }
+
+    /// Replace the breakpoints of the run, taken at the next statement.
+    pub fn set_breakpoints(&self, breakpoints:Breakpoints) -> bool {
+        self.requests.send(Request::Breakpoints(breakpoints)).is_ok()
+    }
+}
@@ -0,0 +1,78 @@
+use super::breakpoint::Breakpoints;
+use super::handler::{Command, DebugHandler};
+use super::state::DebugState;
+
+/// Where the program stops next, besides breakpoints. Depths count the
+/// calls in progress.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Mode {
+    Run,
+    StepInto,
+    /// At a depth of at most this.
+    StepOver(usize),
+    /// At a depth below this.
+    StepOut(usize),
+}
+
+/// Decides where a [`crate::interpreter::runner::Runner`] stops, and asks
+/// its [`DebugHandler`] how to go on each time.
+pub struct Debugger<'a> {
+    handler: Box<dyn DebugHandler + 'a>,
+    breakpoints: Breakpoints,
+    mode: Mode,
+}
+
+impl<'a> Debugger<'a> {
+    /// Debugger that stops before the first statement.
+    pub fn new(handler:Box<dyn DebugHandler + 'a>) -> Self {
+        Self { handler, breakpoints: Breakpoints::new(), mode: Mode::StepInto }
+    }
+
+    pub fn with_breakpoints(mut self, breakpoints:Breakpoints) -> Self {
+        self.breakpoints = breakpoints;
+        self
+    }
+
+    /// Start as if `command` were given before the first statement, as
+    /// `Command::Continue` to run until the first breakpoint.
+    pub fn starting_with(mut self, command:Command) -> Self {
+        self.apply(command, 0);
+        self
+    }
+
+    pub fn breakpoints(&self) -> &Breakpoints {
+        &self.breakpoints
+    }
+
+    /// A `Pause` or `Stop` given while the program runs.
+    pub fn interrupt(&mut self) -> Option<Command> {
+        self.handler.poll(&mut self.breakpoints)
+    }
+
+    /// Whether the step in progress ends before a statement `depth` calls
+    /// deep.
+    pub fn is_stepping(&self, depth:usize) -> bool {
+        match self.mode {
+            Mode::Run => false,
+            Mode::StepInto => true,
+            Mode::StepOver(over) => depth <= over,
+            Mode::StepOut(out) => depth < out,
+        }
+    }
+
+    /// Hand the pause to the handler, `false` when it stops the program.
+    pub fn pause(&mut self, state:&DebugState, depth:usize) -> bool {
+        let command = self.handler.paused(state, &mut self.breakpoints);
+        self.apply(command, depth);
+        command != Command::Stop
+    }
+
+    fn apply(&mut self, command:Command, depth:usize) {
+        self.mode = match command {
+            Command::Continue | Command::Stop => Mode::Run,
+            Command::Pause | Command::StepInto => Mode::StepInto,
+            Command::StepOver => Mode::StepOver(depth),
+            Command::StepOut => Mode::StepOut(depth),
+        };
+    }
+}
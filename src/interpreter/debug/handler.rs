@@ -0,0 +1,35 @@
+use super::breakpoint::Breakpoints;
+use super::state::DebugState;
+
+/// How to go on from a pause, or what to do to a running program.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Command {
+    /// Run until a breakpoint.
+    Continue,
+    /// Stop before the next statement. The same as `StepInto` when
+    /// already paused.
+    Pause,
+    /// Stop before the next statement, inside a called subprogram too.
+    StepInto,
+    /// Stop before the next statement of this subprogram or a caller.
+    StepOver,
+    /// Stop before the next statement of the caller.
+    StepOut,
+    /// End the program.
+    Stop,
+}
+
+/// Front end of a [`super::debugger::Debugger`], so the same run can be
+/// driven from a terminal or from the GUI.
+pub trait DebugHandler {
+    /// The program stopped before the statement of `state`. Blocks until
+    /// the user picks how to go on, changing `breakpoints` meanwhile if
+    /// they wish.
+    fn paused(&mut self, state:&DebugState, breakpoints:&mut Breakpoints) -> Command;
+
+    /// Checked before every statement while the program runs, for a
+    /// `Pause` or `Stop` given meanwhile; other commands are ignored.
+    fn poll(&mut self, _breakpoints:&mut Breakpoints) -> Option<Command> {
+        None
+    }
+}
@@ -0,0 +1,6 @@
+pub mod breakpoint;
+pub mod channel;
+pub mod debugger;
+pub mod handler;
+pub mod state;
+pub mod terminal;
@@ -0,0 +1,131 @@
+use std::fmt;
+
+use crate::interpreter::ast::types::TypeKind;
+use crate::interpreter::tokens::span::Span;
+use crate::interpreter::value::Value;
+
+/// Why the program stopped.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Pause {
+    /// Before the first statement, or after a step.
+    Step,
+    Breakpoint { line:usize },
+    /// On [`super::handler::Command::Pause`] while running.
+    Requested,
+    /// The condition of the breakpoint on `line` could not be evaluated,
+    /// as when it names an undeclared variable.
+    ConditionFailed { line:usize, message:String },
+}
+
+impl fmt::Display for Pause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Pause::Step => f.write_str("passo"),
+            Pause::Breakpoint { line } => write!(f, "ponto de parada da linha {}", line),
+            Pause::Requested => f.write_str("pausa pedida"),
+            Pause::ConditionFailed { line, message } => {
+                write!(f, "a condição do ponto de parada da linha {} falhou: {}", line, message)
+            },
+        }
+    }
+}
+
+/// A variable as the debugger shows it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VariableState {
+    name: String,
+    kind: TypeKind,
+    /// The single value, or every element of an array named as `v[1]`
+    /// or `m[1, 2]`.
+    elements: Vec<(String, Value)>,
+}
+
+impl VariableState {
+    pub fn new(name:&str, kind:TypeKind, elements:Vec<(String, Value)>) -> Self {
+        Self { name: name.to_string(), kind, elements }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Type of the value, or of each element.
+    pub fn kind(&self) -> TypeKind {
+        self.kind
+    }
+
+    pub fn elements(&self) -> &Vec<(String, Value)> {
+        &self.elements
+    }
+}
+
+/// A subprogram call in progress.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CallState {
+    name: String,
+    /// Where it was called from.
+    call: Span,
+    /// Parameters, then local variables, in declaration order.
+    variables: Vec<VariableState>,
+}
+
+impl CallState {
+    pub fn new(name:&str, call:Span, variables:Vec<VariableState>) -> Self {
+        Self { name: name.to_string(), call, variables }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn call(&self) -> Span {
+        self.call
+    }
+
+    pub fn variables(&self) -> &Vec<VariableState> {
+        &self.variables
+    }
+}
+
+/// Where a paused program is and what its variables hold.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DebugState {
+    /// Statement about to run.
+    span: Span,
+    pause: Pause,
+    /// Calls in progress, the innermost last.
+    calls: Vec<CallState>,
+    globals: Vec<VariableState>,
+}
+
+impl DebugState {
+    pub fn new(span:Span, pause:Pause, calls:Vec<CallState>, globals:Vec<VariableState>) -> Self {
+        Self { span, pause, calls, globals }
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Line of the statement about to run.
+    pub fn line(&self) -> usize {
+        self.span.start().line()
+    }
+
+    pub fn pause(&self) -> &Pause {
+        &self.pause
+    }
+
+    pub fn calls(&self) -> &Vec<CallState> {
+        &self.calls
+    }
+
+    pub fn globals(&self) -> &Vec<VariableState> {
+        &self.globals
+    }
+
+    /// Variables of the running subprogram, empty in the program body.
+    pub fn locals(&self) -> &[VariableState] {
+        self.calls.last().map_or(&[], |call| call.variables.as_slice())
+    }
+}
@@ -0,0 +1,152 @@
+use std::io::{self, BufRead, Write};
+
+use crate::interpreter::language::Language;
+use crate::interpreter::value::Value;
+
+use super::breakpoint::{Breakpoint, Breakpoints};
+use super::handler::{Command, DebugHandler};
+use super::state::{DebugState, VariableState};
+
+const HELP:&str = "\
+comandos do depurador:
+  c, continuar            executa até o próximo ponto de parada
+  e, entrar               executa um passo, entrando nas chamadas
+  p, proximo              executa um passo sem entrar nas chamadas
+  s, sair                 executa até voltar para quem chamou
+  q, parar                encerra o programa
+  v, variaveis            mostra as variáveis visíveis
+  l, pilha                mostra as chamadas em andamento
+  b, parada [<linha> [se <condição>]]
+                          adiciona um ponto de parada, ou lista os que há
+  r, remover <linha>      remove o ponto de parada da linha
+  ?, ajuda                mostra esta mensagem
+";
+
+/// Debugger driven by commands typed on stdin, reporting on stderr so
+/// the program's own output stays on stdout. `leia` reads from the same
+/// stdin between pauses.
+pub struct TerminalDebugger {
+    /// Language breakpoint conditions are written in.
+    language: Box<dyn Language>,
+    /// Source lines, to show the statement about to run.
+    lines: Vec<String>,
+}
+
+impl TerminalDebugger {
+    pub fn new(language:Box<dyn Language>, code:&str) -> Self {
+        Self { language, lines: code.lines().map(String::from).collect() }
+    }
+
+    fn prompt(&self) -> Option<String> {
+        eprint!("(depurador) ");
+        let _ = io::stderr().flush();
+
+        let mut line = String::new();
+        match io::stdin().lock().read_line(&mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(line.trim().to_string()),
+        }
+    }
+
+    fn variables(state:&DebugState) {
+        if let Some(call) = state.calls().last() {
+            eprintln!("locais de {}:", call.name());
+            Self::list(state.locals());
+        }
+        eprintln!("globais:");
+        Self::list(state.globals());
+    }
+
+    fn list(variables:&[VariableState]) {
+        if variables.is_empty() {
+            eprintln!("  nenhuma");
+        }
+        for variable in variables {
+            match variable.elements().as_slice() {
+                [(name, value)] if name == variable.name() => {
+                    eprintln!("  {}: {} = {}", name, variable.kind(), Self::show(value));
+                },
+                elements => {
+                    eprintln!("  {}: vetor de {}", variable.name(), variable.kind());
+                    for (name, value) in elements {
+                        eprintln!("    {} = {}", name, Self::show(value));
+                    }
+                },
+            }
+        }
+    }
+
+    /// Value as written in a program, texts quoted.
+    fn show(value:&Value) -> String {
+        match value {
+            Value::Caractere(text) => format!("\"{}\"", text),
+            other => other.to_string(),
+        }
+    }
+
+    fn stack(state:&DebugState) {
+        for call in state.calls().iter().rev() {
+            eprintln!("  em {}, chamado na linha {}", call.name(), call.call().start().line());
+        }
+        eprintln!("  no programa principal");
+    }
+
+    fn breakpoint(&self, argument:&str, breakpoints:&mut Breakpoints) {
+        if argument.is_empty() {
+            if breakpoints.is_empty() {
+                eprintln!("nenhum ponto de parada");
+            }
+            for breakpoint in breakpoints.list() {
+                eprintln!("  linha {}", breakpoint);
+            }
+            return;
+        }
+
+        match Breakpoint::parse(argument, self.language.as_ref()) {
+            Ok(breakpoint) => {
+                eprintln!("ponto de parada na linha {}", breakpoint);
+                breakpoints.add(breakpoint);
+            },
+            Err(message) => eprintln!("{}", message.trim_end()),
+        }
+    }
+
+    fn remove(argument:&str, breakpoints:&mut Breakpoints) {
+        match argument.parse() {
+            Ok(line) if breakpoints.remove(line) => eprintln!("ponto de parada da linha {} removido", line),
+            Ok(line) => eprintln!("não há ponto de parada na linha {}", line),
+            Err(_) => eprintln!("informe o número da linha, como 'remover 12'"),
+        }
+    }
+}
+
+impl DebugHandler for TerminalDebugger {
+    /// Once stdin ends, the program runs to the end.
+    fn paused(&mut self, state:&DebugState, breakpoints:&mut Breakpoints) -> Command {
+        let source = self.lines.get(state.line() - 1).map_or("", |line| line.trim());
+        eprintln!("[{}] linha {}: {}", state.pause(), state.line(), source);
+
+        loop {
+            let Some(input) = self.prompt() else {
+                return Command::Continue;
+            };
+            let (command, argument) = input.split_once(char::is_whitespace).unwrap_or((&input, ""));
+            let argument = argument.trim();
+
+            match command {
+                "c" | "continuar" => return Command::Continue,
+                "e" | "entrar" => return Command::StepInto,
+                "p" | "proximo" => return Command::StepOver,
+                "s" | "sair" => return Command::StepOut,
+                "q" | "parar" => return Command::Stop,
+                "v" | "variaveis" => Self::variables(state),
+                "l" | "pilha" => Self::stack(state),
+                "b" | "parada" => self.breakpoint(argument, breakpoints),
+                "r" | "remover" => Self::remove(argument, breakpoints),
+                "?" | "ajuda" => eprint!("{}", HELP),
+                "" => {},
+                other => eprintln!("comando desconhecido '{}', digite 'ajuda' para ver os comandos", other),
+            }
+        }
+    }
+}
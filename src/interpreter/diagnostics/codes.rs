@@ -0,0 +1,54 @@
+//! Stable diagnostic codes. Codes are never reused once published: `E00xx`
+//! are loading errors, `E01xx` lexical, `E02xx` syntax, `E03xx` semantic and
+//! `E04xx` runtime errors. Warnings use `W` with the same ranges.
+
+pub const FILE_UNREADABLE:&str = "E0001";
+pub const FILE_UNWRITABLE:&str = "E0002";
+
+pub const SOURCE_TRANSCODED:&str = "W0001";
+
+pub const UNTERMINATED_DELIMITER:&str = "E0101";
+
+pub const UNEXPECTED_TOKEN:&str = "E0201";
+pub const TRAILING_CONTENT:&str = "E0202";
+pub const EXPECTED_IDENTIFIER:&str = "E0203";
+pub const EXPECTED_TYPE:&str = "E0204";
+pub const EXPECTED_EXPRESSION:&str = "E0205";
+pub const UNCLOSED_BLOCK:&str = "E0206";
+pub const UNDEFINED_RULE:&str = "E0207";
+pub const EMPTY_GRAMMAR:&str = "E0208";
+pub const UNSUPPORTED_LIBRARY:&str = "E0209";
+pub const MISSING_ENTRY_POINT:&str = "E0210";
+pub const NESTING_TOO_DEEP:&str = "E0211";
+
+pub const UNDECLARED_NAME:&str = "E0301";
+pub const DUPLICATE_DECLARATION:&str = "E0302";
+pub const INCOMPATIBLE_ASSIGNMENT:&str = "E0303";
+pub const NON_LOGICAL_CONDITION:&str = "E0304";
+pub const ARGUMENT_COUNT:&str = "E0305";
+pub const ARGUMENT_TYPE:&str = "E0306";
+pub const UNDEFINED_SUBPROGRAM:&str = "E0307";
+pub const INCOMPATIBLE_OPERANDS:&str = "E0308";
+pub const NON_INTEGER_EXPRESSION:&str = "E0309";
+pub const NO_RETURN_VALUE:&str = "E0310";
+pub const INVALID_BOUNDS:&str = "E0311";
+pub const NOT_AN_ARRAY:&str = "E0312";
+pub const INDEX_COUNT:&str = "E0313";
+pub const MISSING_RETURN:&str = "E0314";
+
+pub const UNDECLARED_VARIABLE:&str = "E0401";
+pub const ASSIGNMENT_MISMATCH:&str = "E0402";
+pub const CONDITION_NOT_LOGICAL:&str = "E0403";
+pub const DIVISION_BY_ZERO:&str = "E0404";
+pub const OPERATOR_MISMATCH:&str = "E0405";
+pub const INVALID_INPUT:&str = "E0406";
+pub const INPUT_EXHAUSTED:&str = "E0407";
+pub const ZERO_STEP:&str = "E0408";
+pub const UNSUPPORTED_CALL:&str = "E0409";
+pub const EXPECTED_INTEGER:&str = "E0410";
+pub const IO_FAILURE:&str = "E0411";
+pub const INDEX_OUT_OF_BOUNDS:&str = "E0412";
+pub const CALL_DEPTH_EXCEEDED:&str = "E0413";
+pub const FUNCTION_WITHOUT_RETURN:&str = "E0414";
+pub const BUILTIN_FAILED:&str = "E0415";
+pub const DEBUG_STOPPED:&str = "E0416";
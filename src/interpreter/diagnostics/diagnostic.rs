@@ -0,0 +1,171 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::interpreter::tokens::span::Span;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Note,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(
+            match self {
+                Severity::Note => "nota",
+                Severity::Warning => "aviso",
+                Severity::Error => "erro",
+            }
+        )
+    }
+}
+
+/// Extra span shown next to the primary one, e.g. where a block was opened.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Label {
+    span: Span,
+    message: String,
+}
+
+impl Label {
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+/// Replacing the text at `span` with `replacement` fixes the problem.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Suggestion {
+    message: String,
+    span: Span,
+    replacement: String,
+}
+
+impl Suggestion {
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    pub fn replacement(&self) -> &str {
+        &self.replacement
+    }
+}
+
+/// Optional parts of a diagnostic, boxed so `Result<_, Diagnostic>` stays
+/// small on the happy path.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+struct Details {
+    label: Option<String>,
+    secondary: Vec<Label>,
+    notes: Vec<String>,
+    suggestion: Option<Suggestion>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Diagnostic {
+    severity: Severity,
+    code: &'static str,
+    message: String,
+    span: Span,
+    #[serde(flatten)]
+    details: Box<Details>,
+}
+
+impl Diagnostic {
+    pub fn new(severity:Severity, code:&'static str, message:String, span:Span) -> Self {
+        Self {
+            severity,
+            code,
+            message,
+            span,
+            details: Box::default(),
+        }
+    }
+
+    pub fn error(code:&'static str, message:String, span:Span) -> Self {
+        Self::new(Severity::Error, code, message, span)
+    }
+
+    pub fn warning(code:&'static str, message:String, span:Span) -> Self {
+        Self::new(Severity::Warning, code, message, span)
+    }
+
+    /// Text printed under the carets of the primary span.
+    pub fn with_label(mut self, label:&str) -> Self {
+        self.details.label = Some(label.to_string());
+        self
+    }
+
+    pub fn with_secondary(mut self, span:Span, message:&str) -> Self {
+        self.details.secondary.push(Label { span, message: message.to_string() });
+        self
+    }
+
+    pub fn with_note(mut self, note:&str) -> Self {
+        self.details.notes.push(note.to_string());
+        self
+    }
+
+    pub fn with_suggestion(mut self, message:&str, span:Span, replacement:&str) -> Self {
+        self.details.suggestion = Some(Suggestion {
+            message: message.to_string(),
+            span,
+            replacement: replacement.to_string(),
+        });
+        self
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    pub fn label(&self) -> Option<&str> {
+        self.details.label.as_deref()
+    }
+
+    pub fn secondary(&self) -> &Vec<Label> {
+        &self.details.secondary
+    }
+
+    pub fn notes(&self) -> &Vec<String> {
+        &self.details.notes
+    }
+
+    pub fn suggestion(&self) -> Option<&Suggestion> {
+        self.details.suggestion.as_ref()
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}[{}]: {}", self.span.start(), self.severity, self.code, self.message)
+    }
+}
@@ -0,0 +1,3 @@
+pub mod codes;
+pub mod diagnostic;
+pub mod renderer;
@@ -0,0 +1,123 @@
+use crate::interpreter::tokens::span::Span;
+
+use super::diagnostic::Diagnostic;
+
+struct Marker<'a> {
+    span: Span,
+    underline: char,
+    message: Option<&'a str>,
+}
+
+/// Prints diagnostics in the rustc style, quoting the source lines with
+/// carets under the offending spans:
+///
+/// ```text
+/// erro[E0201]: 'entao' esperado, encontrado 'x'
+///  --> script.alg:5:8
+///   |
+/// 5 |   se a x
+///   |        ^ esperado aqui
+/// ```
+pub struct DiagnosticRenderer<'a> {
+    lines: Vec<&'a str>,
+    file: Option<&'a str>,
+}
+
+impl<'a> DiagnosticRenderer<'a> {
+    pub fn new(source:&'a str, file:Option<&'a str>) -> Self {
+        Self {
+            lines: source.split('\n').map(|l| l.trim_end_matches('\r')).collect(),
+            file,
+        }
+    }
+
+    pub fn render_all(&self, diagnostics:&[Diagnostic]) -> String {
+        diagnostics.iter().map(|d| self.render(d)).collect::<Vec<_>>().join("\n")
+    }
+
+    pub fn render(&self, diagnostic:&Diagnostic) -> String {
+        let start = diagnostic.span().start();
+
+        let mut markers = vec![Marker { span: *diagnostic.span(), underline: '^', message: diagnostic.label() }];
+        for label in diagnostic.secondary() {
+            markers.push(Marker { span: *label.span(), underline: '-', message: Some(label.message()) });
+        }
+        markers.sort_by_key(|m| (m.span.start().line(), m.span.start().column()));
+
+        let last_line = markers.iter().map(|m| m.span.start().line()).max().unwrap_or(1);
+        let width = last_line.to_string().len();
+        let gutter = " ".repeat(width);
+
+        let mut out = format!("{}[{}]: {}\n", diagnostic.severity(), diagnostic.code(), diagnostic.message());
+
+        // Line 0 means the diagnostic is about the whole file, e.g. it could
+        // not be read, so there is no snippet to quote.
+        if start.line() == 0 {
+            out += &format!("{}--> {}\n", gutter, self.file.unwrap_or("<código>"));
+            markers.clear();
+        } else {
+            out += &format!("{}--> {}:{}:{}\n", gutter, self.file.unwrap_or("<código>"), start.line(), start.column());
+            out += &format!("{} |\n", gutter);
+        }
+
+        let mut previous_line = None;
+        for marker in &markers {
+            let line_number = marker.span.start().line();
+            let line = self.line(line_number);
+
+            if previous_line != Some(line_number) {
+                if previous_line.is_some_and(|p| line_number > p + 1) {
+                    out += &format!("{} |\n", gutter);
+                }
+                out += &format!("{:>width$} | {}\n", line_number, line, width = width);
+                previous_line = Some(line_number);
+            }
+
+            out += &format!("{} | {}\n", gutter, Self::underline(line, marker).trim_end());
+        }
+
+        for note in diagnostic.notes() {
+            out += &format!("{} = nota: {}\n", gutter, note);
+        }
+
+        if let Some(suggestion) = diagnostic.suggestion() {
+            out += &format!("{} = ajuda: {}: `{}`\n", gutter, suggestion.message(), suggestion.replacement());
+        }
+
+        out
+    }
+
+    fn line(&self, number:usize) -> &'a str {
+        self.lines.get(number.saturating_sub(1)).copied().unwrap_or("")
+    }
+
+    /// Carets under the marker span; spans running past the line are cut at
+    /// its end, empty spans get a single caret.
+    fn underline(line:&str, marker:&Marker) -> String {
+        let start = marker.span.start();
+        let end = marker.span.end();
+        let line_length = line.chars().count();
+
+        let first = start.column().saturating_sub(1);
+        let last = if end.line() == start.line() {
+            end.column().saturating_sub(1)
+        } else {
+            line_length
+        };
+        let length = last.saturating_sub(first).max(1);
+
+        // Keep tabs from the source line so the carets stay aligned.
+        let padding:String = line.chars()
+            .chain(std::iter::repeat(' '))
+            .take(first)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+
+        let mut text = format!("{}{}", padding, marker.underline.to_string().repeat(length));
+        if let Some(message) = marker.message {
+            text.push(' ');
+            text.push_str(message);
+        }
+        text
+    }
+}
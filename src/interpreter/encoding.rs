@@ -0,0 +1,105 @@
+use encoding_rs::{UTF_8, WINDOWS_1252};
+
+const UTF8_BOM:&[u8] = b"\xEF\xBB\xBF";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SourceEncoding {
+    #[default]
+    Utf8,
+    /// What the classic VisuAlg editor writes. ISO-8859-1 files decode the
+    /// same way, since it only differs on the 0x80-0x9F control range.
+    Windows1252,
+}
+
+impl SourceEncoding {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Utf8 => "UTF-8",
+            Self::Windows1252 => "Windows-1252",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    CrLf,
+}
+
+/// How a source file was stored on disk, so it can be written back the
+/// same way after being edited as UTF-8 text with `\n` line endings.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SourceFormat {
+    encoding: SourceEncoding,
+    bom: bool,
+    line_ending: LineEnding,
+}
+
+impl SourceFormat {
+    pub fn encoding(&self) -> SourceEncoding {
+        self.encoding
+    }
+
+    pub fn has_bom(&self) -> bool {
+        self.bom
+    }
+
+    pub fn line_ending(&self) -> LineEnding {
+        self.line_ending
+    }
+
+    /// Whether the text had to be converted to be read as UTF-8.
+    pub fn is_transcoded(&self) -> bool {
+        self.encoding != SourceEncoding::Utf8
+    }
+
+    /// Detect the format of `bytes` and return their text as UTF-8 with
+    /// `\n` line endings. Bytes that are not valid UTF-8 are read as
+    /// Windows-1252, which maps every byte to some character.
+    pub fn decode(bytes:&[u8]) -> (String, Self) {
+        let (bytes, bom) = match bytes.strip_prefix(UTF8_BOM) {
+            Some(rest) => (rest, true),
+            None => (bytes, false),
+        };
+
+        let (text, encoding) = match UTF_8.decode_without_bom_handling_and_without_replacement(bytes) {
+            Some(text) => (text, SourceEncoding::Utf8),
+            None => (WINDOWS_1252.decode_without_bom_handling(bytes).0, SourceEncoding::Windows1252),
+        };
+
+        let line_ending = if text.contains("\r\n") { LineEnding::CrLf } else { LineEnding::Lf };
+        let text = text.replace("\r\n", "\n");
+
+        (text, Self { encoding, bom, line_ending })
+    }
+
+    /// Bytes of `text` in this format. Characters Windows-1252 cannot hold
+    /// are written as `&#N;` references, so the caller should warn first,
+    /// see [`SourceFormat::can_encode`].
+    pub fn encode(&self, text:&str) -> Vec<u8> {
+        let text = match self.line_ending {
+            LineEnding::CrLf => text.replace("\r\n", "\n").replace('\n', "\r\n"),
+            LineEnding::Lf => text.to_string(),
+        };
+
+        let mut bytes = vec![];
+        if self.bom {
+            bytes.extend_from_slice(UTF8_BOM);
+        }
+
+        match self.encoding {
+            SourceEncoding::Utf8 => bytes.extend_from_slice(text.as_bytes()),
+            SourceEncoding::Windows1252 => bytes.extend_from_slice(&WINDOWS_1252.encode(&text).0),
+        }
+        bytes
+    }
+
+    /// Whether every character of `text` exists in this encoding.
+    pub fn can_encode(&self, text:&str) -> bool {
+        match self.encoding {
+            SourceEncoding::Utf8 => true,
+            SourceEncoding::Windows1252 => !WINDOWS_1252.encode(text).2,
+        }
+    }
+}
@@ -1,47 +1,270 @@
-use super::{language::Language, tokenizer::Tokenizer};
+use super::ast::program::Program;
+use super::console::handler::IoHandler;
+use super::console::terminal::TerminalIo;
+use super::debug::debugger::Debugger;
+use super::diagnostics::codes;
+use super::diagnostics::diagnostic::Diagnostic;
+use super::diagnostics::renderer::DiagnosticRenderer;
+use super::encoding::SourceFormat;
+use super::languages;
+use super::random::{RandomSource, SeededRandom};
+use super::runner::{RunOutcome, Runner, DEFAULT_MAX_DEPTH};
+use super::semantic::checker::TypeChecker;
+use super::tokens::edit::{TextEdit, TokenChange};
+use super::tokens::span::Span;
+use super::tokens::token::Token;
+use super::{language::Language, syntaxer::Syntaxer, tokenizer::Tokenizer};
 
+pub type StageResult<T> = Result<T, Vec<Diagnostic>>;
+
+/// Drives a program through the interpreter stages: load, tokenize, parse,
+/// check and run. Each stage runs the ones before it when needed and keeps
+/// its result, so tokens and AST stay available after a run.
 pub struct Interpreter {
+    tokenizer:Tokenizer,
     code:Option<String>,
-    file_path:Option<String>
+    file_path:Option<String>,
+    format:SourceFormat,
+    tokenized:bool,
+    program:Option<Program>,
+    checked:bool,
+    warnings:Vec<Diagnostic>,
+    max_depth:usize,
 }
 
 impl Interpreter {
-    pub fn from_code(code:String) -> Self {
-        Self { code: Some(code), file_path: None }
+    pub fn new(language:Box<dyn Language>) -> Self {
+        Self {
+            tokenizer: Tokenizer::new(language),
+            code: None,
+            file_path: None,
+            format: SourceFormat::default(),
+            tokenized: false,
+            program: None,
+            checked: false,
+            warnings: vec![],
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
     }
 
-    pub fn from_file(file:String) -> Self {
-        Self { code: None, file_path: Some(file) }
+    pub fn from_code(language:Box<dyn Language>, code:String) -> Self {
+        let mut interpreter = Self::new(language);
+        interpreter.set_code(code);
+        interpreter
     }
 
-    fn load_file(&mut self, file:String) {
-        match std::fs::read_to_string(file) {
-            Ok(text) => {
-                self.code = Some(text);
-            }
-            Err(e) => {
-                println!("Filed to read file: {}", e);
+    pub fn from_file(language:Box<dyn Language>, file:String) -> Self {
+        let mut interpreter = Self::new(language);
+        interpreter.file_path = Some(file);
+        interpreter
+    }
+
+    /// Interpreter for `code` in the language [`languages::detect`] finds.
+    pub fn for_code(code:String) -> Self {
+        Self::from_code(languages::detect(None, &code), code)
+    }
+
+    /// Interpreter for `file` in the language [`languages::detect`] finds
+    /// from its name and content. An unreadable file is reported when it
+    /// is loaded.
+    pub fn for_file(file:String) -> Self {
+        let code = std::fs::read(&file).map(|bytes| SourceFormat::decode(&bytes).0).unwrap_or_default();
+        Self::from_file(languages::detect(Some(&file), &code), file)
+    }
+
+    /// Replace the source, dropping the results of every stage.
+    pub fn set_code(&mut self, code:String) {
+        self.code = Some(code);
+        self.tokenized = false;
+        self.program = None;
+        self.checked = false;
+        self.warnings.clear();
+    }
+
+    /// Apply an editor change to the source, loading it first. Cached
+    /// tokens are updated by tokenizing only the lines the edit touched,
+    /// and the change is returned; the later stages are dropped.
+    pub fn edit(&mut self, edit:&TextEdit) -> StageResult<Option<TokenChange>> {
+        self.load()?;
+        edit.apply(self.code.as_mut().expect("code loaded above"));
+        self.program = None;
+        self.checked = false;
+
+        if !self.tokenized {
+            return Ok(None);
+        }
+
+        let change = self.tokenizer.edit(edit);
+        self.warnings.retain(|w| w.code() == codes::SOURCE_TRANSCODED);
+        self.warnings.extend(self.tokenizer.diagnostics().iter().filter(|d| !d.is_error()).cloned());
+        Ok(Some(change))
+    }
+
+    pub fn language(&self) -> &dyn Language {
+        self.tokenizer.language()
+    }
+
+    pub fn file_path(&self) -> Option<&str> {
+        self.file_path.as_deref()
+    }
+
+    /// Source code, once loaded.
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+
+    /// How the loaded file was stored. Code given directly is UTF-8.
+    pub fn source_format(&self) -> SourceFormat {
+        self.format
+    }
+
+    /// Most subprogram calls a run may have going at once, see
+    /// [`Runner::with_max_depth`].
+    pub fn set_max_depth(&mut self, depth:usize) {
+        self.max_depth = depth;
+    }
+
+    /// Non-fatal diagnostics gathered by the stages run so far.
+    pub fn warnings(&self) -> &Vec<Diagnostic> {
+        &self.warnings
+    }
+
+    pub fn load(&mut self) -> StageResult<&str> {
+        if self.code.is_none() {
+            let Some(file) = self.file_path.clone() else {
+                return Err(vec![Diagnostic::error(
+                    codes::FILE_UNREADABLE,
+                    String::from("nenhum código ou arquivo informado"),
+                    Span::default()
+                )]);
+            };
+
+            let bytes = std::fs::read(&file).map_err(|e| vec![Diagnostic::error(
+                codes::FILE_UNREADABLE,
+                format!("não foi possível ler o arquivo '{}': {}", file, e),
+                Span::default()
+            )])?;
+            let (text, format) = SourceFormat::decode(&bytes);
+            self.set_code(text);
+            self.format = format;
+
+            if format.is_transcoded() {
+                self.warnings.push(
+                    Diagnostic::warning(
+                        codes::SOURCE_TRANSCODED,
+                        format!("o arquivo '{}' foi lido como {} e convertido para UTF-8", file, format.encoding().name()),
+                        Span::default()
+                    ).with_note("salve o arquivo em UTF-8 para evitar a conversão")
+                );
             }
         }
+
+        Ok(self.code.as_deref().unwrap_or_default())
+    }
+
+    pub fn tokenize(&mut self) -> StageResult<&Vec<Token>> {
+        if !self.tokenized {
+            let code = self.load()?.to_string();
+            self.tokenizer.tokenize(&code);
+            self.tokenized = true;
+
+            self.warnings.extend(self.tokenizer.diagnostics().iter().filter(|d| !d.is_error()).cloned());
+        }
+
+        let errors:Vec<Diagnostic> = self.tokenizer.diagnostics().iter()
+            .filter(|d| d.is_error())
+            .cloned()
+            .collect();
+
+        if errors.is_empty() {
+            Ok(self.tokenizer.tokens())
+        } else {
+            Err(errors)
+        }
     }
 
-    pub fn interpret(&mut self, language:Box<dyn Language>) {
-        let mut has_file = false;
-        if let Some(file) = self.file_path.clone() {
-            has_file = true;
-            self.load_file(file);
+    pub fn parse(&mut self) -> StageResult<&Program> {
+        if self.program.is_none() {
+            self.tokenize()?;
+            self.program = Some(Syntaxer::new(&self.tokenizer).parse()?);
         }
 
-        let mut tokenizer = Tokenizer::new(language);
+        Ok(self.program.as_ref().expect("program parsed above"))
+    }
 
-        if let Some(code) = self.code.clone() {
-            tokenizer.tokenize(&code);
+    /// Resolve names and types of the parsed program, before anything runs.
+    pub fn check(&mut self) -> StageResult<&Program> {
+        self.parse()?;
+        let program = self.program.as_ref().expect("program parsed above");
 
-            tokenizer.print();
+        if !self.checked {
+            let language = self.tokenizer.language();
+            TypeChecker::new(language.is_case_sensitive())
+                .with_builtins(&language.builtins())
+                .check(program)?;
+            self.checked = true;
         }
 
-        if has_file {
-            self.code = None;
+        Ok(program)
+    }
+
+    /// Execute the checked program on the terminal. Unlike the other stages
+    /// this runs again on every call.
+    pub fn run(&mut self) -> StageResult<RunOutcome> {
+        self.run_with(&mut TerminalIo)
+    }
+
+    pub fn run_with(&mut self, io:&mut dyn IoHandler) -> StageResult<RunOutcome> {
+        self.run_with_random(io, &mut SeededRandom::from_time())
+    }
+
+    /// Run drawing the numbers of `rand` and `randi` from `random`, as a
+    /// [`SeededRandom`] for a run that can be repeated.
+    pub fn run_with_random(&mut self, io:&mut dyn IoHandler, random:&mut dyn RandomSource) -> StageResult<RunOutcome> {
+        self.execute(io, random, None)
+    }
+
+    /// Run under `debugger`, which stops the program where it is asked to
+    /// and reports its state. A stop asked for by the user ends the run
+    /// with [`codes::DEBUG_STOPPED`].
+    pub fn debug_with(
+        &mut self,
+        io:&mut dyn IoHandler,
+        random:&mut dyn RandomSource,
+        debugger:Debugger
+    ) -> StageResult<RunOutcome> {
+        self.execute(io, random, Some(debugger))
+    }
+
+    fn execute(
+        &mut self,
+        io:&mut dyn IoHandler,
+        random:&mut dyn RandomSource,
+        debugger:Option<Debugger>
+    ) -> StageResult<RunOutcome> {
+        self.check()?;
+        let program = self.program.as_ref().expect("program checked above");
+        let mut runner = Runner::new(io, self.tokenizer.language())
+            .with_random(Box::new(random))
+            .with_max_depth(self.max_depth);
+        if let Some(debugger) = debugger {
+            runner = runner.with_debugger(debugger);
         }
+        runner.run(program).map_err(|error| vec![error])
     }
-}
\ No newline at end of file
+
+    /// Tokens of the last tokenize stage, even if it reported errors.
+    pub fn tokens(&self) -> Option<&Vec<Token>> {
+        self.tokenized.then(|| self.tokenizer.tokens())
+    }
+
+    pub fn program(&self) -> Option<&Program> {
+        self.program.as_ref()
+    }
+
+    /// Diagnostics printed with snippets of the loaded source.
+    pub fn render(&self, diagnostics:&[Diagnostic]) -> String {
+        let source = self.code.as_deref().unwrap_or("");
+        DiagnosticRenderer::new(source, self.file_path.as_deref()).render_all(diagnostics)
+    }
+}
@@ -1,12 +1,40 @@
+use super::ast::expression::Expression;
+use super::ast::program::Program;
+use super::builtin::Builtin;
+use super::diagnostics::diagnostic::Diagnostic;
 use super::tokens::separator::TokenSeparators;
-use super::rules::rule::{SyntaxRule, SyntaxType}
+use super::tokens::token::Token;
+use super::value::Value;
+use super::rules::rule::SyntaxGroup;
 
 pub trait Language {
     fn escape(&self) -> &str;
 
+    /// Char written by the escape char followed by `escaped`, such as a
+    /// line break for `\n`.
+    fn unescape(&self, escaped:char) -> char;
+
     fn name(&self) -> &str;
 
+    /// Whether keywords and identifiers must match in case. Tokens keep
+    /// their original spelling either way.
+    fn is_case_sensitive(&self) -> bool;
+
+    /// Value of a boolean literal, `None` if `word` is not one.
+    fn boolean(&self, word:&str) -> Option<bool>;
+
+    /// Text `escreva` prints for `value` when no width is given.
+    fn write(&self, value:&Value) -> String;
+
     fn separators(&self) -> Vec<TokenSeparators>;
 
-    fn syntax() -> Vec<SyntaxRule>;
-}
\ No newline at end of file
+    fn syntax(&self) -> Vec<SyntaxGroup>;
+
+    /// Functions programs call without declaring them.
+    fn builtins(&self) -> Vec<Box<dyn Builtin>>;
+
+    fn parse(&self, tokens:&[Token]) -> Result<Program, Vec<Diagnostic>>;
+
+    /// A lone expression, such as the condition of a breakpoint.
+    fn parse_expression(&self, tokens:&[Token]) -> Result<Expression, Vec<Diagnostic>>;
+}
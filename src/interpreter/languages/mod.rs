@@ -1 +1,116 @@
-pub mod visualg;
\ No newline at end of file
+pub mod portugol;
+pub mod visualg;
+
+use std::path::Path;
+
+use super::language::Language;
+
+/// A language the interpreter knows, with what identifies its programs.
+struct Registration {
+    name: &'static str,
+    /// File extensions written by the language's own editor, lowercase.
+    extensions: &'static [&'static str],
+    /// Words every program starts with, comments aside.
+    opening: &'static [&'static str],
+    create: fn() -> Box<dyn Language>,
+}
+
+/// Every language, the default first.
+const REGISTRY:&[Registration] = &[
+    Registration {
+        name: "visualg",
+        extensions: &["alg"],
+        opening: &["algoritmo"],
+        create: || Box::new(visualg::VisuAlg {}),
+    },
+    Registration {
+        name: "portugol",
+        extensions: &["por"],
+        opening: &["programa", "{"],
+        create: || Box::new(portugol::PortugolStudio {}),
+    },
+];
+
+/// Names accepted by [`by_name`], the default first.
+pub fn names() -> Vec<&'static str> {
+    REGISTRY.iter().map(|r| r.name).collect()
+}
+
+/// Language called `name`, ignoring case.
+pub fn by_name(name:&str) -> Option<Box<dyn Language>> {
+    let name = name.to_lowercase();
+    REGISTRY.iter().find(|r| r.name == name).map(|r| (r.create)())
+}
+
+/// A new instance of the registered `language`, for a second tokenizer
+/// beside the one that owns it.
+pub fn instance_of(language:&dyn Language) -> Option<Box<dyn Language>> {
+    REGISTRY.iter()
+        .map(|r| (r.create)())
+        .find(|created| created.name() == language.name())
+}
+
+/// Language whose editor saves files with the extension of `path`.
+/// Shared extensions such as `.txt` match none.
+pub fn by_extension(path:&str) -> Option<Box<dyn Language>> {
+    let extension = Path::new(path).extension()?.to_str()?.to_lowercase();
+    REGISTRY.iter()
+        .find(|r| r.extensions.contains(&extension.as_str()))
+        .map(|r| (r.create)())
+}
+
+/// Language whose programs start like `code`.
+pub fn by_content(code:&str) -> Option<Box<dyn Language>> {
+    REGISTRY.iter()
+        .map(|r| (r, (r.create)()))
+        .find(|(registration, language)| {
+            let words = opening_words(code, registration.opening.len());
+            words.len() == registration.opening.len()
+                && words.iter().zip(registration.opening).all(|(word, expected)| {
+                    if language.is_case_sensitive() { word == expected } else { word.to_lowercase() == *expected }
+                })
+        })
+        .map(|(_, language)| language)
+}
+
+/// Language of a program: the one its first words belong to, else the
+/// one of its file extension, else VisuAlg.
+pub fn detect(path:Option<&str>, code:&str) -> Box<dyn Language> {
+    by_content(code)
+        .or_else(|| path.and_then(by_extension))
+        .unwrap_or_else(|| (REGISTRY[0].create)())
+}
+
+/// Up to `count` words at the start of `code`, skipping blanks and `//`
+/// and `/* */` comments. Punctuation chars count as words.
+fn opening_words(code:&str, count:usize) -> Vec<String> {
+    let mut words = vec![];
+    let mut rest = code;
+
+    while words.len() < count {
+        rest = rest.trim_start();
+
+        if let Some(comment) = rest.strip_prefix("//") {
+            rest = comment.split_once('\n').map_or("", |(_, after)| after);
+            continue;
+        }
+        if let Some(comment) = rest.strip_prefix("/*") {
+            rest = comment.split_once("*/").map_or("", |(_, after)| after);
+            continue;
+        }
+
+        let Some(first) = rest.chars().next() else {
+            break;
+        };
+        let length = if first.is_alphanumeric() || first == '_' {
+            rest.find(|c:char| !c.is_alphanumeric() && c != '_').unwrap_or(rest.len())
+        } else {
+            first.len_utf8()
+        };
+
+        words.push(rest[..length].to_string());
+        rest = &rest[length..];
+    }
+
+    words
+}
@@ -0,0 +1,369 @@
+use crate::interpreter::tokens::schema::TokenTypes;
+use crate::interpreter::tokens::separator::{SeparatorSetting};
+
+use super::super::tokens::separator::TokenSeparators;
+
+use super::super::ast::expression::Expression;
+use super::super::ast::program::Program;
+use super::super::builtin::Builtin;
+use super::super::language::Language;
+use super::super::diagnostics::diagnostic::Diagnostic;
+use super::super::parser::portugol::PortugolParser;
+use super::super::rules::rule::{SyntaxRule, SyntaxGroup};
+use super::super::tokens::token::Token;
+use super::super::value::Value;
+
+/// The language of Portugol Studio: C-like braces and operators over
+/// Portuguese keywords, `programa { funcao inicio() { ... } }`.
+pub struct PortugolStudio;
+
+impl Language for PortugolStudio {
+    fn escape(&self) -> &str {
+        "\\"
+    }
+
+    fn unescape(&self, escaped:char) -> char {
+        match escaped {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            '0' => '\0',
+            other => other,
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Portugol Studio"
+    }
+
+    fn is_case_sensitive(&self) -> bool {
+        true
+    }
+
+    fn boolean(&self, word:&str) -> Option<bool> {
+        match word {
+            "verdadeiro" => Some(true),
+            "falso" => Some(false),
+            _ => None
+        }
+    }
+
+    /// Logical values in lowercase, and whole reals keep their `.0`.
+    fn write(&self, value:&Value) -> String {
+        match value {
+            Value::Real(real) if real.fract() == 0.0 && real.is_finite() => format!("{:.1}", real),
+            Value::Logico(true) => String::from("verdadeiro"),
+            Value::Logico(false) => String::from("falso"),
+            _ => value.to_string(),
+        }
+    }
+
+    fn separators(&self) -> Vec<TokenSeparators> {
+        vec![
+            TokenSeparators::InAlphaRange {
+                alphas: vec![ ' ', '\t' ],
+                settings: SeparatorSetting::new(TokenTypes::None)
+                    .gruppable()
+            },
+            TokenSeparators::Alpha {
+                alpha: '\n',
+                settings: SeparatorSetting::new(TokenTypes::None)
+                    .gruppable()
+            },
+            TokenSeparators::InAlphaRange {
+                alphas: vec![ '.', ',', ':', ';' ],
+                settings: SeparatorSetting::new(TokenTypes::Keyword)
+            },
+            TokenSeparators::Word {
+                word: String::from("-->"),
+                settings: SeparatorSetting::new(TokenTypes::Keyword)
+            },
+            TokenSeparators::InAlphaRange {
+                alphas: vec![ '[', ']', '(', ')', '{', '}' ],
+                settings: SeparatorSetting::new(TokenTypes::Groupper)
+            },
+            TokenSeparators::AlphaUntil {
+                alpha: '"',
+                end: Some('"'),
+                skip_content: false,
+                settings: SeparatorSetting::new(TokenTypes::String)
+            },
+            TokenSeparators::AlphaUntil {
+                alpha: '\'',
+                end: Some('\''),
+                skip_content: false,
+                settings: SeparatorSetting::new(TokenTypes::String)
+            },
+            TokenSeparators::WordUntil {
+                word: "//".to_string(),
+                end: None,
+                skip_content: false,
+                settings: SeparatorSetting::new(TokenTypes::LineComment).gruppable()
+            },
+            TokenSeparators::WordUntil {
+                word: "/*".to_string(),
+                end: Some("*/".to_string()),
+                skip_content: false,
+                settings: SeparatorSetting::new(TokenTypes::GroupComment)
+            },
+            TokenSeparators::InAlphaRange {
+                alphas: vec![ '+', '-', '*', '/', '%' ],
+                settings: SeparatorSetting::new(TokenTypes::Arithmetic)
+            },
+            TokenSeparators::InWordRange {
+                words: vec![
+                    String::from("=="), String::from("!="), String::from("<="), String::from(">="),
+                    String::from("<"), String::from(">"),
+                ],
+                settings: SeparatorSetting::new(TokenTypes::Relational)
+            },
+            TokenSeparators::InWordRange {
+                words: vec![ String::from("nao"), String::from("e"), String::from("ou") ],
+                settings: SeparatorSetting::new(TokenTypes::Logical)
+                    .whole_word()
+            },
+            TokenSeparators::InWordRange {
+                words: vec![
+                    String::from("="),
+                    String::from("+="), String::from("-="), String::from("*="), String::from("/="),
+                    String::from("++"), String::from("--"),
+                ],
+                settings: SeparatorSetting::new(TokenTypes::Assignment)
+            },
+            TokenSeparators::Alpha {
+                alpha: '&',
+                settings: SeparatorSetting::new(TokenTypes::Keyword)
+            },
+            TokenSeparators::InWordRange{
+                words: vec![
+                    String::from("programa"), String::from("funcao"),
+
+                    String::from("se"), String::from("senao"),
+
+                    String::from("enquanto"), String::from("faca"), String::from("para"),
+
+                    String::from("escolha"),
+                ],
+                settings: SeparatorSetting::new(TokenTypes::Groupper)
+                    .whole_word()
+            },
+            TokenSeparators::InWordRange{
+                words: vec![
+                    String::from("caso"), String::from("contrario"),
+
+                    String::from("pare"), String::from("retorne"),
+
+                    String::from("const"), String::from("inclua"), String::from("biblioteca"),
+                ],
+                settings: SeparatorSetting::new(TokenTypes::Keyword)
+                    .whole_word()
+            },
+            TokenSeparators::InWordRange{
+                words: vec![ String::from("verdadeiro"), String::from("falso") ],
+                settings: SeparatorSetting::new(TokenTypes::Boolean)
+                    .whole_word()
+            },
+            TokenSeparators::InWordRange{
+                words: vec![
+                    String::from("inteiro"), String::from("real"),
+                    String::from("cadeia"), String::from("caracter"),
+                    String::from("logico"), String::from("vazio"),
+                ],
+                settings: SeparatorSetting::new(TokenTypes::Type)
+                    .whole_word()
+            },
+        ]
+    }
+
+    fn syntax(&self) -> Vec<SyntaxGroup> {
+        use SyntaxRule as R;
+
+        let binary = |name:&str, operators:&[&str], operand:&str| SyntaxGroup::new(
+            name,
+            vec![
+                R::reference(operand),
+                R::sequence(vec![R::one_of(operators), R::reference(operand)]).optional().repeated(),
+            ]
+        );
+        let condition = || R::wrapped_by("(", ")", R::reference("expressao"));
+
+        vec![
+            SyntaxGroup::new("programa", vec![
+                R::keyword("programa"),
+                R::wrapped_by("{", "}", R::choice(vec![
+                    R::reference("inclusao"),
+                    R::reference("declaracao"),
+                    R::reference("funcao"),
+                ]).optional().repeated()),
+            ]),
+            SyntaxGroup::new("inclusao", vec![
+                R::keyword("inclua"),
+                R::keyword("biblioteca"),
+                R::identifier(),
+                R::sequence(vec![R::keyword("-->"), R::identifier()]).optional(),
+            ]),
+            SyntaxGroup::new("declaracao", vec![
+                R::keyword("const").optional(),
+                R::token(TokenTypes::Type),
+                R::sequence(vec![
+                    R::identifier(),
+                    R::wrapped_by("[", "]", R::numeric(false, false).optional()).optional().repeated(),
+                    R::sequence(vec![
+                        R::keyword("="),
+                        R::choice(vec![R::reference("inicializador"), R::reference("expressao")]),
+                    ]).optional(),
+                ]).separated_by(","),
+            ]),
+            SyntaxGroup::new("inicializador", vec![
+                R::wrapped_by("{", "}", R::choice(vec![
+                    R::reference("inicializador"),
+                    R::reference("expressao"),
+                ]).separated_by(",").optional()),
+            ]),
+            SyntaxGroup::new("funcao", vec![
+                R::keyword("funcao"),
+                R::token(TokenTypes::Type).optional(),
+                R::identifier(),
+                R::wrapped_by("(", ")", R::reference("parametro").separated_by(",").optional()),
+                R::reference("bloco"),
+            ]),
+            SyntaxGroup::new("parametro", vec![
+                R::token(TokenTypes::Type),
+                R::keyword("&").optional(),
+                R::identifier(),
+            ]),
+            SyntaxGroup::new("bloco", vec![
+                R::wrapped_by("{", "}", R::reference("comando").optional().repeated()),
+            ]),
+            SyntaxGroup::new("corpo", vec![
+                R::choice(vec![R::reference("bloco"), R::reference("comando")]),
+            ]),
+            SyntaxGroup::new("comando", vec![
+                R::choice(vec![
+                    R::reference("declaracao"),
+                    R::reference("se"),
+                    R::reference("enquanto"),
+                    R::reference("faca"),
+                    R::reference("para"),
+                    R::reference("escolha"),
+                    R::reference("escreva"),
+                    R::reference("leia"),
+                    R::reference("retorne"),
+                    R::keyword("pare"),
+                    R::reference("atribuicao"),
+                    R::reference("chamada"),
+                ]),
+            ]),
+            SyntaxGroup::new("se", vec![
+                R::keyword("se"),
+                condition(),
+                R::reference("corpo"),
+                R::sequence(vec![
+                    R::keyword("senao"),
+                    R::choice(vec![R::reference("se"), R::reference("corpo")]),
+                ]).optional(),
+            ]),
+            SyntaxGroup::new("enquanto", vec![
+                R::keyword("enquanto"),
+                condition(),
+                R::reference("corpo"),
+            ]),
+            SyntaxGroup::new("faca", vec![
+                R::keyword("faca"),
+                R::reference("bloco"),
+                R::keyword("enquanto"),
+                condition(),
+            ]),
+            SyntaxGroup::new("para", vec![
+                R::keyword("para"),
+                R::wrapped_by("(", ")", R::sequence(vec![
+                    R::choice(vec![R::reference("declaracao"), R::reference("atribuicao")]).optional(),
+                    R::keyword(";"),
+                    R::reference("expressao").optional(),
+                    R::keyword(";"),
+                    R::reference("atribuicao").optional(),
+                ])),
+                R::reference("corpo"),
+            ]),
+            SyntaxGroup::new("escolha", vec![
+                R::keyword("escolha"),
+                condition(),
+                R::wrapped_by("{", "}", R::reference("caso").optional().repeated()),
+            ]),
+            SyntaxGroup::new("caso", vec![
+                R::keyword("caso"),
+                R::choice(vec![R::keyword("contrario"), R::reference("expressao")]),
+                R::keyword(":"),
+                R::reference("comando").optional().repeated(),
+            ]),
+            SyntaxGroup::new("escreva", vec![
+                R::keyword("escreva"),
+                R::reference("argumentos"),
+            ]),
+            SyntaxGroup::new("leia", vec![
+                R::keyword("leia"),
+                R::wrapped_by("(", ")", R::reference("alvo").separated_by(",")),
+            ]),
+            SyntaxGroup::new("retorne", vec![
+                R::keyword("retorne"),
+                R::reference("expressao").optional(),
+            ]),
+            SyntaxGroup::new("atribuicao", vec![
+                R::reference("alvo"),
+                R::choice(vec![
+                    R::sequence(vec![R::one_of(&["=", "+=", "-=", "*=", "/="]), R::reference("expressao")]),
+                    R::one_of(&["++", "--"]),
+                ]),
+            ]),
+            SyntaxGroup::new("alvo", vec![
+                R::identifier(),
+                R::wrapped_by("[", "]", R::reference("expressao")).optional().repeated(),
+            ]),
+            SyntaxGroup::new("chamada", vec![
+                R::identifier(),
+                R::reference("argumentos"),
+            ]),
+            SyntaxGroup::new("argumentos", vec![
+                R::wrapped_by("(", ")", R::reference("expressao").separated_by(",").optional()),
+            ]),
+            SyntaxGroup::new("expressao", vec![R::reference("ou")]),
+            binary("ou", &["ou"], "e"),
+            binary("e", &["e"], "nao"),
+            SyntaxGroup::new("nao", vec![
+                R::keyword("nao").optional().repeated(),
+                R::reference("relacional"),
+            ]),
+            binary("relacional", &["==", "!=", "<", "<=", ">", ">="], "aditiva"),
+            binary("aditiva", &["+", "-"], "multiplicativa"),
+            binary("multiplicativa", &["*", "/", "%"], "unaria"),
+            SyntaxGroup::new("unaria", vec![
+                R::one_of(&["-", "+"]).optional().repeated(),
+                R::reference("primaria"),
+            ]),
+            SyntaxGroup::new("primaria", vec![
+                R::choice(vec![
+                    R::numeric(true, false),
+                    R::text(),
+                    R::token(TokenTypes::Boolean),
+                    R::wrapped_by("(", ")", R::reference("expressao")),
+                    R::reference("chamada"),
+                    R::reference("alvo"),
+                ]),
+            ]),
+        ]
+    }
+
+    /// Portugol Studio keeps its functions in libraries, which `inclua`
+    /// cannot load yet.
+    fn builtins(&self) -> Vec<Box<dyn Builtin>> {
+        vec![]
+    }
+
+    fn parse(&self, tokens:&[Token]) -> Result<Program, Vec<Diagnostic>> {
+        PortugolParser::new(tokens).parse()
+    }
+
+    fn parse_expression(&self, tokens:&[Token]) -> Result<Expression, Vec<Diagnostic>> {
+        PortugolParser::new(tokens).parse_expression()
+    }
+}
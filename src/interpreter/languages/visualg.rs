@@ -3,8 +3,16 @@ use crate::interpreter::tokens::separator::{SeparatorSetting};
 
 use super::super::tokens::separator::TokenSeparators;
 
+use super::super::ast::expression::Expression;
+use super::super::ast::program::Program;
+use super::super::builtin::Builtin;
+use super::super::builtins;
 use super::super::language::Language;
-use super::super::rules::rule::{SyntaxRule, SyntaxType, SyntaxGroup};
+use super::super::diagnostics::diagnostic::Diagnostic;
+use super::super::parser::visualg::VisuAlgParser;
+use super::super::rules::rule::{SyntaxRule, SyntaxGroup};
+use super::super::tokens::token::Token;
+use super::super::value::Value;
 
 pub struct VisuAlg;
 
@@ -13,10 +21,36 @@ impl Language for VisuAlg {
         "\\"
     }
 
+    fn unescape(&self, escaped:char) -> char {
+        escaped
+    }
+
     fn name(&self) -> &str {
         "VisuAlg"
     }
 
+    fn is_case_sensitive(&self) -> bool {
+        false
+    }
+
+    fn boolean(&self, word:&str) -> Option<bool> {
+        match word.to_lowercase().as_str() {
+            "verdadeiro" => Some(true),
+            "falso" => Some(false),
+            _ => None
+        }
+    }
+
+    /// Non-negative numbers get a leading space, where the sign of
+    /// negatives goes.
+    fn write(&self, value:&Value) -> String {
+        match value {
+            Value::Inteiro(n) if *n >= 0 => format!(" {}", value),
+            Value::Real(r) if !r.is_sign_negative() => format!(" {}", value),
+            _ => value.to_string(),
+        }
+    }
+
     fn separators(&self) -> Vec<TokenSeparators> {
         vec![
             TokenSeparators::Alpha {
@@ -41,7 +75,7 @@ impl Language for VisuAlg {
                 alpha: '"',
                 end: Some('"'),
                 skip_content: false,
-                settings: SeparatorSetting::new(TokenTypes::Groupper)
+                settings: SeparatorSetting::new(TokenTypes::String)
             },
             TokenSeparators::WordUntil {
                 word: "//".to_string(),
@@ -56,15 +90,31 @@ impl Language for VisuAlg {
                 settings: SeparatorSetting::new(TokenTypes::GroupComment)
             },
             TokenSeparators::InAlphaRange {
-                alphas: vec![ '+', '-', '*', '/' ],
+                alphas: vec![ '+', '-', '*', '/', '%', '\\', '^' ],
+                settings: SeparatorSetting::new(TokenTypes::Arithmetic)
+            },
+            TokenSeparators::InWordRange {
+                words: vec![ String::from("mod"), String::from("div") ],
                 settings: SeparatorSetting::new(TokenTypes::Arithmetic)
+                    .whole_word()
             },
             TokenSeparators::InWordRange {
-                words: vec![ String::from("and"), String::from("or") ],
-                settings: SeparatorSetting::new(TokenTypes::Operator)
+                words: vec![
+                    String::from("<>"), String::from("<="), String::from(">="),
+                    String::from("<"), String::from(">"), String::from("="),
+                ],
+                settings: SeparatorSetting::new(TokenTypes::Relational)
             },
-            TokenSeparators::Word {
-                word: String::from("<-"),
+            TokenSeparators::InWordRange {
+                words: vec![
+                    String::from("nao"), String::from("e"), String::from("ou"), String::from("xou"),
+                    String::from("and"), String::from("or"),
+                ],
+                settings: SeparatorSetting::new(TokenTypes::Logical)
+                    .whole_word()
+            },
+            TokenSeparators::InWordRange {
+                words: vec![ String::from("<-"), String::from(":=") ],
                 settings: SeparatorSetting::new(TokenTypes::Assignment)
             },
             TokenSeparators::InWordRange{
@@ -86,22 +136,27 @@ impl Language for VisuAlg {
                     String::from("funcao"), String::from("fimfuncao"),
                 ],
                 settings: SeparatorSetting::new(TokenTypes::Groupper)
+                    .whole_word()
             },
             TokenSeparators::InWordRange{
                 words: vec![
-                    String::from("inicio"), String::from("var"),
+                    String::from("inicio"), String::from("var"), String::from("vetor"),
 
                     String::from("entao"), String::from("senao"),
 
                     String::from("ate"), String::from("passo"),
 
-                    String::from("ate"),
-
                     String::from("caso"), String::from("outrocaso"),
                     
-                    String::from("retorne"), String::from("verdadeiro"), String::from("falso")
+                    String::from("retorne")
                 ],
                 settings: SeparatorSetting::new(TokenTypes::Keyword)
+                    .whole_word()
+            },
+            TokenSeparators::InWordRange{
+                words: vec![ String::from("verdadeiro"), String::from("falso") ],
+                settings: SeparatorSetting::new(TokenTypes::Boolean)
+                    .whole_word()
             },
             TokenSeparators::InWordRange{
                 words: vec![
@@ -109,19 +164,245 @@ impl Language for VisuAlg {
                     String::from("caractere"), String::from("logico")
                 ],
                 settings: SeparatorSetting::new(TokenTypes::Type)
+                    .whole_word()
             },
         ]
     }
 
-    fn syntax() -> Vec<SyntaxGroup> {
+    fn syntax(&self) -> Vec<SyntaxGroup> {
+        use SyntaxRule as R;
+
+        let block = |end:&[&str]| R::sequence(vec![
+            R::reference("comando").optional().repeated(),
+            R::one_of(end),
+        ]);
+
+        let binary = |name:&str, operators:&[&str], operand:&str| SyntaxGroup::new(
+            name,
+            vec![
+                R::reference(operand),
+                R::sequence(vec![R::one_of(operators), R::reference(operand)]).optional().repeated(),
+            ]
+        );
+
         vec![
-            SyntaxGroup::new(
-                vec![
-                    SyntaxRule::new(
-                        SyntaxType::Keyword("if".to_string())
-                    )
-                ]
-            )
+            SyntaxGroup::new("algoritmo", vec![
+                R::keyword("algoritmo"),
+                R::text().optional(),
+                R::choice(vec![
+                    R::reference("variaveis"),
+                    R::reference("subprograma"),
+                ]).optional().repeated(),
+                R::keyword("inicio"),
+                block(&["fimalgoritmo"]),
+            ]),
+            SyntaxGroup::new("variaveis", vec![
+                R::keyword("var"),
+                R::reference("declaracao").optional().repeated(),
+            ]),
+            SyntaxGroup::new("declaracao", vec![
+                R::identifier().separated_by(","),
+                R::keyword(":"),
+                R::reference("tipo"),
+            ]),
+            SyntaxGroup::new("tipo", vec![
+                R::choice(vec![
+                    R::sequence(vec![
+                        R::keyword("vetor"),
+                        R::wrapped_by("[", "]", R::reference("limites").separated_by(",")),
+                        R::keyword("de"),
+                        R::reference("tipo_simples"),
+                    ]),
+                    R::reference("tipo_simples"),
+                ]),
+            ]),
+            SyntaxGroup::new("tipo_simples", vec![
+                R::choice(vec![
+                    R::token(TokenTypes::Type),
+                    R::one_of(&["caracter", "literal", "numerico"]),
+                ]),
+            ]),
+            SyntaxGroup::new("limites", vec![
+                R::keyword("-").optional(),
+                R::numeric(false, false),
+                R::keyword("."),
+                R::keyword("."),
+                R::keyword("-").optional(),
+                R::numeric(false, false),
+            ]),
+            SyntaxGroup::new("subprograma", vec![
+                R::choice(vec![
+                    R::sequence(vec![
+                        R::keyword("procedimento"),
+                        R::identifier(),
+                        R::reference("parametros").optional(),
+                        R::reference("variaveis").optional(),
+                        R::keyword("inicio").optional(),
+                        block(&["fimprocedimento"]),
+                    ]),
+                    R::sequence(vec![
+                        R::keyword("funcao"),
+                        R::identifier(),
+                        R::reference("parametros").optional(),
+                        R::keyword(":"),
+                        R::reference("tipo"),
+                        R::reference("variaveis").optional(),
+                        R::keyword("inicio").optional(),
+                        block(&["fimfuncao"]),
+                    ]),
+                ]),
+            ]),
+            SyntaxGroup::new("parametros", vec![
+                R::wrapped_by("(", ")", R::reference("parametro").separated_by(";").optional()),
+            ]),
+            SyntaxGroup::new("parametro", vec![
+                R::keyword("var").optional(),
+                R::reference("declaracao"),
+            ]),
+            SyntaxGroup::new("comando", vec![
+                R::choice(vec![
+                    R::reference("se"),
+                    R::reference("enquanto"),
+                    R::reference("para"),
+                    R::reference("repita"),
+                    R::reference("escolha"),
+                    R::reference("escreva"),
+                    R::reference("leia"),
+                    R::reference("retorne"),
+                    R::keyword("interrompa"),
+                    R::reference("atribuicao"),
+                    R::reference("chamada"),
+                ]),
+            ]),
+            SyntaxGroup::new("se", vec![
+                R::keyword("se"),
+                R::reference("expressao"),
+                R::keyword("entao"),
+                R::reference("comando").optional().repeated(),
+                R::sequence(vec![
+                    R::keyword("senao"),
+                    R::reference("comando").optional().repeated(),
+                ]).optional(),
+                R::keyword("fimse"),
+            ]),
+            SyntaxGroup::new("enquanto", vec![
+                R::keyword("enquanto"),
+                R::reference("expressao"),
+                R::keyword("faca"),
+                block(&["fimenquanto"]),
+            ]),
+            SyntaxGroup::new("para", vec![
+                R::keyword("para"),
+                R::identifier(),
+                R::keyword("de"),
+                R::reference("expressao"),
+                R::keyword("ate"),
+                R::reference("expressao"),
+                R::sequence(vec![R::keyword("passo"), R::reference("expressao")]).optional(),
+                R::keyword("faca"),
+                block(&["fimpara"]),
+            ]),
+            SyntaxGroup::new("repita", vec![
+                R::keyword("repita"),
+                R::reference("comando").optional().repeated(),
+                R::choice(vec![
+                    R::sequence(vec![R::keyword("ate"), R::reference("expressao")]),
+                    R::keyword("fimrepita"),
+                ]),
+            ]),
+            SyntaxGroup::new("escolha", vec![
+                R::keyword("escolha"),
+                R::reference("expressao"),
+                R::reference("caso").optional().repeated(),
+                R::sequence(vec![
+                    R::keyword("outrocaso"),
+                    R::reference("comando").optional().repeated(),
+                ]).optional(),
+                R::keyword("fimescolha"),
+            ]),
+            SyntaxGroup::new("caso", vec![
+                R::keyword("caso"),
+                R::sequence(vec![
+                    R::reference("expressao"),
+                    R::sequence(vec![R::keyword("ate"), R::reference("expressao")]).optional(),
+                ]).separated_by(","),
+                R::reference("comando").optional().repeated(),
+            ]),
+            SyntaxGroup::new("escreva", vec![
+                R::one_of(&["escreva", "escreval"]),
+                R::wrapped_by("(", ")", R::sequence(vec![
+                    R::reference("expressao"),
+                    R::sequence(vec![R::keyword(":"), R::reference("expressao")]).optional().repeated(),
+                ]).separated_by(",").optional()).optional(),
+            ]),
+            SyntaxGroup::new("leia", vec![
+                R::keyword("leia"),
+                R::wrapped_by("(", ")", R::reference("alvo").separated_by(",")),
+            ]),
+            SyntaxGroup::new("retorne", vec![
+                R::keyword("retorne"),
+                R::reference("expressao").optional(),
+            ]),
+            SyntaxGroup::new("atribuicao", vec![
+                R::reference("alvo"),
+                R::token(TokenTypes::Assignment),
+                R::reference("expressao"),
+            ]),
+            SyntaxGroup::new("alvo", vec![
+                R::identifier(),
+                R::reference("indices").optional().repeated(),
+            ]),
+            SyntaxGroup::new("indices", vec![
+                R::wrapped_by("[", "]", R::reference("expressao").separated_by(",")),
+            ]),
+            SyntaxGroup::new("chamada", vec![
+                R::identifier(),
+                R::reference("argumentos").optional(),
+            ]),
+            SyntaxGroup::new("argumentos", vec![
+                R::wrapped_by("(", ")", R::reference("expressao").separated_by(",").optional()),
+            ]),
+            SyntaxGroup::new("expressao", vec![R::reference("ou")]),
+            binary("ou", &["ou", "or", "xou"], "e"),
+            binary("e", &["e", "and"], "nao"),
+            SyntaxGroup::new("nao", vec![
+                R::keyword("nao").optional().repeated(),
+                R::reference("relacional"),
+            ]),
+            binary("relacional", &["=", "<>", "<", "<=", ">", ">="], "aditiva"),
+            binary("aditiva", &["+", "-"], "multiplicativa"),
+            binary("multiplicativa", &["*", "/", "\\", "%", "mod", "div"], "unaria"),
+            SyntaxGroup::new("unaria", vec![
+                R::one_of(&["-", "+"]).optional().repeated(),
+                R::reference("potencia"),
+            ]),
+            // `^` binds tighter than the sign and groups to the right.
+            SyntaxGroup::new("potencia", vec![
+                R::reference("primaria"),
+                R::sequence(vec![R::keyword("^"), R::reference("unaria")]).optional(),
+            ]),
+            SyntaxGroup::new("primaria", vec![
+                R::choice(vec![
+                    R::numeric(true, false),
+                    R::text(),
+                    R::token(TokenTypes::Boolean),
+                    R::wrapped_by("(", ")", R::reference("expressao")),
+                    R::sequence(vec![R::identifier(), R::reference("indices").repeated()]),
+                    R::reference("chamada"),
+                ]),
+            ]),
         ]
     }
+
+    fn builtins(&self) -> Vec<Box<dyn Builtin>> {
+        builtins::visualg()
+    }
+
+    fn parse(&self, tokens:&[Token]) -> Result<Program, Vec<Diagnostic>> {
+        VisuAlgParser::new(tokens).parse()
+    }
+
+    fn parse_expression(&self, tokens:&[Token]) -> Result<Expression, Vec<Diagnostic>> {
+        VisuAlgParser::new(tokens).parse_expression()
+    }
 }
\ No newline at end of file
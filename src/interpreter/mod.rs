@@ -1,8 +1,20 @@
+pub mod ast;
+pub mod builtin;
+pub mod builtins;
+pub mod console;
+pub mod debug;
+pub mod diagnostics;
+pub mod encoding;
 pub mod syntaxer;
+pub mod parser;
+pub mod random;
 pub mod runner;
+pub mod semantic;
+pub mod value;
 pub mod language;
 pub mod languages;
 pub mod tokens;
 pub mod tokenizer;
+#[allow(clippy::module_inception)]
 pub mod interpreter;
-pub mod rules;
\ No newline at end of file
+pub mod rules;
@@ -0,0 +1,286 @@
+use crate::interpreter::ast::expression::Literal;
+use crate::interpreter::diagnostics::codes;
+use crate::interpreter::diagnostics::diagnostic::Diagnostic;
+use crate::interpreter::tokens::schema::TokenTypes;
+use crate::interpreter::tokens::span::{Position, Span};
+use crate::interpreter::tokens::token::Token;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LexemeKind {
+    Word,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Boolean(bool),
+    Symbol,
+}
+
+/// Parser-level unit built from one or more tokens: whitespace and comments
+/// are dropped, literals take the value the tokenizer attached and the
+/// `unknown` runs left by the tokenizer are split into words, numbers and
+/// symbols.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lexeme {
+    kind: LexemeKind,
+    text: String,
+    span: Span,
+    schema: TokenTypes,
+}
+
+impl Lexeme {
+    fn new(kind:LexemeKind, text:String, span:Span, schema:TokenTypes) -> Self {
+        Self { kind, text, span, schema }
+    }
+
+    pub fn kind(&self) -> &LexemeKind {
+        &self.kind
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Type of the token the lexeme came from.
+    pub fn schema(&self) -> &TokenTypes {
+        &self.schema
+    }
+
+    pub fn is_word(&self, word:&str) -> bool {
+        self.kind == LexemeKind::Word && self.text.eq_ignore_ascii_case(word)
+    }
+
+    pub fn is_symbol(&self, symbol:&str) -> bool {
+        self.kind == LexemeKind::Symbol && self.text == symbol
+    }
+}
+
+/// Most statements and expressions a parser may have open inside one
+/// another. The parsers recurse once per level, so deeper code would
+/// exhaust the native stack.
+pub const MAX_NESTING:usize = 128;
+
+pub struct TokenCursor {
+    lexemes: Vec<Lexeme>,
+    position: usize,
+    end: Span,
+    case_sensitive: bool,
+    /// Statements and expressions open around the position.
+    depth: usize,
+}
+
+impl TokenCursor {
+    pub fn new(tokens:&[Token]) -> Self {
+        let end = tokens.last()
+            .map_or(Span::default(), |t| Span::new(t.span().end(), t.span().end()));
+
+        let mut cursor = Self { lexemes: vec![], position: 0, end, case_sensitive: false, depth: 0 };
+        for token in tokens {
+            cursor.normalize(token);
+        }
+        cursor
+    }
+
+    /// Only match words spelled with the same case.
+    pub fn case_sensitive(mut self) -> Self {
+        self.case_sensitive = true;
+        self
+    }
+
+    fn normalize(&mut self, token:&Token) {
+        let schema = token.schema();
+        let value = token.value();
+        let span = *token.span();
+
+        let literal = match token.literal() {
+            Some(Literal::Integer(integer)) => Some(LexemeKind::Integer(*integer)),
+            Some(Literal::Real(real)) => Some(LexemeKind::Real(*real)),
+            Some(Literal::String(text)) => Some(LexemeKind::Text(text.clone())),
+            Some(Literal::Boolean(boolean)) => Some(LexemeKind::Boolean(*boolean)),
+            None => None,
+        };
+
+        if let Some(kind) = literal {
+            self.push(Lexeme::new(kind, value.clone(), span, schema.clone()));
+            return;
+        }
+
+        match schema {
+            // Unterminated strings are reported by the tokenizer.
+            TokenTypes::None | TokenTypes::LineComment | TokenTypes::GroupComment | TokenTypes::String => {},
+            TokenTypes::Custom { .. } => self.split(value, span.start(), schema),
+            _ => {
+                // Undo the tokenizer grouping, one lexeme per repeat.
+                let mut start = span.start();
+                for _ in 0..*token.count() {
+                    let end = start.advance(value);
+                    let kind = if value.starts_with(|c:char| c.is_alphabetic()) {
+                        LexemeKind::Word
+                    } else {
+                        LexemeKind::Symbol
+                    };
+                    self.push(Lexeme::new(kind, value.clone(), Span::new(start, end), schema.clone()));
+                    start = end;
+                }
+            }
+        }
+    }
+
+    /// Split an `unknown` token into words, integers and symbols.
+    fn split(&mut self, text:&str, start:Position, schema:&TokenTypes) {
+        let chars:Vec<char> = text.chars().collect();
+        let mut index = 0;
+
+        while index < chars.len() {
+            let c = chars[index];
+            let begin = index;
+
+            if c.is_whitespace() {
+                index += 1;
+                continue;
+            }
+
+            let kind = if c.is_alphabetic() || c == '_' {
+                while index < chars.len() && (chars[index].is_alphanumeric() || chars[index] == '_') {
+                    index += 1;
+                }
+                LexemeKind::Word
+            } else if c.is_ascii_digit() {
+                while index < chars.len() && chars[index].is_ascii_digit() {
+                    index += 1;
+                }
+                let digits:String = chars[begin..index].iter().collect();
+                match digits.parse() {
+                    Ok(value) => LexemeKind::Integer(value),
+                    Err(_) => LexemeKind::Real(digits.parse().unwrap_or(f64::INFINITY)),
+                }
+            } else {
+                index += 1;
+                if let Some(&next) = chars.get(index) {
+                    if matches!((c, next), ('<', '>') | ('<', '=') | ('>', '=')) {
+                        index += 1;
+                    }
+                }
+                LexemeKind::Symbol
+            };
+
+            let piece:String = chars[begin..index].iter().collect();
+            let prefix:String = chars[..begin].iter().collect();
+            let piece_start = start.advance(&prefix);
+            let span = Span::new(piece_start, piece_start.advance(&piece));
+
+            self.push(Lexeme::new(kind, piece, span, schema.clone()));
+        }
+    }
+
+    fn push(&mut self, lexeme:Lexeme) {
+        self.lexemes.push(lexeme);
+    }
+
+    pub fn peek(&self) -> Option<&Lexeme> {
+        self.lexemes.get(self.position)
+    }
+
+    pub fn peek_at(&self, offset:usize) -> Option<&Lexeme> {
+        self.lexemes.get(self.position + offset)
+    }
+
+    pub fn advance(&mut self) -> Option<Lexeme> {
+        let lexeme = self.lexemes.get(self.position).cloned();
+        if lexeme.is_some() {
+            self.position += 1;
+        }
+        lexeme
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Go back to a position returned by [`TokenCursor::position`].
+    pub fn rewind(&mut self, position:usize) {
+        self.position = position.min(self.lexemes.len());
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.position >= self.lexemes.len()
+    }
+
+    /// Span of the next lexeme, or the end of the source.
+    pub fn span(&self) -> Span {
+        self.peek().map_or(self.end, |l| l.span)
+    }
+
+    /// Span of the last consumed lexeme.
+    pub fn previous_span(&self) -> Span {
+        if self.position == 0 {
+            return self.span();
+        }
+        self.lexemes[self.position - 1].span
+    }
+
+    pub fn check_word(&self, word:&str) -> bool {
+        self.peek().is_some_and(|l| l.is_word(word) && (!self.case_sensitive || l.text() == word))
+    }
+
+    pub fn check_symbol(&self, symbol:&str) -> bool {
+        self.peek().is_some_and(|l| l.is_symbol(symbol))
+    }
+
+    pub fn match_word(&mut self, word:&str) -> bool {
+        if self.check_word(word) {
+            self.position += 1;
+            return true;
+        }
+        false
+    }
+
+    pub fn match_symbol(&mut self, symbol:&str) -> bool {
+        if self.check_symbol(symbol) {
+            self.position += 1;
+            return true;
+        }
+        false
+    }
+
+    /// Open a statement or expression at the position, failing with
+    /// [`codes::NESTING_TOO_DEEP`] past [`MAX_NESTING`] levels. Pair each
+    /// success with [`TokenCursor::leave`].
+    pub fn enter(&mut self) -> Result<(), Diagnostic> {
+        if self.depth >= MAX_NESTING {
+            return Err(
+                Diagnostic::error(
+                    codes::NESTING_TOO_DEEP,
+                    format!("código aninhado em mais de {} níveis", MAX_NESTING),
+                    self.span()
+                )
+                .with_label("aninhamento máximo atingido aqui")
+                .with_note("divida a expressão ou o bloco em partes menores")
+            );
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    pub fn leave(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+
+    /// Skip every remaining lexeme.
+    pub fn skip_all(&mut self) {
+        self.position = self.lexemes.len();
+    }
+
+    /// Skip the remaining lexemes of the line holding `span`.
+    pub fn skip_line(&mut self, span:Span) {
+        while let Some(lexeme) = self.peek() {
+            if lexeme.span.start().line() > span.start().line() {
+                break;
+            }
+            self.position += 1;
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod cursor;
+pub mod portugol;
+pub mod visualg;
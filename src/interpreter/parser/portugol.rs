@@ -0,0 +1,953 @@
+use crate::interpreter::ast::expression::{BinaryOperator, Expression, ExpressionKind, Literal, UnaryOperator};
+use crate::interpreter::ast::program::{Parameter, Program, Subprogram, SubprogramKind, VarDeclaration};
+use crate::interpreter::ast::statement::{Case, CaseLabel, Statement, StatementKind, Target, WriteArgument};
+use crate::interpreter::ast::types::{Bounds, Identifier, TypeKind, TypeSpec};
+use crate::interpreter::diagnostics::codes;
+use crate::interpreter::diagnostics::diagnostic::Diagnostic;
+use crate::interpreter::tokens::span::Span;
+use crate::interpreter::tokens::token::Token;
+
+use super::cursor::{LexemeKind, TokenCursor};
+
+const RESERVED:&[&str] = &[
+    "programa", "funcao", "inclua", "biblioteca", "const",
+    "se", "senao", "enquanto", "faca", "para",
+    "escolha", "caso", "contrario", "pare", "retorne",
+    "escreva", "leia", "limpa",
+    "inteiro", "real", "cadeia", "caracter", "logico", "vazio",
+    "e", "ou", "nao", "verdadeiro", "falso",
+];
+
+/// Name of the function a program starts at.
+const ENTRY_POINT:&str = "inicio";
+
+type ParseResult<T> = Result<T, Diagnostic>;
+
+/// Recursive descent parser for Portugol Studio programs. The program is
+/// lowered to the same AST as VisuAlg: `inicio` becomes the program body,
+/// variables declared inside blocks are hoisted to the enclosing function
+/// with their initial values left in place as assignments, `para` becomes
+/// a `while` loop and `faca ... enquanto` a `repeat ... until`.
+pub struct PortugolParser {
+    cursor: TokenCursor,
+    errors: Vec<Diagnostic>,
+    /// Variables declared so far in the function being parsed.
+    locals: Vec<VarDeclaration>,
+}
+
+impl PortugolParser {
+    pub fn new(tokens:&[Token]) -> Self {
+        Self { cursor: TokenCursor::new(tokens).case_sensitive(), errors: vec![], locals: vec![] }
+    }
+
+    pub fn parse(mut self) -> Result<Program, Vec<Diagnostic>> {
+        match self.program() {
+            Ok(program) if self.errors.is_empty() => Ok(program),
+            Ok(_) => Err(Self::too_deep_alone(self.errors)),
+            Err(error) => {
+                self.errors.push(error);
+                Err(Self::too_deep_alone(self.errors))
+            }
+        }
+    }
+
+    /// Code nested too deeply leaves every block around it unclosed, so
+    /// only the nesting error is worth reporting.
+    fn too_deep_alone(errors:Vec<Diagnostic>) -> Vec<Diagnostic> {
+        match errors.iter().find(|e| e.code() == codes::NESTING_TOO_DEEP) {
+            Some(error) => vec![error.clone()],
+            None => errors,
+        }
+    }
+
+    /// A lone expression, with nothing after it.
+    pub fn parse_expression(mut self) -> Result<Expression, Vec<Diagnostic>> {
+        let expression = self.expression().map_err(|error| vec![error])?;
+
+        if let Some(lexeme) = self.cursor.peek() {
+            self.errors.push(Diagnostic::error(
+                codes::TRAILING_CONTENT,
+                format!("conteúdo inesperado após a expressão: '{}'", lexeme.text()),
+                lexeme.span()
+            ));
+        }
+
+        if self.errors.is_empty() {
+            Ok(expression)
+        } else {
+            Err(self.errors)
+        }
+    }
+
+    fn program(&mut self) -> ParseResult<Program> {
+        let start = self.cursor.span();
+        self.expect_word("programa")?;
+        self.expect_symbol("{")?;
+
+        let mut initializers = vec![];
+        let mut functions = vec![];
+
+        while !self.cursor.is_at_end() && !self.cursor.check_symbol("}") {
+            if self.cursor.check_word("inclua") {
+                let error = self.library()?;
+                self.errors.push(error);
+            } else if self.cursor.check_word("funcao") {
+                functions.push(self.subprogram()?);
+            } else {
+                let statements = self.declaration()?;
+                initializers.extend(statements);
+            }
+        }
+        self.expect_closing("}", "programa", start)?;
+
+        if let Some(lexeme) = self.cursor.peek() {
+            return Err(
+                Diagnostic::error(
+                    codes::TRAILING_CONTENT,
+                    format!("conteúdo inesperado após o fim do programa: '{}'", lexeme.text()),
+                    lexeme.span()
+                )
+                .with_note("o programa termina na '}' que fecha 'programa'")
+            );
+        }
+
+        let declarations = std::mem::take(&mut self.locals);
+        let span = start.join(&self.cursor.previous_span());
+
+        let Some(entry) = functions.iter().position(|f| f.name.name == ENTRY_POINT) else {
+            return Err(
+                Diagnostic::error(
+                    codes::MISSING_ENTRY_POINT,
+                    format!("função '{}' não encontrada", ENTRY_POINT),
+                    start
+                )
+                .with_note("o programa começa pela função 'inicio', declare 'funcao inicio() { ... }'")
+            );
+        };
+        let entry = functions.remove(entry);
+
+        if let Some(parameter) = entry.parameters.first() {
+            self.errors.push(
+                Diagnostic::error(
+                    codes::UNEXPECTED_TOKEN,
+                    format!("a função '{}' não recebe parâmetros", ENTRY_POINT),
+                    parameter.span
+                )
+            );
+        }
+
+        let mut body = initializers;
+        body.extend(entry.body);
+
+        Ok(Program {
+            name: None,
+            declarations: declarations.into_iter().chain(entry.declarations).collect(),
+            subprograms: functions,
+            body,
+            span,
+        })
+    }
+
+    /// `inclua biblioteca Nome [--> apelido]`, returned as the error that
+    /// libraries are not available yet.
+    fn library(&mut self) -> ParseResult<Diagnostic> {
+        let start = self.cursor.span();
+        self.expect_word("inclua")?;
+        self.expect_word("biblioteca")?;
+        let name = self.identifier()?;
+        if self.cursor.match_symbol("-->") {
+            self.identifier()?;
+        }
+
+        Ok(
+            Diagnostic::error(
+                codes::UNSUPPORTED_LIBRARY,
+                format!("a biblioteca '{}' não está disponível", name.name),
+                start.join(&self.cursor.previous_span())
+            )
+            .with_label("incluída aqui")
+        )
+    }
+
+    /// `[const] tipo a [= valor], v[3] [= {1, 2, 3}]`. The names are added
+    /// to [`PortugolParser::locals`] and the initial values are returned
+    /// as assignments, one per array element.
+    fn declaration(&mut self) -> ParseResult<Vec<Statement>> {
+        let start = self.cursor.span();
+        self.cursor.match_word("const");
+        let type_spec = self.type_spec()?;
+
+        let mut names = vec![];
+        let mut arrays = vec![];
+        let mut assignments = vec![];
+        loop {
+            let name = self.identifier()?;
+            let sizes = self.sizes()?;
+
+            let mut lengths = vec![0; sizes.len()];
+            if self.cursor.match_symbol("=") {
+                if !sizes.is_empty() && self.cursor.check_symbol("{") {
+                    let mut elements = vec![];
+                    self.initializer(&mut vec![], &mut lengths, &mut elements)?;
+                    for (indices, value) in elements {
+                        let indices = indices.into_iter()
+                            .map(|index| Expression::new(ExpressionKind::Literal(Literal::Integer(index)), value.span))
+                            .collect();
+                        let span = value.span;
+                        assignments.push(Statement::new(StatementKind::Assign { target: Target::new(name.clone(), indices), value }, span));
+                    }
+                } else {
+                    let value = self.expression()?;
+                    let span = name.span.join(&value.span);
+                    assignments.push(Statement::new(StatementKind::Assign { target: Target::new(name.clone(), vec![]), value }, span));
+                }
+            }
+
+            if sizes.is_empty() {
+                names.push(name);
+            } else {
+                let mut dimensions = vec![];
+                for ((size, span), length) in sizes.into_iter().zip(lengths) {
+                    let size = match size {
+                        Some(size) => size,
+                        None if length > 0 => length as i64,
+                        None => return Err(
+                            Diagnostic::error(codes::INVALID_BOUNDS, format!("tamanho do vetor '{}' não informado", name.name), span)
+                                .with_label("tamanho esperado aqui")
+                                .with_note("informe o tamanho, como v[10], ou os valores iniciais, como v[] = {1, 2, 3}")
+                        ),
+                    };
+                    dimensions.push(Bounds { lower: 0, upper: size - 1, span });
+                }
+                let span = start.join(&self.cursor.previous_span());
+                arrays.push(VarDeclaration {
+                    names: vec![name],
+                    type_spec: TypeSpec { kind: type_spec.kind, dimensions, span: type_spec.span },
+                    span,
+                });
+            }
+
+            if !self.cursor.match_symbol(",") {
+                break;
+            }
+        }
+
+        if !names.is_empty() {
+            self.hoist(VarDeclaration { names, type_spec, span: start.join(&self.cursor.previous_span()) });
+        }
+        for array in arrays {
+            self.hoist(array);
+        }
+        Ok(assignments)
+    }
+
+    /// `[3][2]` after a declared name, `None` for a size left as `[]`.
+    fn sizes(&mut self) -> ParseResult<Vec<(Option<i64>, Span)>> {
+        let mut sizes = vec![];
+        while self.cursor.check_symbol("[") {
+            let start = self.cursor.span();
+            self.cursor.advance();
+
+            let size = match self.cursor.peek().map(|l| l.kind().clone()) {
+                Some(LexemeKind::Integer(value)) => {
+                    self.cursor.advance();
+                    Some(value)
+                },
+                _ if self.cursor.check_symbol("]") => None,
+                _ => return Err(self.unexpected("tamanho inteiro")),
+            };
+            self.expect_symbol("]")?;
+            sizes.push((size, start.join(&self.cursor.previous_span())));
+        }
+        Ok(sizes)
+    }
+
+    /// `{1, 2, 3}`, or `{{1, 2}, {3, 4}}` for a matrix. Each value is
+    /// returned with its indices, and `lengths` grows to the longest list
+    /// found at each depth.
+    fn initializer(&mut self, prefix:&mut Vec<i64>, lengths:&mut [usize], elements:&mut Vec<(Vec<i64>, Expression)>) -> ParseResult<()> {
+        let depth = prefix.len();
+        self.expect_symbol("{")?;
+
+        let mut count = 0;
+        if !self.cursor.check_symbol("}") {
+            loop {
+                prefix.push(count as i64);
+                if depth + 1 < lengths.len() {
+                    self.initializer(prefix, lengths, elements)?;
+                } else {
+                    elements.push((prefix.clone(), self.expression()?));
+                }
+                prefix.pop();
+                count += 1;
+
+                if !self.cursor.match_symbol(",") {
+                    break;
+                }
+            }
+        }
+        self.expect_symbol("}")?;
+
+        lengths[depth] = lengths[depth].max(count);
+        Ok(())
+    }
+
+    /// Add a declaration to the enclosing function. Names already declared
+    /// with the same type, as the counters of two `para` loops, are
+    /// shared; the checker reports any other repeated name.
+    fn hoist(&mut self, mut declaration:VarDeclaration) {
+        let same = |a:&TypeSpec, b:&TypeSpec| {
+            a.kind == b.kind
+                && a.dimensions.len() == b.dimensions.len()
+                && a.dimensions.iter().zip(&b.dimensions).all(|(x, y)| x.lower == y.lower && x.upper == y.upper)
+        };
+        let type_spec = &declaration.type_spec;
+        let locals = &self.locals;
+        declaration.names.retain(|name| {
+            !locals.iter().any(|d| same(&d.type_spec, type_spec) && d.names.iter().any(|n| n.name == name.name))
+        });
+
+        if !declaration.names.is_empty() {
+            self.locals.push(declaration);
+        }
+    }
+
+    fn type_spec(&mut self) -> ParseResult<TypeSpec> {
+        let span = self.cursor.span();
+        let lexeme = self.cursor.peek().cloned();
+
+        if let Some(kind) = lexeme.as_ref().and_then(|l| Self::type_kind(l.text())) {
+            self.cursor.advance();
+            return Ok(TypeSpec::scalar(kind, span));
+        }
+
+        Err(
+            Diagnostic::error(
+                codes::EXPECTED_TYPE,
+                format!("tipo esperado, encontrado {}", Self::describe(lexeme.as_ref().map(|l| l.text()))),
+                span
+            )
+            .with_label("tipo esperado aqui")
+            .with_note("os tipos válidos são inteiro, real, cadeia, caracter e logico")
+        )
+    }
+
+    fn type_kind(name:&str) -> Option<TypeKind> {
+        match name {
+            "inteiro" => Some(TypeKind::Inteiro),
+            "real" => Some(TypeKind::Real),
+            "cadeia" | "caracter" => Some(TypeKind::Caractere),
+            "logico" => Some(TypeKind::Logico),
+            _ => None
+        }
+    }
+
+    /// `funcao [tipo] nome(parametros) { ... }`, a `procedimento` when the
+    /// type is missing or `vazio`.
+    fn subprogram(&mut self) -> ParseResult<Subprogram> {
+        let start = self.cursor.span();
+        self.expect_word("funcao")?;
+
+        let return_type = if self.cursor.match_word("vazio") {
+            None
+        } else if self.cursor.peek().is_some_and(|l| Self::type_kind(l.text()).is_some()) {
+            Some(self.type_spec()?)
+        } else {
+            None
+        };
+        let kind = if return_type.is_some() { SubprogramKind::Funcao } else { SubprogramKind::Procedimento };
+
+        let name = self.identifier()?;
+
+        let mut parameters = vec![];
+        self.expect_symbol("(")?;
+        if !self.cursor.check_symbol(")") {
+            loop {
+                parameters.push(self.parameter()?);
+                if !self.cursor.match_symbol(",") {
+                    break;
+                }
+            }
+        }
+        self.expect_symbol(")")?;
+
+        let globals = std::mem::take(&mut self.locals);
+        let body = self.block(start, "funcao");
+        let declarations = std::mem::replace(&mut self.locals, globals);
+
+        Ok(Subprogram {
+            kind,
+            name,
+            parameters,
+            return_type,
+            declarations,
+            body: body?,
+            span: start.join(&self.cursor.previous_span()),
+        })
+    }
+
+    /// `tipo [&] nome`
+    fn parameter(&mut self) -> ParseResult<Parameter> {
+        let start = self.cursor.span();
+        let type_spec = self.type_spec()?;
+        let by_reference = self.cursor.match_symbol("&");
+        let name = self.identifier()?;
+
+        Ok(Parameter { name, type_spec, by_reference, span: start.join(&self.cursor.previous_span()) })
+    }
+
+    /// Statements between braces. Errors are recorded and the rest of the
+    /// offending line is skipped.
+    fn block(&mut self, opened:Span, opener:&str) -> ParseResult<Vec<Statement>> {
+        self.expect_symbol("{")?;
+        let mut statements = vec![];
+
+        while !self.cursor.is_at_end() && !self.cursor.check_symbol("}") {
+            let span = self.cursor.span();
+            match self.nested(Self::statement) {
+                Ok(statement) => statements.extend(statement),
+                Err(error) if error.code() == codes::NESTING_TOO_DEEP => {
+                    self.errors.push(error);
+                    self.cursor.skip_all();
+                },
+                Err(error) => {
+                    self.errors.push(error);
+                    self.cursor.skip_line(span);
+                }
+            }
+        }
+
+        self.expect_closing("}", opener, opened)?;
+        Ok(statements)
+    }
+
+    /// A block, or a single statement as in `se (x) escreva(x)`.
+    fn body(&mut self, opened:Span, opener:&str) -> ParseResult<Vec<Statement>> {
+        if self.cursor.check_symbol("{") {
+            self.block(opened, opener)
+        } else {
+            self.nested(Self::statement)
+        }
+    }
+
+    /// One statement, which declarations and `para` loops expand into
+    /// several.
+    fn statement(&mut self) -> ParseResult<Vec<Statement>> {
+        let start = self.cursor.span();
+
+        if self.cursor.check_word("const") || self.cursor.peek().is_some_and(|l| Self::type_kind(l.text()).is_some()) {
+            return self.declaration();
+        }
+        if self.cursor.match_word("para") {
+            return self.for_statement(start);
+        }
+
+        let kind = if self.cursor.match_word("se") {
+            self.if_statement(start)?
+        } else if self.cursor.match_word("enquanto") {
+            let condition = self.condition()?;
+            let body = self.body(start, "enquanto")?;
+            StatementKind::While { condition, body }
+        } else if self.cursor.match_word("faca") {
+            let body = self.block(start, "faca")?;
+            self.expect_word("enquanto")?;
+            let condition = self.condition()?;
+            StatementKind::Repeat { body, condition: Some(Self::negate(condition)) }
+        } else if self.cursor.match_word("escolha") {
+            self.choose_statement(start)?
+        } else if self.cursor.match_word("retorne") {
+            let value = if self.starts_on_line(start) && !self.cursor.check_symbol("}") {
+                Some(self.expression()?)
+            } else {
+                None
+            };
+            StatementKind::Return { value }
+        } else if self.cursor.match_word("pare") {
+            StatementKind::Break
+        } else if self.cursor.match_word("escreva") {
+            let arguments = self.arguments()?.into_iter()
+                .map(|value| WriteArgument { span: value.span, value, width: None, precision: None })
+                .collect();
+            StatementKind::Write { arguments, newline: false }
+        } else if self.cursor.match_word("leia") {
+            self.expect_symbol("(")?;
+            let mut targets = vec![self.target()?];
+            while self.cursor.match_symbol(",") {
+                targets.push(self.target()?);
+            }
+            self.expect_symbol(")")?;
+            StatementKind::Read { targets }
+        } else if self.cursor.check_word("limpa") {
+            // Clears the console like VisuAlg's `limpatela`.
+            self.cursor.advance();
+            self.expect_symbol("(")?;
+            self.expect_symbol(")")?;
+            StatementKind::Call { name: Identifier::new(String::from("limpatela"), start), arguments: vec![] }
+        } else {
+            self.assignment_or_call()?
+        };
+
+        Ok(vec![Statement::new(kind, start.join(&self.cursor.previous_span()))])
+    }
+
+    /// `a = 1`, `v[i] += 1`, `a++` or `f(a)`.
+    fn assignment_or_call(&mut self) -> ParseResult<StatementKind> {
+        let target = self.target()?;
+
+        if self.cursor.match_symbol("=") {
+            return Ok(StatementKind::Assign { target, value: self.expression()? });
+        }
+
+        let operator_span = self.cursor.span();
+        let compound = match self.cursor.peek().map(|l| l.text()) {
+            Some("+=") => Some(BinaryOperator::Add),
+            Some("-=") => Some(BinaryOperator::Subtract),
+            Some("*=") => Some(BinaryOperator::Multiply),
+            Some("/=") => Some(BinaryOperator::Quotient),
+            _ => None,
+        };
+        if let Some(operator) = compound {
+            self.cursor.advance();
+            let value = self.expression()?;
+            return Ok(Self::update(target, operator, value));
+        }
+
+        let step = match self.cursor.peek().map(|l| l.text()) {
+            Some("++") => Some(BinaryOperator::Add),
+            Some("--") => Some(BinaryOperator::Subtract),
+            _ => None,
+        };
+        if let Some(operator) = step {
+            self.cursor.advance();
+            let one = Expression::new(ExpressionKind::Literal(Literal::Integer(1)), operator_span);
+            return Ok(Self::update(target, operator, one));
+        }
+
+        if target.indices.is_empty() && self.cursor.check_symbol("(") {
+            let arguments = self.arguments()?;
+            return Ok(StatementKind::Call { name: target.name, arguments });
+        }
+
+        Err(self.unexpected("'=' ou '('"))
+    }
+
+    /// `target = target <operator> value`
+    fn update(target:Target, operator:BinaryOperator, value:Expression) -> StatementKind {
+        let current = if target.indices.is_empty() {
+            Expression::new(ExpressionKind::Variable(target.name.clone()), target.name.span)
+        } else {
+            Expression::new(ExpressionKind::Index { name: target.name.clone(), indices: target.indices.clone() }, target.span())
+        };
+        StatementKind::Assign { target, value: Self::binary(operator, current, value) }
+    }
+
+    /// A variable, or an array element as `v[i]` or `m[i][j]`.
+    fn target(&mut self) -> ParseResult<Target> {
+        let name = self.identifier()?;
+        let indices = self.indices()?;
+        Ok(Target::new(name, indices))
+    }
+
+    /// One bracketed expression per dimension, none when no `[` follows.
+    fn indices(&mut self) -> ParseResult<Vec<Expression>> {
+        let mut indices = vec![];
+        while self.cursor.match_symbol("[") {
+            indices.push(self.expression()?);
+            self.expect_symbol("]")?;
+        }
+        Ok(indices)
+    }
+
+    fn if_statement(&mut self, start:Span) -> ParseResult<StatementKind> {
+        let condition = self.condition()?;
+        let then_branch = self.body(start, "se")?;
+
+        let else_branch = if self.cursor.match_word("senao") {
+            Some(self.body(start, "senao")?)
+        } else {
+            None
+        };
+
+        Ok(StatementKind::If { condition, then_branch, else_branch })
+    }
+
+    /// `para (inicio; condicao; passo) corpo` runs as the initialization
+    /// followed by `enquanto (condicao) { corpo passo }`.
+    fn for_statement(&mut self, start:Span) -> ParseResult<Vec<Statement>> {
+        self.expect_symbol("(")?;
+
+        let mut statements = vec![];
+        if !self.cursor.check_symbol(";") {
+            statements.extend(self.nested(Self::statement)?);
+        }
+        self.expect_symbol(";")?;
+
+        let condition = if self.cursor.check_symbol(";") {
+            Expression::new(ExpressionKind::Literal(Literal::Boolean(true)), self.cursor.span())
+        } else {
+            self.expression()?
+        };
+        self.expect_symbol(";")?;
+
+        let mut step = vec![];
+        if !self.cursor.check_symbol(")") {
+            let step_start = self.cursor.span();
+            let kind = self.assignment_or_call()?;
+            step.push(Statement::new(kind, step_start.join(&self.cursor.previous_span())));
+        }
+        self.expect_symbol(")")?;
+
+        let mut body = self.body(start, "para")?;
+        body.extend(step);
+
+        let span = start.join(&self.cursor.previous_span());
+        statements.push(Statement::new(StatementKind::While { condition, body }, span));
+        Ok(statements)
+    }
+
+    /// Cases run into the next one unless they end in `pare`, so each case
+    /// gets the statements of the ones it falls into.
+    fn choose_statement(&mut self, opener:Span) -> ParseResult<StatementKind> {
+        let subject = self.condition()?;
+        self.expect_symbol("{")?;
+
+        // Label (`None` for `contrario`), statements and whether it ends in `pare`.
+        let mut parsed:Vec<(Option<Expression>, Vec<Statement>, bool, Span)> = vec![];
+
+        while self.cursor.check_word("caso") {
+            let start = self.cursor.span();
+            self.cursor.advance();
+
+            let label = if self.cursor.match_word("contrario") {
+                None
+            } else {
+                Some(self.expression()?)
+            };
+            self.expect_symbol(":")?;
+
+            let mut body = vec![];
+            while !self.cursor.is_at_end() && !self.cursor.check_word("caso") && !self.cursor.check_symbol("}") {
+                let span = self.cursor.span();
+                match self.nested(Self::statement) {
+                    Ok(statement) => body.extend(statement),
+                    Err(error) if error.code() == codes::NESTING_TOO_DEEP => {
+                        self.errors.push(error);
+                        self.cursor.skip_all();
+                    },
+                    Err(error) => {
+                        self.errors.push(error);
+                        self.cursor.skip_line(span);
+                    }
+                }
+            }
+
+            let stops = matches!(body.last(), Some(Statement { kind: StatementKind::Break, .. }));
+            if stops {
+                body.pop();
+            }
+            parsed.push((label, body, stops, start.join(&self.cursor.previous_span())));
+        }
+        self.expect_closing("}", "escolha", opener)?;
+
+        for index in (0..parsed.len().saturating_sub(1)).rev() {
+            if !parsed[index].2 {
+                let next = parsed[index + 1].1.clone();
+                parsed[index].1.extend(next);
+            }
+        }
+
+        let mut cases = vec![];
+        let mut default = None;
+        for (label, body, _, span) in parsed {
+            match label {
+                Some(value) => cases.push(Case { labels: vec![CaseLabel::Value(value)], body, span }),
+                None => default = Some(body),
+            }
+        }
+
+        Ok(StatementKind::Choose { subject, cases, default })
+    }
+
+    /// Parenthesized condition of `se`, `enquanto` and `escolha`.
+    fn condition(&mut self) -> ParseResult<Expression> {
+        self.expect_symbol("(")?;
+        let condition = self.expression()?;
+        self.expect_symbol(")")?;
+        Ok(condition)
+    }
+
+    fn negate(condition:Expression) -> Expression {
+        let span = condition.span;
+        Expression::new(ExpressionKind::Unary { operator: UnaryOperator::Not, operand: Box::new(condition) }, span)
+    }
+
+    fn arguments(&mut self) -> ParseResult<Vec<Expression>> {
+        self.expect_symbol("(")?;
+
+        let mut arguments = vec![];
+        if !self.cursor.check_symbol(")") {
+            loop {
+                arguments.push(self.expression()?);
+                if !self.cursor.match_symbol(",") {
+                    break;
+                }
+            }
+        }
+        self.expect_symbol(")")?;
+
+        Ok(arguments)
+    }
+
+    pub fn expression(&mut self) -> ParseResult<Expression> {
+        self.nested(Self::or)
+    }
+
+    fn or(&mut self) -> ParseResult<Expression> {
+        let mut left = self.and()?;
+        while self.cursor.match_word("ou") {
+            let right = self.and()?;
+            left = Self::binary(BinaryOperator::Or, left, right);
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> ParseResult<Expression> {
+        let mut left = self.not()?;
+        while self.cursor.match_word("e") {
+            let right = self.not()?;
+            left = Self::binary(BinaryOperator::And, left, right);
+        }
+        Ok(left)
+    }
+
+    fn not(&mut self) -> ParseResult<Expression> {
+        let start = self.cursor.span();
+        if self.cursor.match_word("nao") {
+            let operand = self.nested(Self::not)?;
+            let span = start.join(&operand.span);
+            return Ok(Expression::new(
+                ExpressionKind::Unary { operator: UnaryOperator::Not, operand: Box::new(operand) },
+                span
+            ));
+        }
+        self.relational()
+    }
+
+    fn relational(&mut self) -> ParseResult<Expression> {
+        let mut left = self.additive()?;
+        loop {
+            let operator = match self.cursor.peek().map(|l| l.text()) {
+                Some("==") => BinaryOperator::Equal,
+                Some("!=") => BinaryOperator::NotEqual,
+                Some("<") => BinaryOperator::Less,
+                Some("<=") => BinaryOperator::LessEqual,
+                Some(">") => BinaryOperator::Greater,
+                Some(">=") => BinaryOperator::GreaterEqual,
+                _ => break,
+            };
+            self.cursor.advance();
+            let right = self.additive()?;
+            left = Self::binary(operator, left, right);
+        }
+        Ok(left)
+    }
+
+    fn additive(&mut self) -> ParseResult<Expression> {
+        let mut left = self.multiplicative()?;
+        loop {
+            let operator = match self.cursor.peek().map(|l| l.text()) {
+                Some("+") => BinaryOperator::Add,
+                Some("-") => BinaryOperator::Subtract,
+                _ => break,
+            };
+            self.cursor.advance();
+            let right = self.multiplicative()?;
+            left = Self::binary(operator, left, right);
+        }
+        Ok(left)
+    }
+
+    fn multiplicative(&mut self) -> ParseResult<Expression> {
+        let mut left = self.unary()?;
+        loop {
+            let operator = match self.cursor.peek().map(|l| l.text()) {
+                Some("*") => BinaryOperator::Multiply,
+                Some("/") => BinaryOperator::Quotient,
+                Some("%") => BinaryOperator::Modulo,
+                _ => break,
+            };
+            self.cursor.advance();
+            let right = self.unary()?;
+            left = Self::binary(operator, left, right);
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> ParseResult<Expression> {
+        let start = self.cursor.span();
+        let operator = if self.cursor.match_symbol("-") {
+            UnaryOperator::Negate
+        } else if self.cursor.match_symbol("+") {
+            UnaryOperator::Plus
+        } else {
+            return self.primary();
+        };
+
+        let operand = self.nested(Self::unary)?;
+        let span = start.join(&operand.span);
+        Ok(Expression::new(ExpressionKind::Unary { operator, operand: Box::new(operand) }, span))
+    }
+
+    fn primary(&mut self) -> ParseResult<Expression> {
+        let start = self.cursor.span();
+        let Some(lexeme) = self.cursor.peek().cloned() else {
+            return Err(Diagnostic::error(
+                codes::EXPECTED_EXPRESSION,
+                String::from("expressão esperada, encontrado fim do arquivo"),
+                start
+            ));
+        };
+
+        let literal = match lexeme.kind() {
+            LexemeKind::Integer(value) => Some(Literal::Integer(*value)),
+            LexemeKind::Real(value) => Some(Literal::Real(*value)),
+            LexemeKind::Text(value) => Some(Literal::String(value.clone())),
+            LexemeKind::Boolean(value) => Some(Literal::Boolean(*value)),
+            _ => None,
+        };
+
+        if let Some(literal) = literal {
+            self.cursor.advance();
+            return Ok(Expression::new(ExpressionKind::Literal(literal), start));
+        }
+
+        if self.cursor.match_symbol("(") {
+            let inner = self.expression()?;
+            self.expect_symbol(")")?;
+            return Ok(Expression::new(inner.kind, start.join(&self.cursor.previous_span())));
+        }
+
+        if lexeme.kind() == &LexemeKind::Word && !Self::is_reserved(lexeme.text()) {
+            let name = self.identifier()?;
+            if self.cursor.check_symbol("(") {
+                let arguments = self.arguments()?;
+                return Ok(Expression::new(
+                    ExpressionKind::Call { name, arguments },
+                    start.join(&self.cursor.previous_span())
+                ));
+            }
+            if self.cursor.check_symbol("[") {
+                let indices = self.indices()?;
+                return Ok(Expression::new(
+                    ExpressionKind::Index { name, indices },
+                    start.join(&self.cursor.previous_span())
+                ));
+            }
+            return Ok(Expression::new(ExpressionKind::Variable(name), start));
+        }
+
+        Err(
+            Diagnostic::error(codes::EXPECTED_EXPRESSION, format!("expressão esperada, encontrado '{}'", lexeme.text()), start)
+                .with_label("expressão esperada aqui")
+        )
+    }
+
+    fn binary(operator:BinaryOperator, left:Expression, right:Expression) -> Expression {
+        let span = left.span.join(&right.span);
+        Expression::new(
+            ExpressionKind::Binary { operator, left: Box::new(left), right: Box::new(right) },
+            span
+        )
+    }
+
+    fn identifier(&mut self) -> ParseResult<Identifier> {
+        let span = self.cursor.span();
+        match self.cursor.peek() {
+            Some(lexeme) if lexeme.kind() == &LexemeKind::Word && !Self::is_reserved(lexeme.text()) => {
+                let name = lexeme.text().to_string();
+                self.cursor.advance();
+                Ok(Identifier::new(name, span))
+            },
+            other => {
+                let reserved = other.is_some_and(|l| l.kind() == &LexemeKind::Word);
+                let mut diagnostic = Diagnostic::error(
+                    codes::EXPECTED_IDENTIFIER,
+                    format!("identificador esperado, encontrado {}", Self::describe(other.map(|l| l.text()))),
+                    span
+                );
+                if reserved {
+                    diagnostic = diagnostic.with_note("palavras reservadas não podem ser usadas como nomes");
+                }
+                Err(diagnostic)
+            },
+        }
+    }
+
+    /// Portugol Studio is case sensitive: `Se` is a valid name.
+    fn is_reserved(word:&str) -> bool {
+        RESERVED.contains(&word)
+    }
+
+    /// Run `parse` one level deeper, see [`TokenCursor::enter`].
+    fn nested<T>(&mut self, parse:fn(&mut Self) -> ParseResult<T>) -> ParseResult<T> {
+        self.cursor.enter()?;
+        let result = parse(self);
+        self.cursor.leave();
+        result
+    }
+
+    /// Whether the next lexeme sits on the same line as `span`.
+    fn starts_on_line(&self, span:Span) -> bool {
+        self.cursor.peek().is_some_and(|l| l.span().start().line() == span.start().line())
+    }
+
+    fn expect_word(&mut self, word:&str) -> ParseResult<()> {
+        if self.cursor.match_word(word) {
+            return Ok(());
+        }
+        Err(self.unexpected(&format!("'{}'", word)))
+    }
+
+    fn expect_symbol(&mut self, symbol:&str) -> ParseResult<()> {
+        if self.cursor.match_symbol(symbol) {
+            return Ok(());
+        }
+        Err(self.unexpected(&format!("'{}'", symbol)))
+    }
+
+    /// Expect the brace closing a block, pointing back at its opener.
+    fn expect_closing(&mut self, symbol:&str, opener:&str, opened:Span) -> ParseResult<()> {
+        if self.cursor.match_symbol(symbol) {
+            return Ok(());
+        }
+
+        let span = self.cursor.span();
+        let here = Span::new(span.start(), span.start());
+        Err(
+            Diagnostic::error(
+                codes::UNCLOSED_BLOCK,
+                format!("'{}' esperado, encontrado {}", symbol, Self::describe(self.cursor.peek().map(|l| l.text()))),
+                span
+            )
+            .with_label(&format!("'{}' esperado aqui", symbol))
+            .with_secondary(opened, &format!("'{}' aberto aqui", opener))
+            .with_suggestion(&format!("feche o bloco com '{}'", symbol), here, symbol)
+        )
+    }
+
+    fn unexpected(&self, expected:&str) -> Diagnostic {
+        Diagnostic::error(
+            codes::UNEXPECTED_TOKEN,
+            format!("{} esperado, encontrado {}", expected, Self::describe(self.cursor.peek().map(|l| l.text()))),
+            self.cursor.span()
+        )
+        .with_label(&format!("{} esperado aqui", expected))
+    }
+
+    fn describe(text:Option<&str>) -> String {
+        match text {
+            Some(text) => format!("'{}'", text),
+            None => String::from("fim do arquivo"),
+        }
+    }
+}
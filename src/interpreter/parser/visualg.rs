@@ -0,0 +1,775 @@
+use crate::interpreter::ast::expression::{BinaryOperator, Expression, ExpressionKind, Literal, UnaryOperator};
+use crate::interpreter::ast::program::{Parameter, Program, Subprogram, SubprogramKind, VarDeclaration};
+use crate::interpreter::ast::statement::{Case, CaseLabel, Statement, StatementKind, Target, WriteArgument};
+use crate::interpreter::ast::types::{Bounds, Identifier, TypeKind, TypeSpec};
+use crate::interpreter::diagnostics::codes;
+use crate::interpreter::diagnostics::diagnostic::Diagnostic;
+use crate::interpreter::tokens::span::Span;
+use crate::interpreter::tokens::token::Token;
+
+use super::cursor::{LexemeKind, TokenCursor};
+
+const RESERVED:&[&str] = &[
+    "algoritmo", "fimalgoritmo", "var", "inicio", "vetor",
+    "se", "entao", "senao", "fimse",
+    "enquanto", "faca", "fimenquanto",
+    "para", "de", "ate", "passo", "fimpara",
+    "repita", "fimrepita",
+    "escolha", "caso", "outrocaso", "fimescolha",
+    "procedimento", "fimprocedimento", "funcao", "fimfuncao", "retorne",
+    "interrompa", "escreva", "escreval", "leia",
+    "e", "ou", "xou", "nao", "and", "or", "mod", "div", "verdadeiro", "falso",
+];
+
+type ParseResult<T> = Result<T, Diagnostic>;
+
+/// Recursive descent parser for VisuAlg programs.
+pub struct VisuAlgParser {
+    cursor: TokenCursor,
+    errors: Vec<Diagnostic>,
+}
+
+impl VisuAlgParser {
+    pub fn new(tokens:&[Token]) -> Self {
+        Self { cursor: TokenCursor::new(tokens), errors: vec![] }
+    }
+
+    pub fn parse(mut self) -> Result<Program, Vec<Diagnostic>> {
+        match self.program() {
+            Ok(program) if self.errors.is_empty() => Ok(program),
+            Ok(_) => Err(Self::too_deep_alone(self.errors)),
+            Err(error) => {
+                self.errors.push(error);
+                Err(Self::too_deep_alone(self.errors))
+            }
+        }
+    }
+
+    /// Code nested too deeply leaves every block around it unclosed, so
+    /// only the nesting error is worth reporting.
+    fn too_deep_alone(errors:Vec<Diagnostic>) -> Vec<Diagnostic> {
+        match errors.iter().find(|e| e.code() == codes::NESTING_TOO_DEEP) {
+            Some(error) => vec![error.clone()],
+            None => errors,
+        }
+    }
+
+    /// A lone expression, with nothing after it.
+    pub fn parse_expression(mut self) -> Result<Expression, Vec<Diagnostic>> {
+        let expression = self.expression().map_err(|error| vec![error])?;
+
+        if let Some(lexeme) = self.cursor.peek() {
+            self.errors.push(Diagnostic::error(
+                codes::TRAILING_CONTENT,
+                format!("conteúdo inesperado após a expressão: '{}'", lexeme.text()),
+                lexeme.span()
+            ));
+        }
+
+        if self.errors.is_empty() {
+            Ok(expression)
+        } else {
+            Err(self.errors)
+        }
+    }
+
+    fn program(&mut self) -> ParseResult<Program> {
+        let start = self.cursor.span();
+        self.expect_word("algoritmo")?;
+
+        let mut name = None;
+        if let Some(LexemeKind::Text(text)) = self.cursor.peek().map(|l| l.kind().clone()) {
+            self.cursor.advance();
+            name = Some(text);
+        }
+
+        let mut declarations = vec![];
+        let mut subprograms = vec![];
+
+        loop {
+            if self.cursor.match_word("var") {
+                declarations.extend(self.declarations(&["inicio", "procedimento", "funcao"])?);
+            } else if self.cursor.check_word("procedimento") || self.cursor.check_word("funcao") {
+                subprograms.push(self.subprogram()?);
+            } else {
+                break;
+            }
+        }
+
+        self.expect_word("inicio")?;
+        let body = self.block(&["fimalgoritmo"]);
+        self.expect_closing("fimalgoritmo", "algoritmo", start)?;
+
+        if let Some(lexeme) = self.cursor.peek() {
+            return Err(
+                Diagnostic::error(
+                    codes::TRAILING_CONTENT,
+                    format!("conteúdo inesperado após fimalgoritmo: '{}'", lexeme.text()),
+                    lexeme.span()
+                )
+                .with_note("o programa termina em fimalgoritmo")
+            );
+        }
+
+        Ok(Program { name, declarations, subprograms, body, span: start.join(&self.cursor.previous_span()) })
+    }
+
+    /// Declarations of a `var` section, up to one of the `until` words.
+    fn declarations(&mut self, until:&[&str]) -> ParseResult<Vec<VarDeclaration>> {
+        let mut declarations = vec![];
+
+        while !self.cursor.is_at_end() && !until.iter().any(|w| self.cursor.check_word(w)) {
+            let start = self.cursor.span();
+            let names = self.identifier_list()?;
+            self.expect_symbol(":")?;
+            let type_spec = self.type_spec()?;
+
+            declarations.push(VarDeclaration { names, type_spec, span: start.join(&self.cursor.previous_span()) });
+        }
+
+        Ok(declarations)
+    }
+
+    fn identifier_list(&mut self) -> ParseResult<Vec<Identifier>> {
+        let mut names = vec![self.identifier()?];
+        while self.cursor.match_symbol(",") {
+            names.push(self.identifier()?);
+        }
+        Ok(names)
+    }
+
+    /// `inteiro`, or `vetor[1..10, 0..2] de inteiro`.
+    fn type_spec(&mut self) -> ParseResult<TypeSpec> {
+        let span = self.cursor.span();
+
+        if self.cursor.match_word("vetor") {
+            self.expect_symbol("[")?;
+            let mut dimensions = vec![self.bounds()?];
+            while self.cursor.match_symbol(",") {
+                dimensions.push(self.bounds()?);
+            }
+            self.expect_symbol("]")?;
+            self.expect_word("de")?;
+
+            let element = self.type_spec()?;
+            if element.is_array() {
+                return Err(
+                    Diagnostic::error(codes::EXPECTED_TYPE, String::from("vetor de vetor não é permitido"), element.span)
+                        .with_note("declare as dimensões juntas, como vetor[1..3, 1..3] de real")
+                );
+            }
+            return Ok(TypeSpec { kind: element.kind, dimensions, span: span.join(&element.span) });
+        }
+
+        let lexeme = self.cursor.peek().cloned();
+        if let Some(kind) = lexeme.as_ref().and_then(|l| TypeKind::from_name(l.text())) {
+            self.cursor.advance();
+            return Ok(TypeSpec::scalar(kind, span));
+        }
+
+        Err(
+            Diagnostic::error(
+                codes::EXPECTED_TYPE,
+                format!("tipo esperado, encontrado {}", Self::describe(lexeme.as_ref().map(|l| l.text()))),
+                span
+            )
+            .with_label("tipo esperado aqui")
+            .with_note("os tipos válidos são inteiro, real, caractere e logico")
+        )
+    }
+
+    /// `1..10`, each end an integer with an optional sign.
+    fn bounds(&mut self) -> ParseResult<Bounds> {
+        let start = self.cursor.span();
+        let lower = self.bound()?;
+        self.expect_symbol(".")?;
+        self.expect_symbol(".")?;
+        let upper = self.bound()?;
+
+        Ok(Bounds { lower, upper, span: start.join(&self.cursor.previous_span()) })
+    }
+
+    fn bound(&mut self) -> ParseResult<i64> {
+        let negative = self.cursor.match_symbol("-");
+        match self.cursor.peek().map(|l| l.kind().clone()) {
+            Some(LexemeKind::Integer(value)) => {
+                self.cursor.advance();
+                Ok(if negative { -value } else { value })
+            },
+            _ => Err(self.unexpected("limite inteiro")),
+        }
+    }
+
+    fn subprogram(&mut self) -> ParseResult<Subprogram> {
+        let start = self.cursor.span();
+        let (kind, end_word) = if self.cursor.match_word("funcao") {
+            (SubprogramKind::Funcao, "fimfuncao")
+        } else {
+            self.expect_word("procedimento")?;
+            (SubprogramKind::Procedimento, "fimprocedimento")
+        };
+
+        let name = self.identifier()?;
+
+        let mut parameters = vec![];
+        if self.cursor.match_symbol("(") {
+            if !self.cursor.check_symbol(")") {
+                loop {
+                    parameters.extend(self.parameter_group()?);
+                    if !self.cursor.match_symbol(";") && !self.cursor.match_symbol(",") {
+                        break;
+                    }
+                }
+            }
+            self.expect_symbol(")")?;
+        }
+
+        let mut return_type = None;
+        if kind == SubprogramKind::Funcao {
+            self.expect_symbol(":")?;
+            return_type = Some(self.type_spec()?);
+        }
+
+        let mut declarations = vec![];
+        if self.cursor.match_word("var") {
+            declarations = self.declarations(&["inicio"])?;
+        }
+        self.cursor.match_word("inicio");
+
+        let body = self.block(&[end_word]);
+        self.expect_closing(end_word, if kind == SubprogramKind::Funcao { "funcao" } else { "procedimento" }, start)?;
+
+        Ok(Subprogram {
+            kind,
+            name,
+            parameters,
+            return_type,
+            declarations,
+            body,
+            span: start.join(&self.cursor.previous_span()),
+        })
+    }
+
+    /// `[var] a, b: inteiro`
+    fn parameter_group(&mut self) -> ParseResult<Vec<Parameter>> {
+        let start = self.cursor.span();
+        let by_reference = self.cursor.match_word("var");
+
+        let names = self.identifier_list()?;
+        self.expect_symbol(":")?;
+        let type_spec = self.type_spec()?;
+        let span = start.join(&self.cursor.previous_span());
+
+        Ok(
+            names.into_iter()
+                .map(|name| Parameter { name, type_spec: type_spec.clone(), by_reference, span })
+                .collect()
+        )
+    }
+
+    /// Statements up to (not including) one of the `until` words. Errors are
+    /// recorded and the rest of the offending line is skipped.
+    fn block(&mut self, until:&[&str]) -> Vec<Statement> {
+        let mut statements = vec![];
+
+        while !self.cursor.is_at_end() && !until.iter().any(|w| self.cursor.check_word(w)) {
+            let span = self.cursor.span();
+            match self.nested(Self::statement) {
+                Ok(statement) => statements.push(statement),
+                Err(error) if error.code() == codes::NESTING_TOO_DEEP => {
+                    self.errors.push(error);
+                    self.cursor.skip_all();
+                },
+                Err(error) => {
+                    self.errors.push(error);
+                    self.cursor.skip_line(span);
+                }
+            }
+        }
+
+        statements
+    }
+
+    fn statement(&mut self) -> ParseResult<Statement> {
+        let start = self.cursor.span();
+
+        let kind = if self.cursor.match_word("se") {
+            self.if_statement(start)?
+        } else if self.cursor.match_word("enquanto") {
+            let condition = self.expression()?;
+            self.expect_word("faca")?;
+            let body = self.block(&["fimenquanto"]);
+            self.expect_closing("fimenquanto", "enquanto", start)?;
+            StatementKind::While { condition, body }
+        } else if self.cursor.match_word("para") {
+            self.for_statement(start)?
+        } else if self.cursor.match_word("repita") {
+            let body = self.block(&["ate", "fimrepita"]);
+            let condition = if self.cursor.match_word("fimrepita") {
+                None
+            } else {
+                self.expect_word("ate")?;
+                Some(self.expression()?)
+            };
+            StatementKind::Repeat { body, condition }
+        } else if self.cursor.match_word("escolha") {
+            self.choose_statement(start)?
+        } else if self.cursor.match_word("retorne") {
+            let value = if self.starts_on_line(start) {
+                Some(self.expression()?)
+            } else {
+                None
+            };
+            StatementKind::Return { value }
+        } else if self.cursor.match_word("interrompa") {
+            StatementKind::Break
+        } else if self.cursor.check_word("escreva") || self.cursor.check_word("escreval") {
+            let newline = self.cursor.check_word("escreval");
+            self.cursor.advance();
+            StatementKind::Write { arguments: self.write_arguments()?, newline }
+        } else if self.cursor.match_word("leia") {
+            self.expect_symbol("(")?;
+            let mut targets = vec![self.target()?];
+            while self.cursor.match_symbol(",") {
+                targets.push(self.target()?);
+            }
+            self.expect_symbol(")")?;
+            StatementKind::Read { targets }
+        } else if self.cursor.peek_at(1).is_some_and(|l| l.is_symbol("[")) {
+            let target = self.target()?;
+            if !self.cursor.match_symbol("<-") && !self.cursor.match_symbol(":=") {
+                return Err(self.unexpected("'<-'"));
+            }
+            StatementKind::Assign { target, value: self.expression()? }
+        } else {
+            let name = self.identifier()?;
+
+            if self.cursor.match_symbol("<-") || self.cursor.match_symbol(":=") {
+                StatementKind::Assign { target: Target::new(name, vec![]), value: self.expression()? }
+            } else {
+                let arguments = if self.cursor.check_symbol("(") {
+                    self.arguments()?
+                } else {
+                    vec![]
+                };
+                StatementKind::Call { name, arguments }
+            }
+        };
+
+        Ok(Statement::new(kind, start.join(&self.cursor.previous_span())))
+    }
+
+    fn if_statement(&mut self, start:Span) -> ParseResult<StatementKind> {
+        let condition = self.expression()?;
+        self.expect_word("entao")?;
+
+        let then_branch = self.block(&["senao", "fimse"]);
+        let else_branch = if self.cursor.match_word("senao") {
+            Some(self.block(&["fimse"]))
+        } else {
+            None
+        };
+        self.expect_closing("fimse", "se", start)?;
+
+        Ok(StatementKind::If { condition, then_branch, else_branch })
+    }
+
+    fn for_statement(&mut self, opener:Span) -> ParseResult<StatementKind> {
+        let variable = self.identifier()?;
+        self.expect_word("de")?;
+        let start = Box::new(self.expression()?);
+        self.expect_word("ate")?;
+        let end = Box::new(self.expression()?);
+
+        let step = if self.cursor.match_word("passo") {
+            Some(Box::new(self.expression()?))
+        } else {
+            None
+        };
+
+        self.expect_word("faca")?;
+        let body = self.block(&["fimpara"]);
+        self.expect_closing("fimpara", "para", opener)?;
+
+        Ok(StatementKind::For { variable, start, end, step, body })
+    }
+
+    fn choose_statement(&mut self, opener:Span) -> ParseResult<StatementKind> {
+        let subject = self.expression()?;
+        let mut cases = vec![];
+        let mut default = None;
+
+        while self.cursor.check_word("caso") {
+            let start = self.cursor.span();
+            self.cursor.advance();
+
+            let mut labels = vec![];
+            loop {
+                let value = self.expression()?;
+                if self.cursor.match_word("ate") {
+                    labels.push(CaseLabel::Range(value, self.expression()?));
+                } else {
+                    labels.push(CaseLabel::Value(value));
+                }
+                if !self.cursor.match_symbol(",") {
+                    break;
+                }
+            }
+
+            let body = self.block(&["caso", "outrocaso", "fimescolha"]);
+            cases.push(Case { labels, body, span: start.join(&self.cursor.previous_span()) });
+        }
+
+        if self.cursor.match_word("outrocaso") {
+            default = Some(self.block(&["fimescolha"]));
+        }
+        self.expect_closing("fimescolha", "escolha", opener)?;
+
+        Ok(StatementKind::Choose { subject, cases, default })
+    }
+
+    fn write_arguments(&mut self) -> ParseResult<Vec<WriteArgument>> {
+        let mut arguments = vec![];
+
+        if !self.cursor.match_symbol("(") {
+            return Ok(arguments);
+        }
+
+        if !self.cursor.check_symbol(")") {
+            loop {
+                let value = self.expression()?;
+                let mut width = None;
+                let mut precision = None;
+
+                if self.cursor.match_symbol(":") {
+                    width = Some(self.expression()?);
+                    if self.cursor.match_symbol(":") {
+                        precision = Some(self.expression()?);
+                    }
+                }
+
+                let span = value.span.join(&self.cursor.previous_span());
+                arguments.push(WriteArgument { value, width, precision, span });
+
+                if !self.cursor.match_symbol(",") {
+                    break;
+                }
+            }
+        }
+        self.expect_symbol(")")?;
+
+        Ok(arguments)
+    }
+
+    /// A variable, or an array element as `v[i]` or `m[i, j]`.
+    fn target(&mut self) -> ParseResult<Target> {
+        let name = self.identifier()?;
+        let indices = self.indices()?;
+        Ok(Target::new(name, indices))
+    }
+
+    /// `[i, j]`, also written `[i][j]`. Empty when no bracket follows.
+    fn indices(&mut self) -> ParseResult<Vec<Expression>> {
+        let mut indices = vec![];
+        while self.cursor.match_symbol("[") {
+            loop {
+                indices.push(self.expression()?);
+                if !self.cursor.match_symbol(",") {
+                    break;
+                }
+            }
+            self.expect_symbol("]")?;
+        }
+        Ok(indices)
+    }
+
+    fn arguments(&mut self) -> ParseResult<Vec<Expression>> {
+        self.expect_symbol("(")?;
+
+        let mut arguments = vec![];
+        if !self.cursor.check_symbol(")") {
+            loop {
+                arguments.push(self.expression()?);
+                if !self.cursor.match_symbol(",") {
+                    break;
+                }
+            }
+        }
+        self.expect_symbol(")")?;
+
+        Ok(arguments)
+    }
+
+    pub fn expression(&mut self) -> ParseResult<Expression> {
+        self.nested(Self::or)
+    }
+
+    fn or(&mut self) -> ParseResult<Expression> {
+        let mut left = self.and()?;
+        loop {
+            let operator = if self.cursor.match_word("ou") || self.cursor.match_word("or") {
+                BinaryOperator::Or
+            } else if self.cursor.match_word("xou") {
+                BinaryOperator::Xor
+            } else {
+                break;
+            };
+            let right = self.and()?;
+            left = Self::binary(operator, left, right);
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> ParseResult<Expression> {
+        let mut left = self.not()?;
+        while self.cursor.match_word("e") || self.cursor.match_word("and") {
+            let right = self.not()?;
+            left = Self::binary(BinaryOperator::And, left, right);
+        }
+        Ok(left)
+    }
+
+    fn not(&mut self) -> ParseResult<Expression> {
+        let start = self.cursor.span();
+        if self.cursor.match_word("nao") {
+            let operand = self.nested(Self::not)?;
+            let span = start.join(&operand.span);
+            return Ok(Expression::new(
+                ExpressionKind::Unary { operator: UnaryOperator::Not, operand: Box::new(operand) },
+                span
+            ));
+        }
+        self.relational()
+    }
+
+    fn relational(&mut self) -> ParseResult<Expression> {
+        let mut left = self.additive()?;
+        loop {
+            let operator = match self.cursor.peek().map(|l| l.text()) {
+                Some("=") => BinaryOperator::Equal,
+                Some("<>") => BinaryOperator::NotEqual,
+                Some("<") => BinaryOperator::Less,
+                Some("<=") => BinaryOperator::LessEqual,
+                Some(">") => BinaryOperator::Greater,
+                Some(">=") => BinaryOperator::GreaterEqual,
+                _ => break,
+            };
+            self.cursor.advance();
+            let right = self.additive()?;
+            left = Self::binary(operator, left, right);
+        }
+        Ok(left)
+    }
+
+    fn additive(&mut self) -> ParseResult<Expression> {
+        let mut left = self.multiplicative()?;
+        loop {
+            let operator = match self.cursor.peek().map(|l| l.text()) {
+                Some("+") => BinaryOperator::Add,
+                Some("-") => BinaryOperator::Subtract,
+                _ => break,
+            };
+            self.cursor.advance();
+            let right = self.multiplicative()?;
+            left = Self::binary(operator, left, right);
+        }
+        Ok(left)
+    }
+
+    fn multiplicative(&mut self) -> ParseResult<Expression> {
+        let mut left = self.unary()?;
+        loop {
+            let operator = match self.cursor.peek().map(|l| l.text().to_lowercase()).as_deref() {
+                Some("*") => BinaryOperator::Multiply,
+                Some("/") => BinaryOperator::Divide,
+                Some("\\" | "div") => BinaryOperator::IntegerDivide,
+                Some("%" | "mod") => BinaryOperator::Modulo,
+                _ => break,
+            };
+            self.cursor.advance();
+            let right = self.unary()?;
+            left = Self::binary(operator, left, right);
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> ParseResult<Expression> {
+        let start = self.cursor.span();
+        let operator = if self.cursor.match_symbol("-") {
+            UnaryOperator::Negate
+        } else if self.cursor.match_symbol("+") {
+            UnaryOperator::Plus
+        } else {
+            return self.power();
+        };
+
+        let operand = self.nested(Self::unary)?;
+        let span = start.join(&operand.span);
+        Ok(Expression::new(ExpressionKind::Unary { operator, operand: Box::new(operand) }, span))
+    }
+
+    /// `^` binds tighter than the sign, so `-2^2` is `-(2^2)`, and groups
+    /// to the right: `2^3^2` is `2^(3^2)`.
+    fn power(&mut self) -> ParseResult<Expression> {
+        let base = self.primary()?;
+        if !self.cursor.match_symbol("^") {
+            return Ok(base);
+        }
+
+        let exponent = self.nested(Self::unary)?;
+        Ok(Self::binary(BinaryOperator::Power, base, exponent))
+    }
+
+    fn primary(&mut self) -> ParseResult<Expression> {
+        let start = self.cursor.span();
+        let Some(lexeme) = self.cursor.peek().cloned() else {
+            return Err(Diagnostic::error(
+                codes::EXPECTED_EXPRESSION,
+                String::from("expressão esperada, encontrado fim do arquivo"),
+                start
+            ));
+        };
+
+        let literal = match lexeme.kind() {
+            LexemeKind::Integer(value) => Some(Literal::Integer(*value)),
+            LexemeKind::Real(value) => Some(Literal::Real(*value)),
+            LexemeKind::Text(value) => Some(Literal::String(value.clone())),
+            LexemeKind::Boolean(value) => Some(Literal::Boolean(*value)),
+            _ => None,
+        };
+
+        if let Some(literal) = literal {
+            self.cursor.advance();
+            return Ok(Expression::new(ExpressionKind::Literal(literal), start));
+        }
+
+        if self.cursor.match_symbol("(") {
+            let inner = self.expression()?;
+            self.expect_symbol(")")?;
+            return Ok(Expression::new(inner.kind, start.join(&self.cursor.previous_span())));
+        }
+
+        if lexeme.kind() == &LexemeKind::Word && !Self::is_reserved(lexeme.text()) {
+            let name = self.identifier()?;
+            if self.cursor.check_symbol("(") {
+                let arguments = self.arguments()?;
+                return Ok(Expression::new(
+                    ExpressionKind::Call { name, arguments },
+                    start.join(&self.cursor.previous_span())
+                ));
+            }
+            if self.cursor.check_symbol("[") {
+                let indices = self.indices()?;
+                return Ok(Expression::new(
+                    ExpressionKind::Index { name, indices },
+                    start.join(&self.cursor.previous_span())
+                ));
+            }
+            return Ok(Expression::new(ExpressionKind::Variable(name), start));
+        }
+
+        Err(
+            Diagnostic::error(codes::EXPECTED_EXPRESSION, format!("expressão esperada, encontrado '{}'", lexeme.text()), start)
+                .with_label("expressão esperada aqui")
+        )
+    }
+
+    fn binary(operator:BinaryOperator, left:Expression, right:Expression) -> Expression {
+        let span = left.span.join(&right.span);
+        Expression::new(
+            ExpressionKind::Binary { operator, left: Box::new(left), right: Box::new(right) },
+            span
+        )
+    }
+
+    fn identifier(&mut self) -> ParseResult<Identifier> {
+        let span = self.cursor.span();
+        match self.cursor.peek() {
+            Some(lexeme) if lexeme.kind() == &LexemeKind::Word && !Self::is_reserved(lexeme.text()) => {
+                let name = lexeme.text().to_string();
+                self.cursor.advance();
+                Ok(Identifier::new(name, span))
+            },
+            other => {
+                let reserved = other.is_some_and(|l| l.kind() == &LexemeKind::Word);
+                let mut diagnostic = Diagnostic::error(
+                    codes::EXPECTED_IDENTIFIER,
+                    format!("identificador esperado, encontrado {}", Self::describe(other.map(|l| l.text()))),
+                    span
+                );
+                if reserved {
+                    diagnostic = diagnostic.with_note("palavras reservadas não podem ser usadas como nomes");
+                }
+                Err(diagnostic)
+            },
+        }
+    }
+
+    fn is_reserved(word:&str) -> bool {
+        let word = word.to_lowercase();
+        RESERVED.contains(&word.as_str())
+    }
+
+    /// Run `parse` one level deeper, see [`TokenCursor::enter`].
+    fn nested<T>(&mut self, parse:fn(&mut Self) -> ParseResult<T>) -> ParseResult<T> {
+        self.cursor.enter()?;
+        let result = parse(self);
+        self.cursor.leave();
+        result
+    }
+
+    /// Whether the next lexeme sits on the same line as `span`.
+    fn starts_on_line(&self, span:Span) -> bool {
+        self.cursor.peek().is_some_and(|l| l.span().start().line() == span.start().line())
+    }
+
+    fn expect_word(&mut self, word:&str) -> ParseResult<()> {
+        if self.cursor.match_word(word) {
+            return Ok(());
+        }
+        Err(self.unexpected(&format!("'{}'", word)))
+    }
+
+    fn expect_symbol(&mut self, symbol:&str) -> ParseResult<()> {
+        if self.cursor.match_symbol(symbol) {
+            return Ok(());
+        }
+        Err(self.unexpected(&format!("'{}'", symbol)))
+    }
+
+    /// Expect the word closing a block, pointing back at its opener.
+    fn expect_closing(&mut self, word:&str, opener:&str, opened:Span) -> ParseResult<()> {
+        if self.cursor.match_word(word) {
+            return Ok(());
+        }
+
+        let span = self.cursor.span();
+        let here = Span::new(span.start(), span.start());
+        Err(
+            Diagnostic::error(
+                codes::UNCLOSED_BLOCK,
+                format!("'{}' esperado, encontrado {}", word, Self::describe(self.cursor.peek().map(|l| l.text()))),
+                span
+            )
+            .with_label(&format!("'{}' esperado aqui", word))
+            .with_secondary(opened, &format!("'{}' aberto aqui", opener))
+            .with_suggestion(&format!("feche o bloco com '{}'", word), here, word)
+        )
+    }
+
+    fn unexpected(&self, expected:&str) -> Diagnostic {
+        Diagnostic::error(
+            codes::UNEXPECTED_TOKEN,
+            format!("{} esperado, encontrado {}", expected, Self::describe(self.cursor.peek().map(|l| l.text()))),
+            self.cursor.span()
+        )
+        .with_label(&format!("{} esperado aqui", expected))
+    }
+
+    fn describe(text:Option<&str>) -> String {
+        match text {
+            Some(text) => format!("'{}'", text),
+            None => String::from("fim do arquivo"),
+        }
+    }
+}
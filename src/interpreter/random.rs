@@ -0,0 +1,191 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Where `rand` and `randi` take their numbers from. A source may fail, as
+/// a [`Replay`] that runs out of recorded numbers does.
+pub trait RandomSource {
+    /// Uniform in `[0, 1)`.
+    fn real(&mut self) -> Result<f64, String>;
+
+    /// Uniform in `[0, limit)`; `limit` is positive.
+    fn below(&mut self, limit:u64) -> Result<u64, String>;
+}
+
+impl<T:RandomSource + ?Sized> RandomSource for &mut T {
+    fn real(&mut self) -> Result<f64, String> {
+        (**self).real()
+    }
+
+    fn below(&mut self, limit:u64) -> Result<u64, String> {
+        (**self).below(limit)
+    }
+}
+
+/// Pseudo-random numbers from SplitMix64. The same seed always gives the
+/// same sequence.
+#[derive(Clone, Debug)]
+pub struct SeededRandom {
+    seed: u64,
+    state: u64,
+}
+
+impl SeededRandom {
+    pub fn new(seed:u64) -> Self {
+        Self { seed, state: seed }
+    }
+
+    /// Generator seeded from the clock, different on every run.
+    pub fn from_time() -> Self {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos());
+        Self::new(nanos as u64)
+    }
+
+    /// Seed the sequence started from, to run it again.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl RandomSource for SeededRandom {
+    fn real(&mut self) -> Result<f64, String> {
+        Ok((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64)
+    }
+
+    fn below(&mut self, limit:u64) -> Result<u64, String> {
+        // Drop the top values that would favour the low results.
+        let zone = u64::MAX - u64::MAX % limit;
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return Ok(value % limit);
+            }
+        }
+    }
+}
+
+/// One number a run drew, written in a log as `real 0.25` or
+/// `inteiro 500 253`, the limit before the value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Draw {
+    Real(f64),
+    Integer { limit:u64, value:u64 },
+}
+
+impl Draw {
+    /// Read a line written by [`Draw`]'s `Display`.
+    pub fn parse(line:&str) -> Option<Self> {
+        let words:Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["real", value] => value.parse().ok().map(Draw::Real),
+            ["inteiro", limit, value] => Some(Draw::Integer { limit: limit.parse().ok()?, value: value.parse().ok()? }),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Draw {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Draw::Real(value) => write!(f, "real {}", value),
+            Draw::Integer { limit, value } => write!(f, "inteiro {} {}", limit, value),
+        }
+    }
+}
+
+/// Source that keeps every number another one draws, so the run can be
+/// replayed from [`Recorder::log`].
+pub struct Recorder<S:RandomSource> {
+    source: S,
+    draws: Vec<Draw>,
+}
+
+impl<S:RandomSource> Recorder<S> {
+    pub fn new(source:S) -> Self {
+        Self { source, draws: vec![] }
+    }
+
+    pub fn draws(&self) -> &Vec<Draw> {
+        &self.draws
+    }
+
+    /// One draw per line, the text [`Replay::parse`] reads.
+    pub fn log(&self) -> String {
+        self.draws.iter().map(|draw| format!("{}\n", draw)).collect()
+    }
+}
+
+impl<S:RandomSource> RandomSource for Recorder<S> {
+    fn real(&mut self) -> Result<f64, String> {
+        let value = self.source.real()?;
+        self.draws.push(Draw::Real(value));
+        Ok(value)
+    }
+
+    fn below(&mut self, limit:u64) -> Result<u64, String> {
+        let value = self.source.below(limit)?;
+        self.draws.push(Draw::Integer { limit, value });
+        Ok(value)
+    }
+}
+
+/// Source that gives back the numbers of a recorded run, in order. It
+/// fails when the program asks for a different kind of number than was
+/// recorded, or for more numbers.
+#[derive(Clone, Debug, Default)]
+pub struct Replay {
+    draws: VecDeque<Draw>,
+}
+
+impl Replay {
+    pub fn new(draws:Vec<Draw>) -> Self {
+        Self { draws: draws.into() }
+    }
+
+    /// Read a [`Recorder::log`]. Blank lines are skipped; the error names
+    /// the first line that is not a draw.
+    pub fn parse(log:&str) -> Result<Self, String> {
+        let mut draws = vec![];
+        for (number, line) in log.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let draw = Draw::parse(line)
+                .ok_or_else(|| format!("linha {} não é um número gravado: '{}'", number + 1, line.trim()))?;
+            draws.push(draw);
+        }
+        Ok(Self::new(draws))
+    }
+
+    fn next(&mut self, asked:&str) -> Result<Draw, String> {
+        self.draws.pop_front()
+            .ok_or_else(|| format!("a gravação não tem mais números para {}", asked))
+    }
+}
+
+impl RandomSource for Replay {
+    fn real(&mut self) -> Result<f64, String> {
+        match self.next("um real")? {
+            Draw::Real(value) => Ok(value),
+            other => Err(format!("a gravação tem '{}', mas o programa pediu um real", other)),
+        }
+    }
+
+    fn below(&mut self, limit:u64) -> Result<u64, String> {
+        match self.next(&format!("um inteiro abaixo de {}", limit))? {
+            Draw::Integer { limit: recorded, value } if recorded == limit && value < limit => Ok(value),
+            Draw::Integer { limit: recorded, value } if recorded == limit => {
+                Err(format!("a gravação tem {} para um inteiro abaixo de {}", value, limit))
+            },
+            other => Err(format!("a gravação tem '{}', mas o programa pediu um inteiro abaixo de {}", other, limit)),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use crate::interpreter::tokens::span::Span;
+
+/// Concrete syntax tree produced by matching a grammar: one `Node` per
+/// matched [`super::rule::SyntaxGroup`], one `Leaf` per consumed lexeme.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SyntaxNode {
+    Leaf {
+        text: String,
+        span: Span,
+    },
+    Node {
+        name: String,
+        children: Vec<SyntaxNode>,
+        span: Span,
+    },
+}
+
+impl SyntaxNode {
+    pub fn span(&self) -> Span {
+        match self {
+            SyntaxNode::Leaf { span, .. } | SyntaxNode::Node { span, .. } => *span,
+        }
+    }
+
+    /// Group name, `None` for leaves.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            SyntaxNode::Node { name, .. } => Some(name),
+            SyntaxNode::Leaf { .. } => None,
+        }
+    }
+
+    pub fn children(&self) -> &[SyntaxNode] {
+        match self {
+            SyntaxNode::Node { children, .. } => children,
+            SyntaxNode::Leaf { .. } => &[],
+        }
+    }
+
+    /// Direct children that are nodes of the group `name`.
+    pub fn find<'a>(&'a self, name:&'a str) -> impl Iterator<Item = &'a SyntaxNode> + 'a {
+        self.children().iter().filter(move |c| c.name() == Some(name))
+    }
+}
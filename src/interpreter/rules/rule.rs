@@ -1,27 +1,128 @@
+use crate::interpreter::tokens::schema::TokenTypes;
+
 pub enum SyntaxType {
-    Keyword { key:String }
+    /// A word or symbol, compared without case.
+    Keyword { key:String },
     OneOf { keys:Vec<String> },
     Numeric { accepts_float:bool, accepts_negative:bool },
-    WrappedBy { open:String, close:String },
+    /// String literal.
+    Text,
+    /// Any word that is not a keyword of the grammar.
+    Identifier,
+    /// Any lexeme produced from a token of the given type.
+    Token { schema:TokenTypes },
+    WrappedBy { open:String, close:String, inner:Box<SyntaxRule> },
+    Sequence { rules:Vec<SyntaxRule> },
+    /// Ordered alternatives, the first one that matches wins.
+    Choice { rules:Vec<SyntaxRule> },
+    /// Another [`SyntaxGroup`] of the grammar, by name.
+    Reference { name:String },
 }
 
 pub struct SyntaxRule {
     syntax:SyntaxType,
     optional:bool,
+    repeated:bool,
+    separator:Option<String>,
 }
 
+/// Named rule of a grammar; it becomes a node of the syntax tree.
 pub struct SyntaxGroup {
-    rules:Vect<SyntaxRule>
+    name:String,
+    rules:Vec<SyntaxRule>
 }
 
-pub impl SyntaxRule {
-    fn new(syntax:SyntaxType, { optional:bool = false }) {
-        SyntaxRule { syntax, optional }
+impl SyntaxRule {
+    pub fn new(syntax:SyntaxType) -> Self {
+        SyntaxRule { syntax, optional: false, repeated: false, separator: None }
+    }
+
+    pub fn keyword(key:&str) -> Self {
+        Self::new(SyntaxType::Keyword { key: key.to_string() })
+    }
+
+    pub fn one_of(keys:&[&str]) -> Self {
+        Self::new(SyntaxType::OneOf { keys: keys.iter().map(|k| k.to_string()).collect() })
+    }
+
+    pub fn numeric(accepts_float:bool, accepts_negative:bool) -> Self {
+        Self::new(SyntaxType::Numeric { accepts_float, accepts_negative })
+    }
+
+    pub fn text() -> Self {
+        Self::new(SyntaxType::Text)
+    }
+
+    pub fn identifier() -> Self {
+        Self::new(SyntaxType::Identifier)
+    }
+
+    pub fn token(schema:TokenTypes) -> Self {
+        Self::new(SyntaxType::Token { schema })
+    }
+
+    pub fn wrapped_by(open:&str, close:&str, inner:SyntaxRule) -> Self {
+        Self::new(SyntaxType::WrappedBy { open: open.to_string(), close: close.to_string(), inner: Box::new(inner) })
+    }
+
+    pub fn sequence(rules:Vec<SyntaxRule>) -> Self {
+        Self::new(SyntaxType::Sequence { rules })
+    }
+
+    pub fn choice(rules:Vec<SyntaxRule>) -> Self {
+        Self::new(SyntaxType::Choice { rules })
+    }
+
+    pub fn reference(name:&str) -> Self {
+        Self::new(SyntaxType::Reference { name: name.to_string() })
+    }
+
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    /// Match the rule again while it keeps matching; combined with
+    /// [`SyntaxRule::optional`] it also accepts zero matches.
+    pub fn repeated(mut self) -> Self {
+        self.repeated = true;
+        self
+    }
+
+    /// Repeat the rule with `separator` between the matches.
+    pub fn separated_by(mut self, separator:&str) -> Self {
+        self.repeated = true;
+        self.separator = Some(separator.to_string());
+        self
+    }
+
+    pub fn syntax(&self) -> &SyntaxType {
+        &self.syntax
+    }
+
+    pub fn is_optional(&self) -> bool {
+        self.optional
+    }
+
+    pub fn is_repeated(&self) -> bool {
+        self.repeated
+    }
+
+    pub fn separator(&self) -> Option<&str> {
+        self.separator.as_deref()
     }
 }
 
-pub impl SyntaxGroup {
-    fn new(rules:Vec<SyntaxRule>) {
-        SyntaxGroup { rules }
+impl SyntaxGroup {
+    pub fn new(name:&str, rules:Vec<SyntaxRule>) -> Self {
+        SyntaxGroup { name: name.to_string(), rules }
     }
-}
\ No newline at end of file
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn rules(&self) -> &Vec<SyntaxRule> {
+        &self.rules
+    }
+}
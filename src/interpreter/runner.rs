@@ -0,0 +1,1076 @@
+use std::collections::HashMap;
+use std::io;
+
+use super::ast::expression::{BinaryOperator, Expression, ExpressionKind, Literal, UnaryOperator};
+use super::ast::program::{Parameter, Program, Subprogram, SubprogramKind, VarDeclaration};
+use super::ast::statement::{CaseLabel, Statement, StatementKind, Target, WriteArgument};
+use super::ast::types::{Bounds, Identifier, TypeKind, TypeSpec};
+use super::builtin::Builtin;
+use super::console::handler::IoHandler;
+use super::debug::debugger::Debugger;
+use super::debug::handler::Command;
+use super::debug::state::{CallState, DebugState, Pause, VariableState};
+use super::diagnostics::codes;
+use super::diagnostics::diagnostic::Diagnostic;
+use super::language::Language;
+use super::parser::cursor::MAX_NESTING;
+use super::random::{RandomSource, SeededRandom};
+use super::tokens::span::Span;
+use super::value::Value;
+
+type RunResult<T> = Result<T, Diagnostic>;
+
+/// Calls that may be running at once before the program is stopped, unless
+/// [`Runner::with_max_depth`] sets another limit. Each call also takes
+/// native stack; this many fit the 8 MiB of a main thread even in an
+/// unoptimized build.
+pub const DEFAULT_MAX_DEPTH:usize = 256;
+
+/// Highest limit [`Runner::with_max_depth`] takes, so the stack of
+/// [`stack_size`] stays reasonable.
+pub const MAX_DEPTH_LIMIT:usize = 10_000;
+
+/// Native stack a call takes, with room for the expressions around it. An
+/// unoptimized build uses about half of it for a plain recursive `funcao`.
+const CALL_STACK:usize = 64 * 1024;
+
+/// Native stack a statement or expression nested in another takes to
+/// parse, check or evaluate, in an unoptimized build.
+const NESTING_STACK:usize = 40 * 1024;
+
+/// Native stack kept free for what runs outside the calls, code nested as
+/// deep as the parsers allow included, and to report the error when it
+/// runs out.
+const STACK_RESERVE:usize = 1024 * 1024 + MAX_NESTING * NESTING_STACK;
+
+/// Widest column and most decimal places `escreva` pads or rounds to; larger
+/// ones are cut to it instead of building a huge string.
+pub const MAX_FORMAT_WIDTH:usize = 1000;
+
+/// Native stack for a thread that runs programs allowed `depth` calls at
+/// once, see [`Runner::with_max_depth`].
+pub fn stack_size(depth:usize) -> usize {
+    depth.min(MAX_DEPTH_LIMIT) * CALL_STACK + STACK_RESERVE
+}
+
+/// How a statement finished, so loops and subprograms can unwind.
+enum Flow {
+    Normal,
+    Break,
+    /// `retorne`, with the value of a `funcao`.
+    Return(Option<Value>),
+}
+
+/// Where the value of a name lives: a variable in [`Runner::storage`], or
+/// a single element of it when an array element was passed to a `var`
+/// parameter.
+#[derive(Clone, Copy)]
+struct Binding {
+    slot: usize,
+    element: Option<usize>,
+}
+
+/// What a call gives each parameter.
+enum Argument {
+    Value(Value),
+    /// Every element of an array, copied for a `vetor` parameter.
+    Array(Vec<Value>),
+    /// Shared with the caller, for a `var` parameter.
+    Reference(Binding),
+}
+
+/// Parameters and local variables of a running subprogram.
+struct Frame {
+    /// Subprogram as declared.
+    name: String,
+    /// Where it was called from.
+    call: Span,
+    /// Keyed by [`Runner::key`].
+    locals: HashMap<String, Binding>,
+    /// Names of `locals` as declared, in declaration order.
+    names: Vec<String>,
+    /// Length of [`Runner::storage`] before the call, where its variables
+    /// start.
+    base: usize,
+}
+
+struct Variable {
+    kind: TypeKind,
+    /// The single value, or every element of an array in row-major order.
+    values: Vec<Value>,
+    /// One range per dimension, empty when not an array.
+    bounds: Vec<Bounds>,
+    /// Where the variable was declared, pointed at by type errors.
+    declared: Span,
+}
+
+impl Variable {
+    /// Index of every element of an array, in the order of `values`.
+    fn indices(&self) -> Vec<Vec<i64>> {
+        let mut all = vec![vec![]];
+        for bounds in &self.bounds {
+            all = all.into_iter()
+                .flat_map(|prefix| (bounds.lower..=bounds.upper).map(move |index| {
+                    let mut indices = prefix.clone();
+                    indices.push(index);
+                    indices
+                }))
+                .collect();
+        }
+        all
+    }
+}
+
+/// What a finished run left behind: the final value of every global, in
+/// declaration order. Arrays contribute one entry per element, named as
+/// `v[1]` or `m[1, 2]`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RunOutcome {
+    variables: Vec<(String, Value)>,
+}
+
+impl RunOutcome {
+    pub fn variables(&self) -> &Vec<(String, Value)> {
+        &self.variables
+    }
+
+    pub fn variable(&self, name:&str) -> Option<&Value> {
+        self.variables.iter().find(|(n, _)| n == name).map(|(_, v)| v)
+    }
+}
+
+/// Tree-walking executor for a parsed [`Program`], talking to the user
+/// through an [`IoHandler`]. A subprogram sees its own parameters and
+/// locals, then the globals; never the variables of its caller.
+pub struct Runner<'a> {
+    /// Every live variable: the globals, then the locals of each frame.
+    storage: Vec<Variable>,
+    /// Keyed by [`Runner::key`].
+    globals: HashMap<String, Binding>,
+    /// Names of `globals` as declared, in declaration order.
+    global_names: Vec<String>,
+    /// One per running call, the innermost last.
+    frames: Vec<Frame>,
+    /// Keyed by [`Runner::key`].
+    subprograms: HashMap<String, &'a Subprogram>,
+    /// Keyed by [`Runner::key`].
+    builtins: HashMap<String, Box<dyn Builtin>>,
+    random: Box<dyn RandomSource + 'a>,
+    max_depth: usize,
+    /// Position of the native stack when the run started.
+    stack_base: usize,
+    debugger: Option<Debugger<'a>>,
+    io: &'a mut dyn IoHandler,
+    language: &'a dyn Language,
+}
+
+impl<'a> Runner<'a> {
+    pub fn new(io:&'a mut dyn IoHandler, language:&'a dyn Language) -> Self {
+        Self {
+            storage: vec![],
+            globals: HashMap::new(),
+            global_names: vec![],
+            frames: vec![],
+            subprograms: HashMap::new(),
+            builtins: HashMap::new(),
+            random: Box::new(SeededRandom::from_time()),
+            max_depth: DEFAULT_MAX_DEPTH,
+            stack_base: 0,
+            debugger: None,
+            io,
+            language,
+        }
+    }
+
+    /// Draw the numbers of `rand` and `randi` from `random` instead of a
+    /// generator seeded from the clock.
+    pub fn with_random(mut self, random:Box<dyn RandomSource + 'a>) -> Self {
+        self.random = random;
+        self
+    }
+
+    /// Stop the program when a call would make more than `depth` calls
+    /// run at once, as a recursion without a base case does. `depth` is
+    /// capped at [`MAX_DEPTH_LIMIT`], and a run on a thread smaller than
+    /// [`stack_size`] of it may overflow the native stack. Calls nested in
+    /// large expressions take more stack; the program is stopped in the same
+    /// way when they use up what [`stack_size`] gives before the limit.
+    pub fn with_max_depth(mut self, depth:usize) -> Self {
+        self.max_depth = depth.min(MAX_DEPTH_LIMIT);
+        self
+    }
+
+    /// Let `debugger` stop the program before its statements.
+    pub fn with_debugger(mut self, debugger:Debugger<'a>) -> Self {
+        self.debugger = Some(debugger);
+        self
+    }
+
+    pub fn run(&mut self, program:&'a Program) -> RunResult<RunOutcome> {
+        self.stack_base = Self::stack_position();
+        self.storage.clear();
+        self.globals.clear();
+        self.global_names.clear();
+        self.frames.clear();
+        self.subprograms = program.subprograms.iter()
+            .map(|subprogram| (self.key(&subprogram.name.name), subprogram))
+            .collect();
+        self.builtins = self.language.builtins().into_iter()
+            .map(|builtin| (self.key(builtin.name()), builtin))
+            .collect();
+        self.declare(&program.declarations);
+
+        self.block(&program.body)?;
+
+        let mut outcome = RunOutcome::default();
+        for name in &self.global_names {
+            let binding = self.globals[&self.key(name)];
+            outcome.variables.extend(self.elements(name, binding));
+        }
+        Ok(outcome)
+    }
+
+    /// Value `name` is bound to, or each element of an array named as
+    /// `v[1]` or `m[1, 2]`.
+    fn elements(&self, name:&str, binding:Binding) -> Vec<(String, Value)> {
+        let variable = &self.storage[binding.slot];
+
+        if let Some(element) = binding.element {
+            return vec![(name.to_string(), variable.values[element].clone())];
+        }
+        if variable.bounds.is_empty() {
+            return vec![(name.to_string(), variable.values[0].clone())];
+        }
+        variable.indices().into_iter().zip(&variable.values)
+            .map(|(indices, value)| {
+                let indices:Vec<String> = indices.iter().map(|i| i.to_string()).collect();
+                (format!("{}[{}]", name, indices.join(", ")), value.clone())
+            })
+            .collect()
+    }
+
+    /// Current value of a global variable, `None` for arrays.
+    pub fn variable(&self, name:&str) -> Option<&Value> {
+        self.globals.get(&self.key(name))
+            .map(|binding| &self.storage[binding.slot])
+            .filter(|v| v.bounds.is_empty())
+            .map(|v| &v.values[0])
+    }
+
+    /// Name as stored in the variable table.
+    fn key(&self, name:&str) -> String {
+        if self.language.is_case_sensitive() {
+            name.to_string()
+        } else {
+            name.to_lowercase()
+        }
+    }
+
+    /// Create the variables of `declarations` in the innermost scope.
+    fn declare(&mut self, declarations:&[VarDeclaration]) {
+        for declaration in declarations {
+            for name in &declaration.names {
+                let binding = self.allocate(&declaration.type_spec, declaration.span);
+                self.bind(&name.name, binding);
+            }
+        }
+    }
+
+    /// A new variable holding the default value of its type.
+    fn allocate(&mut self, type_spec:&TypeSpec, declared:Span) -> Binding {
+        let kind = type_spec.kind;
+        let bounds = type_spec.dimensions.clone();
+        let length = bounds.iter().map(|b| b.len()).product();
+
+        self.storage.push(Variable { kind, values: vec![Value::default_for(kind); length], bounds, declared });
+        Binding { slot: self.storage.len() - 1, element: None }
+    }
+
+    fn bind(&mut self, name:&str, binding:Binding) {
+        let key = self.key(name);
+        match self.frames.last_mut() {
+            Some(frame) => {
+                frame.locals.insert(key, binding);
+                frame.names.push(name.to_string());
+            },
+            None => {
+                self.globals.insert(key, binding);
+                self.global_names.push(name.to_string());
+            },
+        }
+    }
+
+    /// Innermost binding of `name`: a local of the running subprogram,
+    /// else a global.
+    fn resolve(&self, name:&Identifier) -> RunResult<Binding> {
+        let key = self.key(&name.name);
+        self.frames.last()
+            .and_then(|frame| frame.locals.get(&key))
+            .or_else(|| self.globals.get(&key))
+            .copied()
+            .ok_or_else(|| Self::undeclared(name))
+    }
+
+    fn block(&mut self, statements:&[Statement]) -> RunResult<Flow> {
+        for statement in statements {
+            match self.statement(statement)? {
+                Flow::Normal => {},
+                flow => return Ok(flow),
+            }
+        }
+        Ok(Flow::Normal)
+    }
+
+    fn statement(&mut self, statement:&Statement) -> RunResult<Flow> {
+        self.checkpoint(statement.span)?;
+
+        match &statement.kind {
+            StatementKind::Assign { target, value } => {
+                let value = self.evaluate(value)?;
+                self.assign(&target.name, &target.indices, value)?;
+            },
+            StatementKind::Write { arguments, newline } => {
+                let mut text = String::new();
+                for argument in arguments {
+                    text.push_str(&self.format_argument(argument)?);
+                }
+                if *newline {
+                    text.push('\n');
+                }
+                self.io.write(&text).map_err(|e| Self::io_failure(e, statement.span))?;
+            },
+            StatementKind::Read { targets } => {
+                for target in targets {
+                    self.read(target)?;
+                }
+            },
+            StatementKind::Call { name, arguments } if self.key(&name.name) == "limpatela" && arguments.is_empty() => {
+                self.io.clear_screen().map_err(|e| Self::io_failure(e, statement.span))?;
+            },
+            StatementKind::Call { name, arguments } => {
+                self.call(name, arguments)?;
+            },
+            StatementKind::If { condition, then_branch, else_branch } => {
+                if self.condition(condition)? {
+                    return self.block(then_branch);
+                } else if let Some(else_branch) = else_branch {
+                    return self.block(else_branch);
+                }
+            },
+            StatementKind::While { condition, body } => {
+                while self.condition(condition)? {
+                    match self.block(body)? {
+                        Flow::Normal => {},
+                        Flow::Break => break,
+                        flow => return Ok(flow),
+                    }
+                }
+            },
+            StatementKind::For { variable, start, end, step, body } => {
+                return self.for_loop(variable, start, end, step.as_deref(), body);
+            },
+            StatementKind::Repeat { body, condition } => {
+                loop {
+                    match self.block(body)? {
+                        Flow::Normal => {},
+                        Flow::Break => break,
+                        flow => return Ok(flow),
+                    }
+                    if let Some(condition) = condition {
+                        if self.condition(condition)? {
+                            break;
+                        }
+                    }
+                }
+            },
+            StatementKind::Choose { subject, cases, default } => {
+                let subject_value = self.evaluate(subject)?;
+
+                for case in cases {
+                    for label in &case.labels {
+                        if self.matches_label(&subject_value, label, subject.span)? {
+                            return self.block(&case.body);
+                        }
+                    }
+                }
+
+                if let Some(default) = default {
+                    return self.block(default);
+                }
+            },
+            StatementKind::Return { value } => {
+                let value = match value {
+                    Some(value) => Some(self.evaluate(value)?),
+                    None => None,
+                };
+                return Ok(Flow::Return(value));
+            },
+            StatementKind::Break => return Ok(Flow::Break),
+        }
+
+        Ok(Flow::Normal)
+    }
+
+    /// Let the debugger, if any, stop before the statement at `span`.
+    fn checkpoint(&mut self, span:Span) -> RunResult<()> {
+        // Taken out meanwhile, so what a breakpoint condition calls runs
+        // without stopping.
+        let Some(mut debugger) = self.debugger.take() else {
+            return Ok(());
+        };
+        let result = self.pause(&mut debugger, span);
+        self.debugger = Some(debugger);
+        result
+    }
+
+    fn pause(&mut self, debugger:&mut Debugger, span:Span) -> RunResult<()> {
+        let depth = self.frames.len();
+        let line = span.start().line();
+
+        let mut pause = match debugger.interrupt() {
+            Some(Command::Stop) => return Err(Self::stopped(span)),
+            Some(Command::Pause) => Some(Pause::Requested),
+            _ => debugger.is_stepping(depth).then_some(Pause::Step),
+        };
+
+        if let (None, Some(breakpoint)) = (&pause, debugger.breakpoints().at(line)) {
+            pause = match breakpoint.condition().map(|condition| self.condition(condition)) {
+                None | Some(Ok(true)) => Some(Pause::Breakpoint { line }),
+                Some(Ok(false)) => None,
+                Some(Err(error)) => Some(Pause::ConditionFailed { line, message: error.message().to_string() }),
+            };
+        }
+
+        let Some(pause) = pause else {
+            return Ok(());
+        };
+        let state = self.debug_state(span, pause);
+        match debugger.pause(&state, depth) {
+            true => Ok(()),
+            false => Err(Self::stopped(span)),
+        }
+    }
+
+    /// Where the program is and the variables of every scope.
+    fn debug_state(&self, span:Span, pause:Pause) -> DebugState {
+        let variable = |name:&String, binding:Binding| {
+            let kind = self.storage[binding.slot].kind;
+            VariableState::new(name, kind, self.elements(name, binding))
+        };
+
+        let calls = self.frames.iter()
+            .map(|frame| {
+                let variables = frame.names.iter()
+                    .map(|name| variable(name, frame.locals[&self.key(name)]))
+                    .collect();
+                CallState::new(&frame.name, frame.call, variables)
+            })
+            .collect();
+        let globals = self.global_names.iter()
+            .map(|name| variable(name, self.globals[&self.key(name)]))
+            .collect();
+
+        DebugState::new(span, pause, calls, globals)
+    }
+
+    fn for_loop(
+        &mut self,
+        variable:&Identifier,
+        start:&Expression,
+        end:&Expression,
+        step:Option<&Expression>,
+        body:&[Statement]
+    ) -> RunResult<Flow> {
+        let start = self.integer(start)?;
+        let end = self.integer(end)?;
+        let step = match step {
+            Some(step) => self.integer(step)?,
+            None => 1,
+        };
+
+        if step == 0 {
+            return Err(Diagnostic::error(
+                codes::ZERO_STEP,
+                String::from("passo do laço para não pode ser zero"),
+                variable.span
+            ).with_note("com passo zero o laço nunca terminaria"));
+        }
+
+        let mut current = start;
+        while (step > 0 && current <= end) || (step < 0 && current >= end) {
+            self.assign(variable, &[], Value::Inteiro(current))?;
+
+            match self.block(body)? {
+                Flow::Normal => {},
+                Flow::Break => return Ok(Flow::Normal),
+                flow => return Ok(flow),
+            }
+
+            let next = match self.lookup(variable, &[])? {
+                Value::Inteiro(value) => value.checked_add(step),
+                _ => current.checked_add(step),
+            };
+            // Past the largest or smallest inteiro the loop is over anyway.
+            let Some(next) = next else {
+                return Ok(Flow::Normal);
+            };
+            current = next;
+        }
+
+        self.assign(variable, &[], Value::Inteiro(current))?;
+        Ok(Flow::Normal)
+    }
+
+    /// Run a `procedimento` or `funcao`, returning the value of a `funcao`.
+    /// Arguments are evaluated in the caller's scope; a `var` parameter
+    /// shares the caller's variable instead of copying its value.
+    fn call(&mut self, name:&Identifier, arguments:&[Expression]) -> RunResult<Option<Value>> {
+        let key = self.key(&name.name);
+        let Some(subprogram) = self.subprograms.get(&key).copied() else {
+            if self.builtins.contains_key(&key) {
+                return self.call_builtin(&key, name, arguments).map(Some);
+            }
+            return Err(Self::unsupported_call(name));
+        };
+
+        if self.frames.len() >= self.max_depth {
+            return Err(
+                Diagnostic::error(
+                    codes::CALL_DEPTH_EXCEEDED,
+                    format!("limite de {} chamadas em andamento excedido ao chamar '{}'", self.max_depth, name.name),
+                    name.span
+                )
+                .with_secondary(subprogram.name.span, "definido aqui")
+                .with_note("verifique se a recursão tem um caso que para de chamar de novo")
+            );
+        }
+
+        if Self::stack_position().abs_diff(self.stack_base) > stack_size(self.max_depth) - STACK_RESERVE {
+            return Err(
+                Diagnostic::error(
+                    codes::CALL_DEPTH_EXCEEDED,
+                    format!("pilha de execução esgotada com {} chamadas em andamento ao chamar '{}'", self.frames.len(), name.name),
+                    name.span
+                )
+                .with_secondary(subprogram.name.span, "definido aqui")
+                .with_note("as chamadas estão dentro de expressões grandes demais; simplifique-as ou diminua a recursão")
+            );
+        }
+
+        let mut passed = vec![];
+        for (parameter, argument) in subprogram.parameters.iter().zip(arguments) {
+            let array = !parameter.type_spec.dimensions.is_empty();
+            passed.push(match (parameter.by_reference, array) {
+                (true, false) => Argument::Reference(self.reference(argument)?),
+                (false, false) => Argument::Value(self.evaluate(argument)?),
+                (true, true) => Argument::Reference(self.array_argument(parameter, argument)?),
+                (false, true) => {
+                    let binding = self.array_argument(parameter, argument)?;
+                    Argument::Array(self.storage[binding.slot].values.clone())
+                },
+            });
+        }
+
+        self.frames.push(Frame {
+            name: subprogram.name.name.clone(),
+            call: name.span,
+            locals: HashMap::new(),
+            names: vec![],
+            base: self.storage.len(),
+        });
+        let result = self.enter(subprogram, passed, arguments);
+        let frame = self.frames.pop().expect("frame pushed above");
+        self.storage.truncate(frame.base);
+
+        let value = match result? {
+            Flow::Return(value) => value,
+            _ => None,
+        };
+
+        match (subprogram.kind, value) {
+            (SubprogramKind::Procedimento, _) => Ok(None),
+            (SubprogramKind::Funcao, Some(value)) => {
+                let kind = subprogram.return_type.as_ref().map_or(value.type_kind(), |t| t.kind);
+                let found = value.type_kind();
+                value.coerce(kind).map(Some).ok_or_else(|| Diagnostic::error(
+                    codes::ASSIGNMENT_MISMATCH,
+                    format!("funcao '{}' retorna {}, mas o valor é {}", name.name, kind, found),
+                    name.span
+                ))
+            },
+            (SubprogramKind::Funcao, None) => Err(
+                Diagnostic::error(
+                    codes::FUNCTION_WITHOUT_RETURN,
+                    format!("funcao '{}' terminou sem retorne", name.name),
+                    name.span
+                )
+                .with_secondary(subprogram.name.span, "definida aqui")
+            ),
+        }
+    }
+
+    fn call_builtin(&mut self, key:&str, name:&Identifier, arguments:&[Expression]) -> RunResult<Value> {
+        let mut values = vec![];
+        for argument in arguments {
+            values.push(self.evaluate(argument)?);
+        }
+
+        let builtin = &self.builtins[key];
+        builtin.call(&values, self.random.as_mut()).map_err(|message| {
+            Diagnostic::error(codes::BUILTIN_FAILED, message, name.span)
+                .with_note(&format!("uso: {}", builtin.hint()))
+        })
+    }
+
+    /// Bind the parameters and locals of `subprogram` in the new frame and
+    /// run its body.
+    fn enter(&mut self, subprogram:&Subprogram, passed:Vec<Argument>, arguments:&[Expression]) -> RunResult<Flow> {
+        for ((parameter, passed), argument) in subprogram.parameters.iter().zip(passed).zip(arguments) {
+            let binding = match passed {
+                Argument::Value(value) => {
+                    let binding = self.allocate(&parameter.type_spec, parameter.span);
+                    let found = value.type_kind();
+                    self.storage[binding.slot].values[0] = value.coerce(parameter.type_spec.kind).ok_or_else(|| {
+                        Diagnostic::error(
+                            codes::ASSIGNMENT_MISMATCH,
+                            format!("não é possível passar {} para '{}' do tipo {}", found, parameter.name.name, parameter.type_spec.kind),
+                            argument.span
+                        )
+                        .with_secondary(parameter.span, "parâmetro declarado aqui")
+                    })?;
+                    binding
+                },
+                Argument::Array(values) => {
+                    let binding = self.allocate(&parameter.type_spec, parameter.span);
+                    let kind = parameter.type_spec.kind;
+                    let values:Option<Vec<Value>> = values.into_iter().map(|value| value.coerce(kind)).collect();
+                    self.storage[binding.slot].values = values.ok_or_else(|| {
+                        Diagnostic::error(
+                            codes::ASSIGNMENT_MISMATCH,
+                            format!("não é possível passar o vetor para '{}' de elementos {}", parameter.name.name, kind),
+                            argument.span
+                        )
+                        .with_secondary(parameter.span, "parâmetro declarado aqui")
+                    })?;
+                    binding
+                },
+                Argument::Reference(binding) => binding,
+            };
+            self.bind(&parameter.name.name, binding);
+        }
+        self.declare(&subprogram.declarations);
+
+        self.block(&subprogram.body)
+    }
+
+    /// Address of a local, to measure how much native stack the run uses.
+    #[inline(never)]
+    fn stack_position() -> usize {
+        let marker = 0u8;
+        std::hint::black_box(&marker) as *const u8 as usize
+    }
+
+    fn is_callable(&self, name:&Identifier) -> bool {
+        let key = self.key(&name.name);
+        self.subprograms.contains_key(&key) || self.builtins.contains_key(&key)
+    }
+
+    fn call_value(&mut self, name:&Identifier, arguments:&[Expression], span:Span) -> RunResult<Value> {
+        self.call(name, arguments)?.ok_or_else(|| Diagnostic::error(
+            codes::NO_RETURN_VALUE,
+            format!("procedimento '{}' não retorna valor", name.name),
+            span
+        ))
+    }
+
+    /// Binding a `var` parameter shares with `argument`.
+    fn reference(&mut self, argument:&Expression) -> RunResult<Binding> {
+        match &argument.kind {
+            ExpressionKind::Variable(name) => self.resolve(name),
+            ExpressionKind::Index { name, indices } => {
+                let (slot, offset) = self.locate(name, indices)?;
+                Ok(Binding { slot, element: Some(offset) })
+            },
+            _ => Err(Diagnostic::error(
+                codes::ARGUMENT_TYPE,
+                String::from("parâmetro 'var' precisa receber uma variável"),
+                argument.span
+            )),
+        }
+    }
+
+    /// The caller's array passed whole to the `vetor` parameter
+    /// `parameter`, which must have the same bounds.
+    fn array_argument(&self, parameter:&Parameter, argument:&Expression) -> RunResult<Binding> {
+        let ExpressionKind::Variable(name) = &argument.kind else {
+            return Err(Diagnostic::error(
+                codes::ARGUMENT_TYPE,
+                format!("parâmetro '{}' precisa receber um vetor", parameter.name.name),
+                argument.span
+            ));
+        };
+        let binding = self.resolve(name)?;
+        let variable = &self.storage[binding.slot];
+
+        let range = |bounds:&[Bounds]| bounds.iter().map(|b| (b.lower, b.upper)).collect::<Vec<_>>();
+        if binding.element.is_some() || range(&variable.bounds) != range(&parameter.type_spec.dimensions) {
+            let list = |bounds:&[Bounds]| bounds.iter().map(|b| b.to_string()).collect::<Vec<_>>().join(", ");
+            return Err(
+                Diagnostic::error(
+                    codes::ASSIGNMENT_MISMATCH,
+                    format!(
+                        "'{}' tem limites [{}], mas o parâmetro '{}' espera [{}]",
+                        name.name, list(&variable.bounds), parameter.name.name, list(&parameter.type_spec.dimensions)
+                    ),
+                    argument.span
+                )
+                .with_secondary(parameter.span, "parâmetro declarado aqui")
+                .with_secondary(variable.declared, "vetor declarado aqui")
+            );
+        }
+        Ok(binding)
+    }
+
+    fn matches_label(&mut self, subject:&Value, label:&CaseLabel, span:Span) -> RunResult<bool> {
+        match label {
+            CaseLabel::Value(value) => {
+                let value = self.evaluate(value)?;
+                Ok(Self::compare(BinaryOperator::Equal, subject, &value, span)? == Value::Logico(true))
+            },
+            CaseLabel::Range(low, high) => {
+                let low = self.evaluate(low)?;
+                let high = self.evaluate(high)?;
+                let above = Self::compare(BinaryOperator::GreaterEqual, subject, &low, span)?;
+                let below = Self::compare(BinaryOperator::LessEqual, subject, &high, span)?;
+                Ok(above == Value::Logico(true) && below == Value::Logico(true))
+            },
+        }
+    }
+
+    fn format_argument(&mut self, argument:&WriteArgument) -> RunResult<String> {
+        let value = self.evaluate(&argument.value)?;
+
+        let width = match &argument.width {
+            Some(width) => Some(Self::format_size(self.integer(width)?)),
+            None => None,
+        };
+        let precision = match &argument.precision {
+            Some(precision) => Some(Self::format_size(self.integer(precision)?)),
+            None => None,
+        };
+
+        let text = match (&value, precision) {
+            (Value::Real(real), Some(precision)) => format!("{:.*}", precision, real),
+            (_, None) if width.is_none() => self.language.write(&value),
+            _ => value.to_string(),
+        };
+
+        Ok(match width {
+            Some(width) => format!("{:>width$}", text, width = width),
+            None => text,
+        })
+    }
+
+    /// A width or precision of `escreva`, between zero and [`MAX_FORMAT_WIDTH`].
+    fn format_size(size:i64) -> usize {
+        size.clamp(0, MAX_FORMAT_WIDTH as i64) as usize
+    }
+
+    fn read(&mut self, target:&Target) -> RunResult<()> {
+        let span = target.span();
+        let kind = self.variable_kind(&target.name)?;
+
+        let line = self.io.read_line().map_err(|e| Self::io_failure(e, span))?;
+        let Some(text) = line.as_deref() else {
+            return Err(Diagnostic::error(
+                codes::INPUT_EXHAUSTED,
+                String::from("nenhuma entrada disponível para leia"),
+                span
+            ));
+        };
+
+        let value = Self::parse_input(text, kind).ok_or_else(|| Diagnostic::error(
+            codes::INVALID_INPUT,
+            format!("valor '{}' inválido para '{}' do tipo {}", text, target.name.name, kind),
+            span
+        ))?;
+
+        self.assign(&target.name, &target.indices, value)
+    }
+
+    fn parse_input(text:&str, kind:TypeKind) -> Option<Value> {
+        let trimmed = text.trim();
+        match kind {
+            TypeKind::Inteiro => trimmed.parse().ok().map(Value::Inteiro),
+            TypeKind::Real => trimmed.replace(',', ".").parse().ok().map(Value::Real),
+            TypeKind::Caractere => Some(Value::Caractere(text.to_string())),
+            TypeKind::Logico => match trimmed.to_uppercase().as_str() {
+                "VERDADEIRO" | "V" => Some(Value::Logico(true)),
+                "FALSO" | "F" => Some(Value::Logico(false)),
+                _ => None,
+            },
+        }
+    }
+
+    fn variable_kind(&self, name:&Identifier) -> RunResult<TypeKind> {
+        self.resolve(name).map(|binding| self.storage[binding.slot].kind)
+    }
+
+    /// Slot of the variable `name` and position in its
+    /// [`Variable::values`] of the element `indices` point at, failing
+    /// when one is outside its declared range.
+    fn locate(&mut self, name:&Identifier, indices:&[Expression]) -> RunResult<(usize, usize)> {
+        let mut evaluated = vec![];
+        for index in indices {
+            evaluated.push((self.integer(index)?, index.span));
+        }
+
+        let binding = self.resolve(name)?;
+        let variable = &self.storage[binding.slot];
+        let dimensions = if binding.element.is_some() { 0 } else { variable.bounds.len() };
+        if dimensions != evaluated.len() {
+            return Err(Diagnostic::error(
+                codes::INDEX_COUNT,
+                format!("'{}' tem {} dimensão(ões), mas recebeu {} índice(s)", name.name, dimensions, evaluated.len()),
+                name.span
+            ).with_secondary(variable.declared, "declarada aqui"));
+        }
+        if let Some(element) = binding.element {
+            return Ok((binding.slot, element));
+        }
+
+        let mut offset = 0;
+        for (bounds, (index, span)) in variable.bounds.iter().zip(evaluated) {
+            if !bounds.contains(index) {
+                return Err(
+                    Diagnostic::error(
+                        codes::INDEX_OUT_OF_BOUNDS,
+                        format!("índice {} fora dos limites {} de '{}'", index, bounds, name.name),
+                        span
+                    )
+                    .with_label("índice fora dos limites")
+                    .with_secondary(bounds.span, "limites declarados aqui")
+                );
+            }
+            offset = offset * bounds.len() + (index - bounds.lower) as usize;
+        }
+        Ok((binding.slot, offset))
+    }
+
+    fn lookup(&mut self, name:&Identifier, indices:&[Expression]) -> RunResult<Value> {
+        let (slot, offset) = self.locate(name, indices)?;
+        Ok(self.storage[slot].values[offset].clone())
+    }
+
+    fn assign(&mut self, target:&Identifier, indices:&[Expression], value:Value) -> RunResult<()> {
+        let (slot, offset) = self.locate(target, indices)?;
+        let variable = &mut self.storage[slot];
+        let found = value.type_kind();
+
+        variable.values[offset] = value.coerce(variable.kind).ok_or_else(|| Diagnostic::error(
+            codes::ASSIGNMENT_MISMATCH,
+            format!("não é possível atribuir {} a '{}' do tipo {}", found, target.name, variable.kind),
+            target.span
+        ).with_secondary(variable.declared, &format!("declarada como {} aqui", variable.kind)))?;
+
+        Ok(())
+    }
+
+    fn undeclared(name:&Identifier) -> Diagnostic {
+        Diagnostic::error(
+            codes::UNDECLARED_VARIABLE,
+            format!("variável '{}' não declarada", name.name),
+            name.span
+        ).with_note("variáveis devem ser declaradas na seção 'var'")
+    }
+
+    fn io_failure(error:io::Error, span:Span) -> Diagnostic {
+        Diagnostic::error(codes::IO_FAILURE, format!("falha no console: {}", error), span)
+    }
+
+    fn stopped(span:Span) -> Diagnostic {
+        Diagnostic::error(codes::DEBUG_STOPPED, String::from("execução parada pelo depurador"), span)
+    }
+
+    fn unsupported_call(name:&Identifier) -> Diagnostic {
+        Diagnostic::error(
+            codes::UNSUPPORTED_CALL,
+            format!("chamada de '{}' não suportada", name.name),
+            name.span
+        )
+    }
+
+    fn condition(&mut self, expression:&Expression) -> RunResult<bool> {
+        match self.evaluate(expression)? {
+            Value::Logico(value) => Ok(value),
+            other => Err(Diagnostic::error(
+                codes::CONDITION_NOT_LOGICAL,
+                format!("condição deve ser logico, encontrado {}", other.type_kind()),
+                expression.span
+            )),
+        }
+    }
+
+    fn integer(&mut self, expression:&Expression) -> RunResult<i64> {
+        match self.evaluate(expression)? {
+            Value::Inteiro(value) => Ok(value),
+            other => Err(Diagnostic::error(
+                codes::EXPECTED_INTEGER,
+                format!("valor inteiro esperado, encontrado {}", other.type_kind()),
+                expression.span
+            )),
+        }
+    }
+
+    pub fn evaluate(&mut self, expression:&Expression) -> RunResult<Value> {
+        match &expression.kind {
+            ExpressionKind::Literal(literal) => Ok(match literal {
+                Literal::Integer(value) => Value::Inteiro(*value),
+                Literal::Real(value) => Value::Real(*value),
+                Literal::String(value) => Value::Caractere(value.clone()),
+                Literal::Boolean(value) => Value::Logico(*value),
+            }),
+            // A `funcao` without parameters may be called without parentheses.
+            ExpressionKind::Variable(name) if self.resolve(name).is_err() && self.is_callable(name) => {
+                self.call_value(name, &[], expression.span)
+            },
+            ExpressionKind::Variable(name) => self.lookup(name, &[]),
+            ExpressionKind::Index { name, indices } => self.lookup(name, indices),
+            ExpressionKind::Call { name, arguments } => self.call_value(name, arguments, expression.span),
+            ExpressionKind::Unary { operator, operand } => {
+                let value = self.evaluate(operand)?;
+                Self::unary(*operator, value, expression.span)
+            },
+            ExpressionKind::Binary { operator: BinaryOperator::And, left, right } => {
+                Ok(Value::Logico(self.condition(left)? && self.condition(right)?))
+            },
+            ExpressionKind::Binary { operator: BinaryOperator::Or, left, right } => {
+                Ok(Value::Logico(self.condition(left)? || self.condition(right)?))
+            },
+            ExpressionKind::Binary { operator: BinaryOperator::Xor, left, right } => {
+                Ok(Value::Logico(self.condition(left)? != self.condition(right)?))
+            },
+            ExpressionKind::Binary { operator, left, right } => {
+                let left = self.evaluate(left)?;
+                let right = self.evaluate(right)?;
+                Self::binary(*operator, &left, &right, expression.span)
+            },
+        }
+    }
+
+    fn unary(operator:UnaryOperator, value:Value, span:Span) -> RunResult<Value> {
+        match (operator, value) {
+            (UnaryOperator::Negate, Value::Inteiro(value)) => Ok(Value::Inteiro(value.wrapping_neg())),
+            (UnaryOperator::Negate, Value::Real(value)) => Ok(Value::Real(-value)),
+            (UnaryOperator::Plus, value @ (Value::Inteiro(_) | Value::Real(_))) => Ok(value),
+            (UnaryOperator::Not, Value::Logico(value)) => Ok(Value::Logico(!value)),
+            (operator, value) => Err(Diagnostic::error(
+                codes::OPERATOR_MISMATCH,
+                format!("operador '{}' não se aplica a {}", operator, value.type_kind()),
+                span
+            )),
+        }
+    }
+
+    fn binary(operator:BinaryOperator, left:&Value, right:&Value, span:Span) -> RunResult<Value> {
+        use BinaryOperator::*;
+
+        match operator {
+            Add | Subtract | Multiply | Divide | Power => Self::arithmetic(operator, left, right, span),
+            IntegerDivide | Modulo => Self::integer_arithmetic(operator, left, right, span),
+            Quotient if matches!((left, right), (Value::Inteiro(_), Value::Inteiro(_))) => {
+                Self::integer_arithmetic(operator, left, right, span)
+            },
+            Quotient => Self::arithmetic(operator, left, right, span),
+            _ => Self::compare(operator, left, right, span),
+        }
+    }
+
+    fn arithmetic(operator:BinaryOperator, left:&Value, right:&Value, span:Span) -> RunResult<Value> {
+        use BinaryOperator::*;
+
+        match (operator, left, right) {
+            (Add, Value::Caractere(a), Value::Caractere(b)) => return Ok(Value::Caractere(format!("{}{}", a, b))),
+            (Add, Value::Inteiro(a), Value::Inteiro(b)) => return Ok(Value::Inteiro(a.wrapping_add(*b))),
+            (Subtract, Value::Inteiro(a), Value::Inteiro(b)) => return Ok(Value::Inteiro(a.wrapping_sub(*b))),
+            (Multiply, Value::Inteiro(a), Value::Inteiro(b)) => return Ok(Value::Inteiro(a.wrapping_mul(*b))),
+            (Power, Value::Inteiro(a), Value::Inteiro(b)) => {
+                let Ok(exponent) = u32::try_from(*b) else {
+                    return Err(Diagnostic::error(
+                        codes::OPERATOR_MISMATCH,
+                        format!("expoente negativo {} em potência de inteiros", b),
+                        span
+                    ).with_note("use uma base real para obter um resultado fracionário"));
+                };
+                return Ok(Value::Inteiro(a.wrapping_pow(exponent)));
+            },
+            _ => {}
+        }
+
+        let (Some(a), Some(b)) = (left.as_number(), right.as_number()) else {
+            return Err(Self::mismatch(operator, left, right, span));
+        };
+
+        Ok(Value::Real(match operator {
+            Add => a + b,
+            Subtract => a - b,
+            Multiply => a * b,
+            Power => a.powf(b),
+            _ => {
+                if b == 0.0 {
+                    return Err(Diagnostic::error(codes::DIVISION_BY_ZERO, String::from("divisão por zero"), span));
+                }
+                a / b
+            },
+        }))
+    }
+
+    /// `div`, `mod` and integer quotients only take integers; they
+    /// truncate towards zero.
+    fn integer_arithmetic(operator:BinaryOperator, left:&Value, right:&Value, span:Span) -> RunResult<Value> {
+        let (Value::Inteiro(a), Value::Inteiro(b)) = (left, right) else {
+            return Err(Self::mismatch(operator, left, right, span));
+        };
+
+        if *b == 0 {
+            return Err(Diagnostic::error(codes::DIVISION_BY_ZERO, String::from("divisão por zero"), span));
+        }
+
+        Ok(Value::Inteiro(match operator {
+            BinaryOperator::Modulo => a.wrapping_rem(*b),
+            _ => a.wrapping_div(*b),
+        }))
+    }
+
+    fn compare(operator:BinaryOperator, left:&Value, right:&Value, span:Span) -> RunResult<Value> {
+        use std::cmp::Ordering;
+        use BinaryOperator::*;
+
+        let ordering = match (left, right) {
+            (Value::Caractere(a), Value::Caractere(b)) => a.cmp(b),
+            (Value::Logico(a), Value::Logico(b)) if matches!(operator, Equal | NotEqual) => a.cmp(b),
+            _ => match (left.as_number(), right.as_number()) {
+                (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+                _ => return Err(Self::mismatch(operator, left, right, span)),
+            },
+        };
+
+        Ok(Value::Logico(match operator {
+            Equal => ordering == Ordering::Equal,
+            NotEqual => ordering != Ordering::Equal,
+            Less => ordering == Ordering::Less,
+            LessEqual => ordering != Ordering::Greater,
+            Greater => ordering == Ordering::Greater,
+            GreaterEqual => ordering != Ordering::Less,
+            _ => return Err(Self::mismatch(operator, left, right, span)),
+        }))
+    }
+
+    fn mismatch(operator:BinaryOperator, left:&Value, right:&Value, span:Span) -> Diagnostic {
+        Diagnostic::error(
+            codes::OPERATOR_MISMATCH,
+            format!("operador '{}' não se aplica a {} e {}", operator, left.type_kind(), right.type_kind()),
+            span
+        )
+    }
+}
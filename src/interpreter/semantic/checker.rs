@@ -0,0 +1,613 @@
+use crate::interpreter::ast::expression::{BinaryOperator, Expression, ExpressionKind, Literal, UnaryOperator};
+use crate::interpreter::ast::program::{Program, Subprogram, VarDeclaration};
+use crate::interpreter::ast::statement::{CaseLabel, Statement, StatementKind};
+use crate::interpreter::ast::types::{Identifier, TypeKind, TypeSpec};
+use crate::interpreter::builtin::Builtin;
+use crate::interpreter::diagnostics::codes;
+use crate::interpreter::diagnostics::diagnostic::Diagnostic;
+use crate::interpreter::tokens::span::Span;
+
+use super::symbols::{ParameterType, Signature, Symbol, SymbolTable};
+
+/// Most elements an array may hold, all dimensions together.
+const MAX_ELEMENTS:usize = 1_000_000;
+
+/// Semantic pass run before execution: resolves every name against a
+/// [`SymbolTable`] and infers expression types, collecting all problems
+/// instead of stopping at the first one.
+pub struct TypeChecker {
+    symbols:SymbolTable,
+    diagnostics:Vec<Diagnostic>,
+    /// Return type of the `funcao` being checked.
+    returning:Option<TypeKind>,
+}
+
+impl TypeChecker {
+    pub fn new(case_sensitive:bool) -> Self {
+        let mut symbols = SymbolTable::new(case_sensitive);
+        symbols.declare_subprogram(Signature::new("limpatela", vec![], None)).ok();
+
+        Self { symbols, diagnostics: vec![], returning: None }
+    }
+
+    /// Let programs call `builtins` as if they were declared.
+    pub fn with_builtins(mut self, builtins:&[Box<dyn Builtin>]) -> Self {
+        for builtin in builtins {
+            self.symbols.declare_subprogram(builtin.signature()).ok();
+        }
+        self
+    }
+
+    pub fn check(mut self, program:&Program) -> Result<(), Vec<Diagnostic>> {
+        self.declare_variables(&program.declarations);
+
+        for subprogram in &program.subprograms {
+            self.declare_subprogram(subprogram);
+        }
+        for subprogram in &program.subprograms {
+            self.subprogram(subprogram);
+        }
+
+        self.block(&program.body);
+
+        if self.diagnostics.is_empty() {
+            Ok(())
+        } else {
+            Err(self.diagnostics)
+        }
+    }
+
+    fn declare_variables(&mut self, declarations:&[VarDeclaration]) {
+        for declaration in declarations {
+            self.expect_bounds(&declaration.type_spec);
+            for name in &declaration.names {
+                self.declare(name, &declaration.type_spec);
+            }
+        }
+    }
+
+    /// Each range must go upwards, and the array must fit in
+    /// [`MAX_ELEMENTS`].
+    fn expect_bounds(&mut self, type_spec:&TypeSpec) {
+        let mut elements:usize = 1;
+
+        for bounds in &type_spec.dimensions {
+            if bounds.is_empty() {
+                self.diagnostics.push(
+                    Diagnostic::error(
+                        codes::INVALID_BOUNDS,
+                        format!("limites {} inválidos, o primeiro índice passa do último", bounds),
+                        bounds.span
+                    )
+                    .with_label("limites declarados aqui")
+                );
+                return;
+            }
+            elements = elements.saturating_mul(bounds.len());
+        }
+
+        if elements > MAX_ELEMENTS {
+            self.diagnostics.push(
+                Diagnostic::error(
+                    codes::INVALID_BOUNDS,
+                    format!("vetor com {} elementos passa do limite de {}", elements, MAX_ELEMENTS),
+                    type_spec.span
+                )
+                .with_label("declarado aqui")
+            );
+        }
+    }
+
+    fn declare(&mut self, name:&Identifier, type_spec:&TypeSpec) {
+        let symbol = Symbol::new(name.name.clone(), type_spec.kind, name.span)
+            .with_dimensions(type_spec.dimensions.len());
+
+        if let Err(previous) = self.symbols.declare(symbol) {
+            let previous = *previous.span();
+            self.diagnostics.push(
+                Diagnostic::error(
+                    codes::DUPLICATE_DECLARATION,
+                    format!("'{}' já foi declarada neste escopo", name.name),
+                    name.span
+                )
+                .with_label("declarada novamente aqui")
+                .with_secondary(previous, "declarada pela primeira vez aqui")
+            );
+        }
+    }
+
+    fn declare_subprogram(&mut self, subprogram:&Subprogram) {
+        let name = &subprogram.name;
+
+        if let Err(previous) = self.symbols.declare_subprogram(Signature::from_subprogram(subprogram)) {
+            let mut diagnostic = Diagnostic::error(
+                codes::DUPLICATE_DECLARATION,
+                format!("'{}' já foi definido", name.name),
+                name.span
+            );
+            match previous.span() {
+                Some(span) => diagnostic = diagnostic.with_secondary(*span, "definido pela primeira vez aqui"),
+                None => diagnostic = diagnostic.with_note("o nome já existe na linguagem, escolha outro"),
+            }
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    fn subprogram(&mut self, subprogram:&Subprogram) {
+        self.symbols.push_scope();
+        self.returning = subprogram.return_type.as_ref().map(|t| t.kind);
+
+        for parameter in &subprogram.parameters {
+            self.expect_bounds(&parameter.type_spec);
+            self.declare(&parameter.name, &parameter.type_spec);
+        }
+        self.declare_variables(&subprogram.declarations);
+        self.block(&subprogram.body);
+
+        if let Some(kind) = self.returning {
+            if !Self::returns(&subprogram.body) {
+                self.diagnostics.push(
+                    Diagnostic::error(
+                        codes::MISSING_RETURN,
+                        format!("funcao '{}' pode terminar sem retorne", subprogram.name.name),
+                        subprogram.name.span
+                    )
+                    .with_label("definida aqui")
+                    .with_note(&format!("todo caminho da funcao deve chegar a um retorne com valor {}", kind))
+                );
+            }
+        }
+
+        self.returning = None;
+        self.symbols.pop_scope();
+    }
+
+    /// Whether running `statements` always reaches a `retorne`. Loops
+    /// are not counted, as their body may not run.
+    fn returns(statements:&[Statement]) -> bool {
+        statements.iter().any(|statement| match &statement.kind {
+            StatementKind::Return { .. } => true,
+            StatementKind::If { then_branch, else_branch: Some(else_branch), .. } => {
+                Self::returns(then_branch) && Self::returns(else_branch)
+            },
+            StatementKind::Choose { cases, default: Some(default), .. } => {
+                cases.iter().all(|case| Self::returns(&case.body)) && Self::returns(default)
+            },
+            _ => false,
+        })
+    }
+
+    fn block(&mut self, statements:&[Statement]) {
+        for statement in statements {
+            self.statement(statement);
+        }
+    }
+
+    fn statement(&mut self, statement:&Statement) {
+        match &statement.kind {
+            StatementKind::Assign { target, value } => {
+                let found = self.expression(value);
+                if let (Some(symbol), Some(found)) = (self.element(&target.name, &target.indices), found) {
+                    self.expect_assignable(&symbol, found, value.span, &target.name);
+                }
+            },
+            StatementKind::Write { arguments, .. } => {
+                for argument in arguments {
+                    self.expression(&argument.value);
+                    for format in argument.width.iter().chain(&argument.precision) {
+                        self.expect_integer(format);
+                    }
+                }
+            },
+            StatementKind::Read { targets } => {
+                for target in targets {
+                    self.element(&target.name, &target.indices);
+                }
+            },
+            StatementKind::Call { name, arguments } => {
+                self.call(name, arguments);
+            },
+            StatementKind::If { condition, then_branch, else_branch } => {
+                self.expect_condition(condition, "se");
+                self.block(then_branch);
+                if let Some(else_branch) = else_branch {
+                    self.block(else_branch);
+                }
+            },
+            StatementKind::While { condition, body } => {
+                self.expect_condition(condition, "enquanto");
+                self.block(body);
+            },
+            StatementKind::For { variable, start, end, step, body } => {
+                if let Some(symbol) = self.element(variable, &[]) {
+                    if symbol.kind() != TypeKind::Inteiro {
+                        self.diagnostics.push(
+                            Diagnostic::error(
+                                codes::NON_INTEGER_EXPRESSION,
+                                format!("variável de controle '{}' deve ser inteiro, mas é {}", variable.name, symbol.kind()),
+                                variable.span
+                            )
+                            .with_secondary(*symbol.span(), "declarada aqui")
+                        );
+                    }
+                }
+                self.expect_integer(start);
+                self.expect_integer(end);
+                if let Some(step) = step {
+                    self.expect_integer(step);
+                }
+                self.block(body);
+            },
+            StatementKind::Repeat { body, condition } => {
+                self.block(body);
+                if let Some(condition) = condition {
+                    self.expect_condition(condition, "ate");
+                }
+            },
+            StatementKind::Choose { subject, cases, default } => {
+                let subject_type = self.expression(subject);
+
+                for case in cases {
+                    for label in &case.labels {
+                        let values = match label {
+                            CaseLabel::Value(value) => vec![value],
+                            CaseLabel::Range(low, high) => vec![low, high],
+                        };
+                        for value in values {
+                            let found = self.expression(value);
+                            if let (Some(expected), Some(found)) = (subject_type, found) {
+                                if !Self::comparable(expected, found) {
+                                    self.diagnostics.push(Diagnostic::error(
+                                        codes::INCOMPATIBLE_OPERANDS,
+                                        format!("caso {} não pode ser comparado com escolha do tipo {}", found, expected),
+                                        value.span
+                                    ).with_secondary(subject.span, "expressão escolhida aqui"));
+                                }
+                            }
+                        }
+                    }
+                    self.block(&case.body);
+                }
+
+                if let Some(default) = default {
+                    self.block(default);
+                }
+            },
+            StatementKind::Return { value } => {
+                match (self.returning, value) {
+                    (Some(expected), None) => self.diagnostics.push(Diagnostic::error(
+                        codes::MISSING_RETURN,
+                        format!("retorne de funcao precisa de um valor {}", expected),
+                        statement.span
+                    )),
+                    (None, Some(value)) => self.diagnostics.push(
+                        Diagnostic::error(
+                            codes::NO_RETURN_VALUE,
+                            String::from("só uma funcao retorna valor"),
+                            value.span
+                        )
+                        .with_note("em um procedimento ou no programa principal, use retorne sem valor")
+                    ),
+                    _ => {},
+                }
+
+                let found = value.as_ref().and_then(|v| self.expression(v));
+                if let (Some(expected), Some(found), Some(value)) = (self.returning, found, value) {
+                    if !expected.accepts(found) {
+                        self.diagnostics.push(Diagnostic::error(
+                            codes::INCOMPATIBLE_ASSIGNMENT,
+                            format!("funcao retorna {}, mas o valor é {}", expected, found),
+                            value.span
+                        ));
+                    }
+                }
+            },
+            StatementKind::Break => {},
+        }
+    }
+
+    /// Resolve a variable, reporting it when undeclared.
+    fn variable(&mut self, name:&Identifier) -> Option<Symbol> {
+        let symbol = self.symbols.lookup(&name.name).cloned();
+
+        if symbol.is_none() {
+            self.diagnostics.push(
+                Diagnostic::error(
+                    codes::UNDECLARED_NAME,
+                    format!("variável '{}' não declarada", name.name),
+                    name.span
+                )
+                .with_note("variáveis devem ser declaradas na seção 'var'")
+            );
+        }
+        symbol
+    }
+
+    /// Resolve a variable or one element of an array: `indices` must be
+    /// integers, one per dimension. Arrays cannot be used whole.
+    fn element(&mut self, name:&Identifier, indices:&[Expression]) -> Option<Symbol> {
+        for index in indices {
+            self.expect_integer(index);
+        }
+        let symbol = self.variable(name)?;
+
+        if symbol.dimensions() == indices.len() {
+            return Some(symbol);
+        }
+
+        let diagnostic = if symbol.dimensions() == 0 {
+            Diagnostic::error(codes::NOT_AN_ARRAY, format!("'{}' não é um vetor", name.name), name.span)
+                .with_label("indexada aqui")
+                .with_secondary(*symbol.span(), &format!("declarada como {} aqui", symbol.kind()))
+        } else if indices.is_empty() {
+            Diagnostic::error(codes::INDEX_COUNT, format!("vetor '{}' usado sem índice", name.name), name.span)
+                .with_secondary(*symbol.span(), "declarado aqui")
+                .with_note(&format!("use {}[i] para um elemento do vetor", name.name))
+        } else {
+            Diagnostic::error(
+                codes::INDEX_COUNT,
+                format!(
+                    "'{}' tem {} dimensão(ões), mas recebeu {} índice(s)",
+                    name.name, symbol.dimensions(), indices.len()
+                ),
+                name.span
+            )
+            .with_secondary(*symbol.span(), "declarado aqui")
+        };
+        self.diagnostics.push(diagnostic);
+        None
+    }
+
+    fn expect_assignable(&mut self, symbol:&Symbol, found:TypeKind, span:Span, target:&Identifier) {
+        if symbol.kind().accepts(found) {
+            return;
+        }
+
+        self.diagnostics.push(
+            Diagnostic::error(
+                codes::INCOMPATIBLE_ASSIGNMENT,
+                format!("não é possível atribuir {} a '{}' do tipo {}", found, target.name, symbol.kind()),
+                span
+            )
+            .with_label(&format!("valor do tipo {}", found))
+            .with_secondary(*symbol.span(), &format!("declarada como {} aqui", symbol.kind()))
+        );
+    }
+
+    fn expect_condition(&mut self, condition:&Expression, statement:&str) {
+        match self.expression(condition) {
+            Some(TypeKind::Logico) | None => {},
+            Some(found) => self.diagnostics.push(
+                Diagnostic::error(
+                    codes::NON_LOGICAL_CONDITION,
+                    format!("condição de '{}' deve ser logico, encontrado {}", statement, found),
+                    condition.span
+                )
+                .with_label(&format!("valor do tipo {}", found))
+            ),
+        }
+    }
+
+    fn expect_integer(&mut self, expression:&Expression) {
+        match self.expression(expression) {
+            Some(TypeKind::Inteiro) | None => {},
+            Some(found) => self.diagnostics.push(Diagnostic::error(
+                codes::NON_INTEGER_EXPRESSION,
+                format!("valor inteiro esperado, encontrado {}", found),
+                expression.span
+            )),
+        }
+    }
+
+    /// Check a call against its signature. Returns the signature when the
+    /// callee exists so expression calls can use its return type.
+    fn call(&mut self, name:&Identifier, arguments:&[Expression]) -> Option<Signature> {
+        let Some(signature) = self.symbols.subprogram(&name.name).cloned() else {
+            for argument in arguments {
+                self.expression(argument);
+            }
+            self.diagnostics.push(Diagnostic::error(
+                codes::UNDEFINED_SUBPROGRAM,
+                format!("procedimento ou funcao '{}' não definido", name.name),
+                name.span
+            ));
+            return None;
+        };
+
+        let expected = signature.parameters();
+        if expected.len() != arguments.len() {
+            for argument in arguments {
+                self.expression(argument);
+            }
+            let mut diagnostic = Diagnostic::error(
+                codes::ARGUMENT_COUNT,
+                format!(
+                    "'{}' espera {} argumento(s), mas recebeu {}",
+                    name.name, expected.len(), arguments.len()
+                ),
+                name.span
+            );
+            if let Some(span) = signature.span() {
+                diagnostic = diagnostic.with_secondary(*span, "definido aqui");
+            }
+            self.diagnostics.push(diagnostic);
+            return Some(signature);
+        }
+
+        for (parameter, argument) in expected.iter().zip(arguments) {
+            if parameter.dimensions > 0 {
+                self.array_argument(name, parameter, argument);
+                continue;
+            }
+
+            let found = self.expression(argument);
+            if parameter.by_reference && !matches!(argument.kind, ExpressionKind::Variable(_) | ExpressionKind::Index { .. }) {
+                self.diagnostics.push(Diagnostic::error(
+                    codes::ARGUMENT_TYPE,
+                    String::from("parâmetro 'var' precisa receber uma variável"),
+                    argument.span
+                ));
+                continue;
+            }
+
+            // A reference must hold exactly the parameter type.
+            let accepted = found.is_none_or(|found| match parameter.by_reference {
+                true => found == parameter.kind,
+                false => parameter.kind.accepts(found),
+            });
+
+            if !accepted {
+                self.diagnostics.push(Diagnostic::error(
+                    codes::ARGUMENT_TYPE,
+                    format!(
+                        "argumento de '{}' deve ser {}, encontrado {}",
+                        name.name, parameter.kind, found.map_or(String::new(), |f| f.to_string())
+                    ),
+                    argument.span
+                ));
+            }
+        }
+
+        Some(signature)
+    }
+
+    /// A `vetor` parameter takes a whole array with as many dimensions,
+    /// its elements of the parameter type; a `var` one exactly that type.
+    fn array_argument(&mut self, callee:&Identifier, parameter:&ParameterType, argument:&Expression) {
+        let symbol = match &argument.kind {
+            ExpressionKind::Variable(variable) => {
+                let Some(symbol) = self.variable(variable) else {
+                    return;
+                };
+                Some(symbol)
+            },
+            _ => {
+                self.expression(argument);
+                None
+            },
+        };
+
+        let accepted = symbol.as_ref().is_some_and(|symbol| {
+            symbol.dimensions() == parameter.dimensions && match parameter.by_reference {
+                true => symbol.kind() == parameter.kind,
+                false => parameter.kind.accepts(symbol.kind()),
+            }
+        });
+        if accepted {
+            return;
+        }
+
+        let mut diagnostic = Diagnostic::error(
+            codes::ARGUMENT_TYPE,
+            format!(
+                "argumento de '{}' deve ser um vetor de {} dimensão(ões) de {}",
+                callee.name, parameter.dimensions, parameter.kind
+            ),
+            argument.span
+        )
+        .with_label("vetor esperado aqui");
+        if let Some(symbol) = symbol {
+            let declared = match symbol.dimensions() {
+                0 => format!("declarada como {} aqui", symbol.kind()),
+                dimensions => format!("declarado como vetor de {} dimensão(ões) de {} aqui", dimensions, symbol.kind()),
+            };
+            diagnostic = diagnostic.with_secondary(*symbol.span(), &declared);
+        }
+        if matches!(argument.kind, ExpressionKind::Index { .. }) {
+            diagnostic = diagnostic.with_note("passe o vetor inteiro, sem índices");
+        }
+        self.diagnostics.push(diagnostic);
+    }
+
+    /// Type of an expression, or `None` when it could not be determined
+    /// because of an error already reported.
+    fn expression(&mut self, expression:&Expression) -> Option<TypeKind> {
+        match &expression.kind {
+            ExpressionKind::Literal(literal) => Some(match literal {
+                Literal::Integer(_) => TypeKind::Inteiro,
+                Literal::Real(_) => TypeKind::Real,
+                Literal::String(_) => TypeKind::Caractere,
+                Literal::Boolean(_) => TypeKind::Logico,
+            }),
+            // A `funcao` without parameters may be called without parentheses.
+            ExpressionKind::Variable(name) if self.symbols.lookup(&name.name).is_none() && self.symbols.subprogram(&name.name).is_some() => {
+                self.call_value(name, &[], expression.span)
+            },
+            ExpressionKind::Variable(name) => self.element(name, &[]).map(|s| s.kind()),
+            ExpressionKind::Index { name, indices } => self.element(name, indices).map(|s| s.kind()),
+            ExpressionKind::Call { name, arguments } => self.call_value(name, arguments, expression.span),
+            ExpressionKind::Unary { operator, operand } => {
+                let found = self.expression(operand)?;
+
+                let result = match operator {
+                    UnaryOperator::Negate | UnaryOperator::Plus if found.is_numeric() => Some(found),
+                    UnaryOperator::Not if found == TypeKind::Logico => Some(found),
+                    _ => None,
+                };
+
+                if result.is_none() {
+                    self.diagnostics.push(Diagnostic::error(
+                        codes::INCOMPATIBLE_OPERANDS,
+                        format!("operador '{}' não se aplica a {}", operator, found),
+                        expression.span
+                    ));
+                }
+                result
+            },
+            ExpressionKind::Binary { operator, left, right } => {
+                let left = self.expression(left);
+                let right = self.expression(right);
+                let (left, right) = (left?, right?);
+
+                let result = Self::binary(*operator, left, right);
+                if result.is_none() {
+                    self.diagnostics.push(Diagnostic::error(
+                        codes::INCOMPATIBLE_OPERANDS,
+                        format!("operador '{}' não se aplica a {} e {}", operator, left, right),
+                        expression.span
+                    ));
+                }
+                result
+            },
+        }
+    }
+
+    /// Type a call used as a value returns, reporting procedures.
+    fn call_value(&mut self, name:&Identifier, arguments:&[Expression], span:Span) -> Option<TypeKind> {
+        let signature = self.call(name, arguments)?;
+
+        if signature.return_type().is_none() {
+            self.diagnostics.push(Diagnostic::error(
+                codes::NO_RETURN_VALUE,
+                format!("procedimento '{}' não retorna valor", name.name),
+                span
+            ));
+        }
+        signature.return_type()
+    }
+
+    fn binary(operator:BinaryOperator, left:TypeKind, right:TypeKind) -> Option<TypeKind> {
+        use BinaryOperator::*;
+
+        let numeric = left.is_numeric() && right.is_numeric();
+        let widened = if left == TypeKind::Real || right == TypeKind::Real { TypeKind::Real } else { TypeKind::Inteiro };
+
+        match operator {
+            Add if left == TypeKind::Caractere && right == TypeKind::Caractere => Some(TypeKind::Caractere),
+            Add | Subtract | Multiply if numeric => Some(widened),
+            Divide if numeric => Some(TypeKind::Real),
+            IntegerDivide | Modulo if left == TypeKind::Inteiro && right == TypeKind::Inteiro => Some(TypeKind::Inteiro),
+            Quotient if numeric => Some(widened),
+            Power if numeric => Some(widened),
+            Equal | NotEqual if Self::comparable(left, right) => Some(TypeKind::Logico),
+            Less | LessEqual | Greater | GreaterEqual
+                if Self::comparable(left, right) && left != TypeKind::Logico => Some(TypeKind::Logico),
+            And | Or | Xor if left == TypeKind::Logico && right == TypeKind::Logico => Some(TypeKind::Logico),
+            _ => None,
+        }
+    }
+
+    fn comparable(left:TypeKind, right:TypeKind) -> bool {
+        left == right || (left.is_numeric() && right.is_numeric())
+    }
+}
@@ -0,0 +1,2 @@
+pub mod symbols;
+pub mod checker;
\ No newline at end of file
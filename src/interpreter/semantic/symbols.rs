@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use crate::interpreter::ast::program::{Subprogram, SubprogramKind};
+use crate::interpreter::ast::types::TypeKind;
+use crate::interpreter::tokens::span::Span;
+
+/// A declared variable or parameter.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Symbol {
+    name: String,
+    kind: TypeKind,
+    dimensions: usize,
+    span: Span,
+}
+
+impl Symbol {
+    pub fn new(name:String, kind:TypeKind, span:Span) -> Self {
+        Self { name, kind, dimensions: 0, span }
+    }
+
+    /// Make the symbol an array indexed by `dimensions` indices.
+    pub fn with_dimensions(mut self, dimensions:usize) -> Self {
+        self.dimensions = dimensions;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Type of the value, or of each element of an array.
+    pub fn kind(&self) -> TypeKind {
+        self.kind
+    }
+
+    /// Number of indices an element takes, zero when not an array.
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// Where the symbol was declared.
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParameterType {
+    /// Type of the value, or of each element of a `vetor`.
+    pub kind: TypeKind,
+    pub by_reference: bool,
+    /// Number of indices of a `vetor` parameter, zero for a single value.
+    pub dimensions: usize,
+}
+
+/// What a call site needs to know about a `procedimento` or `funcao`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Signature {
+    name: String,
+    parameters: Vec<ParameterType>,
+    return_type: Option<TypeKind>,
+    span: Option<Span>,
+}
+
+impl Signature {
+    pub fn new(name:&str, parameters:Vec<ParameterType>, return_type:Option<TypeKind>) -> Self {
+        Self { name: name.to_string(), parameters, return_type, span: None }
+    }
+
+    pub fn from_subprogram(subprogram:&Subprogram) -> Self {
+        let parameters = subprogram.parameters.iter()
+            .map(|p| ParameterType {
+                kind: p.type_spec.kind,
+                by_reference: p.by_reference,
+                dimensions: p.type_spec.dimensions.len(),
+            })
+            .collect();
+        let return_type = match subprogram.kind {
+            SubprogramKind::Funcao => subprogram.return_type.as_ref().map(|t| t.kind),
+            SubprogramKind::Procedimento => None,
+        };
+
+        Self {
+            name: subprogram.name.name.clone(),
+            parameters,
+            return_type,
+            span: Some(subprogram.name.span),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn parameters(&self) -> &Vec<ParameterType> {
+        &self.parameters
+    }
+
+    /// `None` for procedures.
+    pub fn return_type(&self) -> Option<TypeKind> {
+        self.return_type
+    }
+
+    /// Declaration site, `None` for built-ins.
+    pub fn span(&self) -> Option<&Span> {
+        self.span.as_ref()
+    }
+}
+
+/// Nested variable scopes over a flat table of subprograms. The first scope
+/// holds the globals; each subprogram body pushes its own. Names are looked
+/// up by [`SymbolTable::key`], so a case-insensitive table finds `TOTAL`
+/// when `total` was declared.
+pub struct SymbolTable {
+    scopes: Vec<HashMap<String, Symbol>>,
+    subprograms: HashMap<String, Signature>,
+    case_sensitive: bool,
+}
+
+impl SymbolTable {
+    pub fn new(case_sensitive:bool) -> Self {
+        Self { scopes: vec![HashMap::new()], subprograms: HashMap::new(), case_sensitive }
+    }
+
+    pub fn key(&self, name:&str) -> String {
+        if self.case_sensitive {
+            name.to_string()
+        } else {
+            name.to_lowercase()
+        }
+    }
+
+    pub fn push_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    /// The global scope is never popped.
+    pub fn pop_scope(&mut self) {
+        if self.scopes.len() > 1 {
+            self.scopes.pop();
+        }
+    }
+
+    /// Declare in the innermost scope. On a clash the earlier symbol is kept
+    /// and returned.
+    pub fn declare(&mut self, symbol:Symbol) -> Result<(), &Symbol> {
+        let key = self.key(&symbol.name);
+        let scope = self.scopes.last_mut().expect("global scope is never popped");
+
+        if scope.contains_key(&key) {
+            return Err(&scope[&key]);
+        }
+        scope.insert(key, symbol);
+        Ok(())
+    }
+
+    /// Innermost symbol called `name`.
+    pub fn lookup(&self, name:&str) -> Option<&Symbol> {
+        let key = self.key(name);
+        self.scopes.iter().rev().find_map(|scope| scope.get(&key))
+    }
+
+    pub fn declare_subprogram(&mut self, signature:Signature) -> Result<(), &Signature> {
+        let key = self.key(&signature.name);
+
+        if self.subprograms.contains_key(&key) {
+            return Err(&self.subprograms[&key]);
+        }
+        self.subprograms.insert(key, signature);
+        Ok(())
+    }
+
+    pub fn subprogram(&self, name:&str) -> Option<&Signature> {
+        self.subprograms.get(&self.key(name))
+    }
+}
@@ -1,15 +1,417 @@
-use super::{rules::rule::SyntaxRule, tokenizer::Tokenizer};
+use std::collections::{HashMap, HashSet};
 
-pub struct Syntaxer {
-    tokenizer:Tokenizer,
+use super::ast::expression::Expression;
+use super::ast::program::Program;
+use super::diagnostics::codes;
+use super::diagnostics::diagnostic::Diagnostic;
+use super::parser::cursor::{Lexeme, LexemeKind, TokenCursor};
+use super::rules::node::SyntaxNode;
+use super::rules::rule::{SyntaxGroup, SyntaxRule, SyntaxType};
+use super::tokenizer::Tokenizer;
+use super::tokens::span::Span;
+
+pub struct Syntaxer<'a> {
+    tokenizer:&'a Tokenizer,
 }
 
-impl Syntaxer {
-    pub fn new(tokenizer:Tokenizer) -> Self {
+impl<'a> Syntaxer<'a> {
+    pub fn new(tokenizer:&'a Tokenizer) -> Self {
         Self { tokenizer }
     }
 
-    pub fn validate_syntax(&self, rules:Vec<SyntaxRule>) {
-        
+    pub fn parse(&self) -> Result<Program, Vec<Diagnostic>> {
+        self.lexical_errors()?;
+        self.tokenizer.language().parse(self.tokenizer.tokens())
+    }
+
+    /// Parse the tokens as a lone expression of the language.
+    pub fn parse_expression(&self) -> Result<Expression, Vec<Diagnostic>> {
+        self.lexical_errors()?;
+        self.tokenizer.language().parse_expression(self.tokenizer.tokens())
+    }
+
+    /// Tokenizer errors make the token stream unreliable, so they are
+    /// reported before any parsing is attempted.
+    fn lexical_errors(&self) -> Result<(), Vec<Diagnostic>> {
+        let errors:Vec<Diagnostic> = self.tokenizer.diagnostics().iter()
+            .filter(|d| d.is_error())
+            .cloned()
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Match the tokens against a grammar, starting at its first group.
+    pub fn validate_syntax(&self, grammar:&[SyntaxGroup]) -> Result<SyntaxNode, Vec<Diagnostic>> {
+        self.lexical_errors()?;
+        let cursor = TokenCursor::new(self.tokenizer.tokens());
+
+        let Some(entry) = grammar.first() else {
+            return Err(vec![
+                Diagnostic::error(codes::EMPTY_GRAMMAR, String::from("gramática vazia"), cursor.span())
+            ]);
+        };
+
+        let case_sensitive = self.tokenizer.language().is_case_sensitive();
+        let mut matcher = GrammarMatcher::new(grammar, cursor, case_sensitive);
+        let tree = matcher.group(entry);
+
+        let mut errors = std::mem::take(&mut matcher.errors);
+        match tree {
+            Some(tree) if matcher.cursor.is_at_end() && errors.is_empty() => Ok(tree),
+            // Past the nesting limit, where matching stopped says nothing.
+            _ if errors.iter().any(|e| e.code() == codes::NESTING_TOO_DEEP) => Err(errors),
+            _ => {
+                errors.push(matcher.failure());
+                Err(errors)
+            }
+        }
+    }
+}
+
+/// Backtracking matcher for [`SyntaxGroup`] grammars. Alternatives are
+/// ordered and repetition is greedy, so grammars must not be left recursive.
+struct GrammarMatcher<'a> {
+    groups:HashMap<&'a str, &'a SyntaxGroup>,
+    /// Keyed by [`GrammarMatcher::fold`].
+    keywords:HashSet<String>,
+    case_sensitive:bool,
+    cursor:TokenCursor,
+    /// Groups being matched at a position, to stop left recursion.
+    active:HashSet<(&'a str, usize)>,
+    /// Positions of the groups that nest deeper, guarded by
+    /// [`TokenCursor::enter`].
+    nested:Vec<usize>,
+    furthest:usize,
+    expected:Vec<String>,
+    errors:Vec<Diagnostic>,
+}
+
+impl<'a> GrammarMatcher<'a> {
+    fn new(grammar:&'a [SyntaxGroup], cursor:TokenCursor, case_sensitive:bool) -> Self {
+        let mut keywords = HashSet::new();
+        for group in grammar {
+            for rule in group.rules() {
+                Self::collect_keywords(rule, &mut keywords);
+            }
+        }
+        let keywords = keywords.iter().map(|k| Self::fold(k, case_sensitive)).collect();
+
+        Self {
+            groups: grammar.iter().map(|g| (g.name(), g)).collect(),
+            keywords,
+            case_sensitive,
+            cursor,
+            active: HashSet::new(),
+            nested: vec![],
+            furthest: 0,
+            expected: vec![],
+            errors: vec![],
+        }
+    }
+
+    fn collect_keywords(rule:&SyntaxRule, keywords:&mut HashSet<String>) {
+        let is_word = |key:&String| key.starts_with(|c:char| c.is_alphabetic());
+
+        match rule.syntax() {
+            SyntaxType::Keyword { key } if is_word(key) => {
+                keywords.insert(key.clone());
+            },
+            SyntaxType::OneOf { keys } => {
+                keywords.extend(keys.iter().filter(|k| is_word(k)).cloned());
+            },
+            SyntaxType::WrappedBy { inner, .. } => Self::collect_keywords(inner, keywords),
+            SyntaxType::Sequence { rules } | SyntaxType::Choice { rules } => {
+                for rule in rules {
+                    Self::collect_keywords(rule, keywords);
+                }
+            },
+            _ => {}
+        }
+    }
+
+    fn fold(text:&str, case_sensitive:bool) -> String {
+        if case_sensitive {
+            text.to_string()
+        } else {
+            text.to_lowercase()
+        }
+    }
+
+    /// Whether a lexeme spells `key`, ignoring case unless the language
+    /// says otherwise.
+    fn spells(text:&str, key:&str, case_sensitive:bool) -> bool {
+        if case_sensitive {
+            text == key
+        } else {
+            text.to_lowercase() == key.to_lowercase()
+        }
+    }
+
+    fn group(&mut self, group:&'a SyntaxGroup) -> Option<SyntaxNode> {
+        let start = self.cursor.position();
+        let key = (group.name(), start);
+
+        if !self.active.insert(key) {
+            return None;
+        }
+
+        // Groups at the position of the one around them are bounded by the
+        // grammar; only those past it nest the code any deeper.
+        let nests = self.nested.last().is_none_or(|&outer| start > outer);
+        if nests {
+            if let Err(error) = self.cursor.enter() {
+                self.active.remove(&key);
+                if !self.errors.contains(&error) {
+                    self.errors.push(error);
+                }
+                return None;
+            }
+            self.nested.push(start);
+        }
+
+        let children = self.sequence(group.rules());
+        self.active.remove(&key);
+        if nests {
+            self.nested.pop();
+            self.cursor.leave();
+        }
+
+        let children = children?;
+        let span = Self::span_of(&children).unwrap_or_else(|| {
+            let here = self.cursor.span().start();
+            Span::new(here, here)
+        });
+
+        Some(SyntaxNode::Node { name: group.name().to_string(), children, span })
+    }
+
+    fn sequence(&mut self, rules:&'a [SyntaxRule]) -> Option<Vec<SyntaxNode>> {
+        let start = self.cursor.position();
+        let mut children = vec![];
+
+        for rule in rules {
+            match self.rule(rule) {
+                Some(mut matched) => children.append(&mut matched),
+                None => {
+                    self.cursor.rewind(start);
+                    return None;
+                }
+            }
+        }
+
+        Some(children)
+    }
+
+    fn rule(&mut self, rule:&'a SyntaxRule) -> Option<Vec<SyntaxNode>> {
+        if !rule.is_repeated() {
+            return match self.once(rule.syntax()) {
+                None if rule.is_optional() => Some(vec![]),
+                matched => matched,
+            };
+        }
+
+        let mut children = vec![];
+        let mut count = 0;
+
+        loop {
+            let before = self.cursor.position();
+
+            if count > 0 {
+                if let Some(separator) = rule.separator() {
+                    let case_sensitive = self.case_sensitive;
+                    match self.terminal(&format!("'{}'", separator), |l| Self::spells(l.text(), separator, case_sensitive)) {
+                        Some(leaf) => children.push(leaf),
+                        None => break,
+                    }
+                }
+            }
+
+            match self.once(rule.syntax()) {
+                Some(mut matched) if self.cursor.position() > before => {
+                    children.append(&mut matched);
+                    count += 1;
+                },
+                _ => {
+                    self.cursor.rewind(before);
+                    if count > 0 && rule.separator().is_some() {
+                        children.pop();
+                    }
+                    break;
+                }
+            }
+        }
+
+        if count == 0 && !rule.is_optional() {
+            return None;
+        }
+        Some(children)
+    }
+
+    fn once(&mut self, syntax:&'a SyntaxType) -> Option<Vec<SyntaxNode>> {
+        match syntax {
+            SyntaxType::Keyword { key } => self.keyword(key),
+            SyntaxType::OneOf { keys } => self.one_of(keys),
+            SyntaxType::Numeric { accepts_float, accepts_negative } => self.numeric(*accepts_float, *accepts_negative),
+            SyntaxType::Text => {
+                self.terminal("cadeia de caracteres", |l| matches!(l.kind(), LexemeKind::Text(_))).map(|l| vec![l])
+            },
+            SyntaxType::Identifier => self.identifier(),
+            SyntaxType::Token { schema } => {
+                self.terminal(&schema.to_string(), |l| l.schema() == schema).map(|l| vec![l])
+            },
+            SyntaxType::WrappedBy { open, close, inner } => self.wrapped(open, close, inner),
+            SyntaxType::Sequence { rules } => self.sequence(rules),
+            SyntaxType::Choice { rules } => rules.iter().find_map(|rule| self.rule(rule)),
+            SyntaxType::Reference { name } => self.reference(name),
+        }
+    }
+
+    // The cases of `once` live apart so the frame it keeps on the stack for
+    // each nested group stays small.
+
+    fn keyword(&mut self, key:&str) -> Option<Vec<SyntaxNode>> {
+        let case_sensitive = self.case_sensitive;
+        self.terminal(&format!("'{}'", key), |l| Self::spells(l.text(), key, case_sensitive)).map(|l| vec![l])
+    }
+
+    fn one_of(&mut self, keys:&[String]) -> Option<Vec<SyntaxNode>> {
+        let description = keys.iter().map(|k| format!("'{}'", k)).collect::<Vec<_>>().join(" ou ");
+        let case_sensitive = self.case_sensitive;
+        self.terminal(&description, |l| keys.iter().any(|k| Self::spells(l.text(), k, case_sensitive))).map(|l| vec![l])
+    }
+
+    fn numeric(&mut self, accepts_float:bool, accepts_negative:bool) -> Option<Vec<SyntaxNode>> {
+        let start = self.cursor.position();
+        let mut leaves = vec![];
+
+        if accepts_negative {
+            if let Some(sign) = self.terminal("'-'", |l| l.is_symbol("-")) {
+                leaves.push(sign);
+            }
+        }
+
+        let number = self.terminal("número", |l| match l.kind() {
+            LexemeKind::Integer(_) => true,
+            LexemeKind::Real(_) => accepts_float,
+            _ => false,
+        });
+
+        match number {
+            Some(number) => {
+                leaves.push(number);
+                Some(leaves)
+            },
+            None => {
+                self.cursor.rewind(start);
+                None
+            }
+        }
+    }
+
+    fn identifier(&mut self) -> Option<Vec<SyntaxNode>> {
+        let keywords = &self.keywords;
+        let case_sensitive = self.case_sensitive;
+        let is_identifier = |l:&Lexeme| {
+            l.kind() == &LexemeKind::Word && !keywords.contains(&Self::fold(l.text(), case_sensitive))
+        };
+        let matches = self.cursor.peek().is_some_and(is_identifier);
+        self.leaf_if(matches, "identificador").map(|l| vec![l])
     }
-}
\ No newline at end of file
+
+    fn wrapped(&mut self, open:&str, close:&str, inner:&'a SyntaxRule) -> Option<Vec<SyntaxNode>> {
+        let start = self.cursor.position();
+        let mut children = vec![];
+
+        let matched = self.terminal(&format!("'{}'", open), |l| l.text() == open)
+            .and_then(|leaf| {
+                children.push(leaf);
+                self.rule(inner)
+            })
+            .and_then(|mut nodes| {
+                children.append(&mut nodes);
+                self.terminal(&format!("'{}'", close), |l| l.text() == close)
+            });
+
+        match matched {
+            Some(leaf) => {
+                children.push(leaf);
+                Some(children)
+            },
+            None => {
+                self.cursor.rewind(start);
+                None
+            }
+        }
+    }
+
+    fn reference(&mut self, name:&str) -> Option<Vec<SyntaxNode>> {
+        match self.groups.get(name) {
+            Some(group) => self.group(group).map(|node| vec![node]),
+            None => {
+                let error = Diagnostic::error(
+                    codes::UNDEFINED_RULE,
+                    format!("regra '{}' não definida na gramática", name),
+                    self.cursor.span()
+                );
+                if !self.errors.contains(&error) {
+                    self.errors.push(error);
+                }
+                None
+            }
+        }
+    }
+
+    fn terminal(&mut self, expected:&str, test:impl Fn(&Lexeme) -> bool) -> Option<SyntaxNode> {
+        let matches = self.cursor.peek().is_some_and(test);
+        self.leaf_if(matches, expected)
+    }
+
+    /// Consume the next lexeme as a leaf, or record what was expected at the
+    /// furthest position reached so far.
+    fn leaf_if(&mut self, matches:bool, expected:&str) -> Option<SyntaxNode> {
+        if matches {
+            let lexeme = self.cursor.advance()?;
+            return Some(SyntaxNode::Leaf { text: lexeme.text().to_string(), span: lexeme.span() });
+        }
+
+        let position = self.cursor.position();
+        if position > self.furthest {
+            self.furthest = position;
+            self.expected.clear();
+        }
+        if position == self.furthest && !self.expected.iter().any(|e| e == expected) {
+            self.expected.push(expected.to_string());
+        }
+        None
+    }
+
+    fn failure(&mut self) -> Diagnostic {
+        if self.cursor.position() > self.furthest || self.expected.is_empty() {
+            self.furthest = self.cursor.position();
+            self.expected = vec![String::from("fim do arquivo")];
+        }
+
+        self.cursor.rewind(self.furthest);
+        let found = match self.cursor.peek() {
+            Some(lexeme) => format!("'{}'", lexeme.text()),
+            None => String::from("fim do arquivo"),
+        };
+
+        Diagnostic::error(
+            codes::UNEXPECTED_TOKEN,
+            format!("esperado {}, encontrado {}", self.expected.join(" ou "), found),
+            self.cursor.span()
+        ).with_label("inesperado")
+    }
+
+    fn span_of(children:&[SyntaxNode]) -> Option<Span> {
+        let first = children.first()?.span();
+        let last = children.last()?.span();
+        Some(first.join(&last))
+    }
+}
@@ -1,431 +1,462 @@
-use eframe::glow::TEXTURE_VIEW_MIN_LEVEL;
-use egui::util::undoer::Settings;
-
+use super::ast::expression::Literal;
+use super::diagnostics::codes;
+use super::diagnostics::diagnostic::Diagnostic;
+use super::tokens::edit::{TextEdit, TokenChange};
 use super::tokens::schema::TokenTypes;
-use super::{language::Language, tokens::token::{Token, TokenIndex}};
-use super::tokens::separator::{SeparatorSetting, TokenSeparators};
-
-struct Range<T> {
-    min:T,
-    max:T,
-}
-
-impl<T: PartialOrd + Clone> Range<T> {
-    fn new(min:T, max:T) -> Self {
-        Self { min, max }
-    }
-
-    fn min(&mut self, min:T) {
-        if min < self.min {
-            self.min = min;
-        }
-    }
-    fn max(&mut self, max:T) {
-        if max > self.max {
-            self.max = max;
-        }
-    }
-    fn range(&mut self, value:T) {
-        self.max(value.clone());
-        self.min(value);
-    }
-}
+use super::tokens::span::SourceIndex;
+use super::tokens::trie::{Closer, SeparatorTrie};
+use super::{language::Language, tokens::token::Token};
 
+/// Single pass, longest-match scanner over the separators of a
+/// [`Language`]. Text no separator matches becomes `unknown` runs, which
+/// [`Tokenizer::tokenize`] then types as literals and identifiers.
 pub struct Tokenizer {
     language:Box<dyn Language>,
+    separators:SeparatorTrie,
+    escape:Option<char>,
     tokens:Vec<Token>,
-    index:usize,
-    search_token:Option<TokenizerSearch>
+    code:String,
+    chars:Vec<char>,
+    source:SourceIndex,
+    diagnostics:Vec<Diagnostic>,
 }
 
-struct TokenizerSearch {
-    token:Separator,
-    skip_content:bool,
-}
-
-#[derive(Clone)]
-enum Separator {
-    Simple {
-        value:String,
-        settings:SeparatorSetting,
-    },
-
-    WithEnd {
-        value:String,
-        end:Option<String>,
-        settings:SeparatorSetting,
-    }
+/// How a delimited run ended.
+enum Delimited {
+    /// Closed by the chars in `start..end`.
+    Closed { start:usize, end:usize },
+    /// Still open at the end of the lexed range. `innermost` is the char
+    /// offset of the deepest nested opener left unclosed, if any.
+    Open { innermost:Option<usize> },
 }
 
 impl Tokenizer {
     pub fn new(language:Box<dyn Language>) -> Self {
-        Self { language, tokens: vec![], index: 0, search_token: None }
+        let separators = SeparatorTrie::new(language.separators(), language.is_case_sensitive());
+        let escape = language.escape().chars().next();
+
+        Self {
+            language,
+            separators,
+            escape,
+            tokens: vec![],
+            code: String::new(),
+            chars: vec![],
+            source: SourceIndex::new(""),
+            diagnostics: vec![],
+        }
     }
 
-    fn get_max_range(&self, separators:&Vec<Separator>) -> Range<usize> {
-        if separators.len() == 0 {
-            return Range::new(0, 0);
-        }
-        
-        let mut min = 0;
-        let mut max = 0;
-
-        let min_fn = |a:&Separator| -> usize {
-            match a {
-                Separator::Simple { value, .. } => value.chars().count(),
-                Separator::WithEnd { value, end, .. } => {
-                    let v = value.chars().count();
-                    let e = end.as_ref().map_or(v, |e| e.chars().count());
-                    v.min(e)
-                },
-            }
-        };
+    pub fn language(&self) -> &dyn Language {
+        self.language.as_ref()
+    }
 
-        let max_fn = |a:&Separator| -> usize {
-            match a {
-                Separator::Simple { value, .. } => value.chars().count(),
-                Separator::WithEnd { value, end, .. } => {
-                    let v = value.chars().count();
-                    let e = end.as_ref().map_or(v, |e| e.chars().count());
-                    v.max(e)
-                },
-            }
-        };
+    pub fn tokens(&self) -> &Vec<Token> {
+        &self.tokens
+    }
 
-        if let Some(_min) = separators.iter().min_by_key(|a| min_fn(a)) {
-            min = min_fn(_min);
-        }
+    /// Problems found by the last [`Tokenizer::tokenize`] call.
+    pub fn diagnostics(&self) -> &Vec<Diagnostic> {
+        &self.diagnostics
+    }
 
-        if let Some(_max) = separators.iter().max_by_key(|a| max_fn(a)) {
-            max = max_fn(_max);
-        }
+    pub fn tokenize(&mut self, code:&str) {
+        self.diagnostics = vec![];
+        self.code = code.to_string();
+        self.chars = code.chars().collect();
+        self.source = SourceIndex::new(code);
 
-        Range::new(min, max)
+        self.tokens = self.lex_range(0, self.chars.len()).unwrap_or_default();
     }
 
-    fn get_ordered_separators(&self) -> Vec<Separator> {
-        use TokenSeparators::*;
-
-        let separators = self.language.separators();
-
-        let mut new_separators:Vec<Separator> = vec![];
-
-        for s in separators {
-            match s {
-                Alpha { alpha, settings }
-                    => new_separators.push(
-                        Separator::Simple {
-                            value: alpha.to_string(),
-                            settings: settings
-                        }
-                    ),
-                InAlphaRange { alphas, settings } => {
-                    for a in alphas {
-                        new_separators.push(
-                            Separator::Simple {
-                                value: a.to_string(),
-                                settings: settings.clone()
-                            }
-                        )
-                    }
-                },
-                AlphaUntil { alpha, end, skip_content, settings } => {
-                    new_separators.push(
-                        Separator::WithEnd {
-                            value: alpha.to_string(),
-                            end: end.map(|c| c.to_string()),
-                            settings: settings
-                        }
-                    )
-                }
+    /// Apply `edit` to the code from the last [`Tokenizer::tokenize`] and
+    /// re-lex only what it touched. Lexing restarts at the line break before
+    /// the edit and stops at the first line start after it where the new
+    /// tokens line up with the old ones; a delimiter opened by the edit
+    /// pushes that point further, up to the end of the code.
+    pub fn edit(&mut self, edit:&TextEdit) -> TokenChange {
+        let length = self.source.len();
+        let start = edit.start().min(length);
+        let end = edit.end().clamp(start, length);
+        let delta = edit.text().chars().count() as isize - (end - start) as isize;
+        let shift = |offset:usize| offset.saturating_add_signed(delta);
+
+        let old = std::mem::take(&mut self.tokens);
+        let first = old.iter()
+            .rposition(|t| Self::is_line_break(t) && t.span().start().char() < start)
+            .unwrap_or(0);
+        let restart = old.get(first).map_or(0, |t| t.span().start().char());
+
+        edit.apply(&mut self.code);
+        self.chars = self.code.chars().collect();
+        self.source = SourceIndex::new(&self.code);
+
+        // Unterminated delimiters run to the end of the code, so their
+        // diagnostics can only be redone by lexing that far.
+        let can_resync = self.diagnostics.is_empty();
+        let mut minimum = end;
+        loop {
+            let resync = (first + 1..old.len()).filter(|_| can_resync).find(|&i| {
+                Self::is_line_break(&old[i - 1])
+                    && !Self::is_line_break(&old[i])
+                    && old[i].span().start().char() >= minimum
+            });
+            let stop = resync.map_or(self.source.len(), |i| shift(old[i].span().start().char()));
+
+            if resync.is_none() {
+                self.diagnostics.clear();
+            }
 
-                Word { word, settings } => new_separators.push(
-                    Separator::Simple {
-                        value: word,
-                        settings: settings
-                    }
-                ),
-                InWordRange { words, settings } => {
-                    for w in words {
-                        new_separators.push(
-                            Separator::Simple {
-                                value: w,
-                                settings: settings.clone()
-                            }
-                        );
+            let lined_up = |tokens:&Vec<Token>| resync.is_none() || tokens.last().is_some_and(|t| {
+                Self::is_line_break(t) && t.span().end().char() == stop
+            });
+
+            match self.lex_range(restart, stop) {
+                Some(tokens) if lined_up(&tokens) => {
+                    let resync = resync.unwrap_or(old.len());
+                    let change = TokenChange::new(first..resync, first..first + tokens.len());
+
+                    let mut old = old.into_iter();
+                    self.tokens = old.by_ref().take(first).collect();
+                    self.tokens.extend(tokens);
+                    for mut token in old.skip(resync - first) {
+                        let span = self.source.span(shift(token.span().start().char()), shift(token.span().end().char()));
+                        token.set_span(span);
+                        self.tokens.push(token);
                     }
+                    return change;
+                },
+                // Still inside a delimiter: try twice as far.
+                _ => {
+                    let resync = resync.expect("lexing to the end always completes");
+                    let reached = old[resync].span().start().char();
+                    minimum = reached + 1 + (reached - restart);
                 }
-                WordUntil { word, end, skip_content, settings } => new_separators.push(
-                    Separator::WithEnd {
-                        value: word,
-                        end: end,
-                        settings: settings
-                    }
-                )
             }
         }
-
-        new_separators.sort_by(|a, b| {
-                let b_value = match b {
-                    Separator::Simple { value, .. } => value.chars().count(),
-                    Separator::WithEnd { value, end, .. } => {
-                        let v = value.chars().count();
-                        let e = end.as_ref().map_or(v, |e| e.chars().count());
-                        v.max(e)
-                    },
-                };
-                let a_value = match a {
-                    Separator::Simple { value, .. } => value.chars().count(),
-                    Separator::WithEnd { value, end, .. } => {
-                        let v = value.chars().count();
-                        let e = end.as_ref().map_or(v, |e| e.chars().count());
-                        v.max(e)
-                    },
-                };
-                b_value.cmp(&a_value)
-            }
-        );
-
-        new_separators
     }
 
-    pub fn tokenize(&mut self, code:&str) {
-        self.tokens = vec![];
-        self.index = 0;
-        
-        let separators = self.get_ordered_separators();
+    fn is_line_break(token:&Token) -> bool {
+        token.value() == "\n"
+    }
 
-        let buffer_range = self.get_max_range(&separators);
+    /// Tokens of the chars in `start..end`, lexed from outside any
+    /// delimiter. `None` when a delimiter is still open at `end` and `end`
+    /// is not the end of the code; at the end it is reported instead.
+    fn lex_range(&mut self, start:usize, end:usize) -> Option<Vec<Token>> {
+        let previous = std::mem::take(&mut self.tokens);
+        let mut position = start;
+        let mut unknown = None;
+
+        while position < end {
+            let Some((index, length)) = self.separator_at(position, end) else {
+                unknown.get_or_insert(position);
+                position += 1;
+                continue;
+            };
+            self.push_unknown(&mut unknown, position);
+
+            let separator = self.separators.separator(index);
+            if separator.closer().is_none() {
+                if separator.settings().is_inclusive() {
+                    let token = Token::new(
+                        self.source.span(position, position + length),
+                        self.text(position, position + length),
+                        separator.settings().map().clone()
+                    );
+                    self.push_token(token, true);
+                }
+                position += length;
+                continue;
+            }
 
-        let mut buffer = String::new();
-        let mut can_count = false;
-        
-        for (i, char) in code.char_indices() {
-            self.index = i;
+            // Delimited runs become a single token, see `collapse`.
 
-            let char_text = char.to_string();
-            buffer += &char_text;
+            match self.find_closer(index, position + length, end) {
+                Delimited::Closed { start: closer, end: closed } => {
+                    // A line break closing a line comment is not part of it.
+                    let line_end = matches!(self.separators.separator(index).closer(), Some(Closer::LineEnd));
+                    let closed = if line_end { closer } else { closed };
 
-            if buffer.chars().count() < buffer_range.max {
-                continue;
+                    self.collapse(index, position, closed);
+                    position = closed;
+                },
+                Delimited::Open { .. } if end < self.chars.len() => {
+                    self.tokens = previous;
+                    return None;
+                },
+                Delimited::Open { innermost } => {
+                    self.report_unterminated(index, position, length, innermost);
+                    position = end;
+                }
             }
+        }
+        self.push_unknown(&mut unknown, end);
 
-            let awaiting_token = self.search_token.is_some();
-            let skip_content = self.search_token.as_ref().map_or(false, |s| s.skip_content);
+        self.classify_literals();
+        Some(std::mem::replace(&mut self.tokens, previous))
+    }
 
-            self.bufferize(&mut buffer, &separators);
+    /// Longest separator at the `position` char offset that fits before
+    /// `end`, as its index and length.
+    fn separator_at(&self, position:usize, end:usize) -> Option<(usize, usize)> {
+        self.separators.longest(&self.chars[position..end], |separator, length| {
+            !separator.settings().is_whole_word() || self.is_word_boundary(position, length)
+        })
+    }
 
-            if buffer.chars().count() >= buffer_range.max {
-                let mut chars = buffer.chars();
-                if let Some(a_char) = chars.next() {
-                    println!("a: {}", a_char);
-                    if !awaiting_token || !skip_content {
-                        println!("\tpushed");
-                        self.push_unknown_character(a_char, chars.clone().count(), awaiting_token);
-                    } else {
-                        println!("\tskipped {} - {}", awaiting_token, skip_content);
-                    }
+    /// Where the run opened by separator `opener` ends, looking from the
+    /// `from` char offset up to `end`. Inside strings the escape char makes
+    /// the next char content, even when it is the closer. Nestable runs
+    /// only end once every opener found inside them is closed.
+    fn find_closer(&self, opener:usize, from:usize, end:usize) -> Delimited {
+        let separator = self.separators.separator(opener);
+        let closer:Vec<char> = match separator.closer() {
+            Some(Closer::Text(text)) => text.chars().collect(),
+            _ => vec!['\n'],
+        };
+        let nested:Vec<char> = separator.value().chars().collect();
+        let nestable = separator.settings().is_nestable() && closer != nested;
+        let exact = separator.settings().is_case_sensitive();
+        let whole_word = separator.settings().is_whole_word();
+        let is_string = separator.settings().map() == &TokenTypes::String;
+
+        let matches = |position:usize, word:&[char]| {
+            position + word.len() <= end
+                && word.iter().enumerate().all(|(i, &c)| {
+                    let found = self.chars[position + i];
+                    if exact { found == c } else { self.separators.same(found, c) }
+                })
+                && (!whole_word || self.is_word_boundary(position, word.len()))
+        };
+
+        let mut openers = vec![];
+        let mut escaped = false;
+        let mut position = from;
+        while position < end {
+            if !escaped && matches(position, &closer) {
+                match openers.pop() {
+                    Some(_) => {
+                        position += closer.len();
+                        continue;
+                    },
+                    None => return Delimited::Closed { start: position, end: position + closer.len() },
                 }
-                buffer = chars.collect();
             }
-        }
 
-        let mut loop_index = 0;
-        while loop_index < buffer.chars().count() {
-            let skip = self.bufferize(&mut buffer, &separators);
+            if !escaped && nestable && matches(position, &nested) {
+                openers.push(position);
+                position += nested.len();
+                continue;
+            }
 
-            if skip > 0 {
-                loop_index += skip;
-            } else {
-                loop_index += 1;
+            if is_string {
+                escaped = !escaped && Some(self.chars[position]) == self.escape;
             }
+            position += 1;
         }
+
+        Delimited::Open { innermost: openers.last().copied() }
     }
 
-    fn bufferize(&mut self, buffer:&mut String, separators:&Vec<Separator>) -> usize {
-        let skip_chars = self.tokenize_buffer_piece(buffer.clone(), &separators);
+    /// A run missing its closer at the end of the file still becomes a
+    /// single token up to there. Line comments are closed by the end of
+    /// the file; other delimiters are reported at their opening position.
+    fn report_unterminated(&mut self, opener:usize, start:usize, length:usize, innermost:Option<usize>) {
+        let end = self.chars.len();
+        self.collapse(opener, start, end);
 
-        if skip_chars > 0 {
-            let mut chars = buffer.chars();
+        let separator = self.separators.separator(opener);
+        let Some(Closer::Text(closer)) = separator.closer().cloned() else {
+            return;
+        };
 
-            for _ in 0..skip_chars {
-                chars.next();
-            }
+        let value = separator.value().to_string();
+        let opening = self.source.span(start, start + length);
+        let eof = self.source.span(end, end);
+
+        let mut diagnostic = Diagnostic::error(
+            codes::UNTERMINATED_DELIMITER,
+            format!("'{}' aberto e nunca fechado", value),
+            opening
+        )
+        .with_label("aberto aqui");
 
-            *buffer = chars.collect();
+        if let Some(nested) = innermost {
+            let span = self.source.span(nested, nested + length);
+            diagnostic = diagnostic.with_secondary(span, "aninhado aqui, também sem fechamento");
         }
 
-        skip_chars
+        self.diagnostics.push(diagnostic.with_suggestion(&format!("feche com '{}'", closer), eof, &closer));
     }
 
-    fn tokenize_buffer_piece(&mut self, buffer:String, separators:&Vec<Separator>) -> usize {
-        for separator in separators {
-            let mut compare = buffer.clone();
-            let mut is_wrapper = false;
-            let mut sep_settings:Option<SeparatorSetting> = None;
-            let temp_value:String = if let Some(search) = &self.search_token {
-                match &search.token {
-                    Separator::WithEnd { value, end, settings } => {
-                        sep_settings = Some(settings.clone());
-                        is_wrapper = true;
-                        match end {
-                            Some(v) => v.clone(),
-                            None => "\n".to_string()
-                        }
-                    },
-                    Separator::Simple { value, settings } => {
-                        sep_settings = Some(settings.clone());
-                        value.to_string()
-                    }
-                }
-            } else {
-                match separator {
-                    Separator::Simple { value, settings } => {
-                        sep_settings = Some(settings.clone());
-                        value.clone()
-                    },
-                    Separator::WithEnd { value, end, settings } => {
-                        sep_settings = Some(settings.clone());
-                        is_wrapper = true;
-                        if self.search_token.is_none() {
-                            value.clone()
-                        } else {
-                            end.clone().unwrap_or_else(|| "\n".to_string())
-                        }
-                    },
-                }
-            };
-            let value = temp_value.clone();
-            
-            if !sep_settings.clone().map_or(false, |s| s.is_case_sensitive()) {
-                compare = compare.to_lowercase();
-            }
+    /// Whether the `length` chars at the `start` char offset are not glued
+    /// to other word chars. Letters are Unicode aware, so `ç` and `ã` count.
+    fn is_word_boundary(&self, start:usize, length:usize) -> bool {
+        let is_word_char = |c:&char| c.is_alphanumeric() || *c == '_';
 
-            if !compare.starts_with(&value) {
-                continue
-            }
+        let before = start.checked_sub(1).and_then(|i| self.chars.get(i));
+        let after = self.chars.get(start + length);
 
-            let awaiting_token = self.search_token.is_some();
-            let skip_content = self.search_token.as_ref().map_or(false, |s| s.skip_content);
-
-            if is_wrapper {
-                if !awaiting_token {
-                    self.search_token = Some(
-                        TokenizerSearch {
-                            token: separator.clone(),
-                            skip_content: false
-                        }
-                    );
-                } else {
-                    let chars: Vec<char> = value.chars().collect();
-                    let len = chars.len();
+        !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+    }
 
-                    for &char in chars.iter().take(chars.len().saturating_sub(1 + value.len())) {
-                        self.push_unknown_character(char, len, true);
-                    }
-                    
-                    self.search_token = None;
-                }
-            }
+    /// Source text of the chars in `start..end`.
+    fn text(&self, start:usize, end:usize) -> String {
+        let span = self.source.span(start, end);
+        self.code[span.start().byte()..span.end().byte()].to_string()
+    }
 
-            let schema = sep_settings.clone().map_or(TokenTypes::None, |s| s.map().clone());
+    /// Push a single token for the delimited run in `start..end`, or
+    /// nothing when the separator skips its content. Strings get their
+    /// unescaped content attached.
+    fn collapse(&mut self, opener:usize, start:usize, end:usize) {
+        let separator = self.separators.separator(opener);
+        if separator.skips_content() {
+            return;
+        }
 
-            let value_length = value.chars().count();
-            if sep_settings.map_or(false, |s| s.is_inclusive()) {
-                let length = compare.chars().count() - 1;
-                let mut start = 0;
-                if self.index > length {
-                    start = self.index - length;
-                }
-                self.push_token(
-                    Token::new(
-                        TokenIndex::new(start, start + value_length),
-                        value.clone(),
-                        schema
-                    ),
-                    true
-                );
-            }
+        let text = self.text(start, end);
+        let schema = separator.settings().map().clone();
 
-            return value_length
+        let mut token = Token::new(self.source.span(start, end), text.clone(), schema.clone());
+        if schema == TokenTypes::String {
+            let content = text.strip_prefix(separator.value()).unwrap_or(&text);
+            let content = match separator.closer() {
+                Some(Closer::Text(closer)) => content.strip_suffix(closer.as_str()).unwrap_or(content),
+                _ => content,
+            };
+            token = token.with_literal(Literal::String(self.unescape(content)));
         }
 
-        0
+        self.tokens.push(token);
     }
 
-    fn merge_last_token(&mut self, new_text:String, index:usize, condition:Box<dyn Fn(&Token) -> bool>) -> Option<Token> {
-        let mut new_token:Option<Token> = None;
-        let last = self.tokens.last();
-        if let Some(token) = last {
-            if condition(token) {
-                let mut value = token.value().to_string();
-                value.push_str(&new_text);
-                new_token = Some(Token::new(
-                    TokenIndex::new(token.index().start(), index),
-                    value,
-                    TokenTypes::Custom { name: "unknown".to_string() }
-                ));
-                self.pop_token();
+    /// Replace each escape char and the char after it with what the
+    /// language says they stand for.
+    fn unescape(&self, content:&str) -> String {
+        let Some(escape) = self.escape else {
+            return content.to_string();
+        };
+
+        let mut text = String::new();
+        let mut chars = content.chars();
+        while let Some(c) = chars.next() {
+            if c == escape {
+                if let Some(next) = chars.next() {
+                    text.push(self.language.unescape(next));
+                    continue;
+                }
             }
+            text.push(c);
         }
-        new_token
+        text
     }
 
-    fn push_unknown_character(&mut self, char:char, buffer_length:usize, merge:bool) {
-        let mut index = 0;
-        if self.index > buffer_length {
-            index = self.index - buffer_length;
-        }
+    /// Give the runs no separator matched a literal or identifier type, and
+    /// join `integer . integer` into reals.
+    fn classify_literals(&mut self) {
+        let tokens = std::mem::take(&mut self.tokens);
 
-        let mut new_token = self.merge_last_token(char.to_string(), index, Box::new(|token| {
-            if let TokenTypes::Custom { name } = token.schema() {
-                if name == "unknown" {
-                    return true;
+        for token in tokens {
+            let token = self.classify(token);
+
+            if token.schema() == &TokenTypes::Integer {
+                if let Some(real) = self.join_real(&token) {
+                    self.tokens.push(real);
+                    continue;
                 }
             }
-            false
-        }));
-
-        if new_token.is_none() {
-            new_token = Some(Token::new(
-                TokenIndex::new(index, index + 1),
-                char.to_string(),
-                TokenTypes::Custom { name: "unknown".to_string() }
-            ));
+            self.tokens.push(token);
         }
+    }
 
-        if let Some(token) = new_token {
-            self.tokens.push(token);
+    fn classify(&self, token:Token) -> Token {
+        let value = token.value();
+        let span = *token.span();
+
+        match token.schema() {
+            TokenTypes::Boolean => match self.language.boolean(value) {
+                Some(boolean) => token.with_literal(Literal::Boolean(boolean)),
+                None => token,
+            },
+            TokenTypes::Custom { name } if name == "unknown" => {
+                if value.chars().all(|c| c.is_ascii_digit()) {
+                    let literal = match value.parse() {
+                        Ok(integer) => Literal::Integer(integer),
+                        Err(_) => Literal::Real(value.parse().unwrap_or(f64::INFINITY)),
+                    };
+                    let schema = match literal {
+                        Literal::Integer(_) => TokenTypes::Integer,
+                        _ => TokenTypes::Real,
+                    };
+                    return Token::new(span, value.clone(), schema).with_literal(literal);
+                }
+
+                let mut chars = value.chars();
+                let is_identifier = chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+                    && chars.all(|c| c.is_alphanumeric() || c == '_');
+                if is_identifier {
+                    return Token::new(span, value.clone(), TokenTypes::Identifier);
+                }
+                token
+            },
+            _ => token,
         }
     }
 
+    /// Pop `integer .` when `fraction` directly follows them.
+    fn join_real(&mut self, fraction:&Token) -> Option<Token> {
+        let [.., whole, dot] = self.tokens.as_slice() else {
+            return None;
+        };
+
+        let touching = whole.span().end() == dot.span().start() && dot.span().end() == fraction.span().start();
+        if whole.schema() != &TokenTypes::Integer || dot.value() != "." || *dot.count() != 1 || !touching {
+            return None;
+        }
+
+        let span = whole.span().join(fraction.span());
+        let text = format!("{}.{}", whole.value(), fraction.value());
+        let value = text.parse().unwrap_or(0.0);
+
+        self.tokens.truncate(self.tokens.len() - 2);
+        Some(Token::new(span, text, TokenTypes::Real).with_literal(Literal::Real(value)))
+    }
+
+    /// End the `unknown` run started at the `start` char offset, if any.
+    fn push_unknown(&mut self, start:&mut Option<usize>, end:usize) {
+        let Some(start) = start.take() else {
+            return;
+        };
+
+        self.tokens.push(Token::new(
+            self.source.span(start, end),
+            self.text(start, end),
+            TokenTypes::Custom { name: "unknown".to_string() }
+        ));
+    }
+
+    /// Push `token`, or with `group` count it into the last token when both
+    /// have the same text.
     fn push_token(&mut self, token:Token, group:bool) {
-        if group {
-            let last = self.tokens.last();
-            if let Some(last_token) = last {
-                if last_token.value() == token.value() {
-                    let mut popped = self.pop_token().unwrap();
-                    popped.increase_count();
-                    self.push_token(popped, true);
-                    return;
-                }
+        if let Some(last) = self.tokens.last_mut() {
+            if group && last.value() == token.value() {
+                last.increase_count(token.span().end());
+                return;
             }
         }
-        
-        self.tokens.push(token);
-    }
 
-    fn pop_token(&mut self) -> Option<Token> {
-        self.tokens.pop()
+        self.tokens.push(token);
     }
 
     pub fn print(&self) {
         for t in &self.tokens {
             let mut value = t.value().clone();
             for _ in 1..*t.count() {
-                value += &t.value();
+                value += t.value();
             }
-            println!("{} [{}] - {}", value, t.schema().to_string(), t.index().to_string());
+            println!("{} [{}] - {}", value, t.schema(), t.span());
         }
     }
-}
\ No newline at end of file
+}
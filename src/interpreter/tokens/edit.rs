@@ -0,0 +1,66 @@
+use std::ops::Range;
+
+/// Replace the chars in `start..end` with `text`. Offsets count chars, like
+/// [`super::span::Position::char`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextEdit {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+impl TextEdit {
+    pub fn new(range:Range<usize>, text:&str) -> Self {
+        Self { start: range.start, end: range.end.max(range.start), text: text.to_string() }
+    }
+
+    pub fn insert(at:usize, text:&str) -> Self {
+        Self::new(at..at, text)
+    }
+
+    pub fn delete(range:Range<usize>) -> Self {
+        Self::new(range, "")
+    }
+
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Apply the edit to `code`, clamping offsets past its end.
+    pub fn apply(&self, code:&mut String) {
+        let byte = |offset:usize| code.char_indices().nth(offset).map_or(code.len(), |(byte, _)| byte);
+        let range = byte(self.start)..byte(self.end);
+        code.replace_range(range, &self.text);
+    }
+}
+
+/// Tokens an edit replaced: `old` indexes the token list before the edit,
+/// `new` the list after it. Tokens past both ranges kept their text and
+/// type, only their spans moved.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenChange {
+    old: Range<usize>,
+    new: Range<usize>,
+}
+
+impl TokenChange {
+    pub fn new(old:Range<usize>, new:Range<usize>) -> Self {
+        Self { old, new }
+    }
+
+    pub fn old(&self) -> &Range<usize> {
+        &self.old
+    }
+
+    pub fn new_range(&self) -> &Range<usize> {
+        &self.new
+    }
+}
@@ -1,3 +1,6 @@
 pub mod schema;
+pub mod span;
 pub mod token;
-pub mod separator;
\ No newline at end of file
+pub mod separator;
+pub mod edit;
+pub mod trie;
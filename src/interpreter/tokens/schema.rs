@@ -1,29 +1,46 @@
-#[derive(Clone)]
+use std::fmt;
+
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TokenTypes {
     Keyword,
     Assignment,
-    Operator,
+    Relational,
+    Logical,
     Arithmetic,
     Groupper,
     Type,
     LineComment,
     GroupComment,
+    Integer,
+    Real,
+    String,
+    Boolean,
+    Identifier,
     Custom { name:String },
     None
 }
 
-impl TokenTypes {
-    pub fn to_string(&self) -> String {
-        String::from(
+impl fmt::Display for TokenTypes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(
             match self {
                 TokenTypes::Keyword => "keyword",
                 TokenTypes::Assignment => "assignment",
-                TokenTypes::Operator => "operator",
+                TokenTypes::Relational => "relational",
+                TokenTypes::Logical => "logical",
                 TokenTypes::Arithmetic => "arithmetic",
                 TokenTypes::Groupper => "groupper",
                 TokenTypes::Type => "type",
                 TokenTypes::LineComment => "line comment",
                 TokenTypes::GroupComment => "group comment",
+                TokenTypes::Integer => "integer",
+                TokenTypes::Real => "real",
+                TokenTypes::String => "string",
+                TokenTypes::Boolean => "boolean",
+                TokenTypes::Identifier => "identifier",
                 TokenTypes::Custom { name } => name,
                 TokenTypes::None => "none",
             }
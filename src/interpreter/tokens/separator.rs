@@ -5,6 +5,8 @@ pub struct SeparatorSetting {
     case_sensitive:bool,
     include:bool,
     group:bool,
+    whole_word:bool,
+    nestable:bool,
     map:TokenTypes
 }
 
@@ -14,7 +16,9 @@ impl SeparatorSetting {
             case_sensitive: false,
             include: true,
             group: false,
-            map: map,
+            whole_word: false,
+            nestable: false,
+            map,
         }
     }
     pub fn case_sensitive(mut self) -> Self {
@@ -29,6 +33,18 @@ impl SeparatorSetting {
         self.group = true;
         self
     }
+    /// Only match when not surrounded by letters, digits or `_`, so `se`
+    /// does not match the start of `seletor`.
+    pub fn whole_word(mut self) -> Self {
+        self.whole_word = true;
+        self
+    }
+    /// For delimiters: an opener inside the run opens a nested run, and
+    /// the run only ends when every one of them is closed.
+    pub fn nestable(mut self) -> Self {
+        self.nestable = true;
+        self
+    }
     pub fn is_case_sensitive(&self) -> bool {
         self.case_sensitive
     }
@@ -38,6 +54,12 @@ impl SeparatorSetting {
     pub fn is_grouppable(&self) -> bool {
         self.group
     }
+    pub fn is_whole_word(&self) -> bool {
+        self.whole_word
+    }
+    pub fn is_nestable(&self) -> bool {
+        self.nestable
+    }
     pub fn map(&self) -> &TokenTypes {
         &self.map
     }
@@ -54,6 +76,8 @@ pub enum TokenSeparators {
         settings: SeparatorSetting
     },
 
+    /// Run from `alpha` up to `end`, or the end of the line when `None`.
+    /// With `skip_content` the run is consumed without producing a token.
     AlphaUntil {
         alpha:char,
         end:Option<char>,
@@ -71,6 +95,7 @@ pub enum TokenSeparators {
         settings: SeparatorSetting
     },
     
+    /// Like [`TokenSeparators::AlphaUntil`], opened by a word.
     WordUntil {
         word:String,
         end:Option<String>,
@@ -83,11 +108,11 @@ impl TokenSeparators {
     pub fn is_alpha(separator:&TokenSeparators) -> bool {
         use TokenSeparators::*;
 
-        match separator {
+        matches!(
+            separator,
             Alpha { .. }
             | InAlphaRange { .. }
-            | AlphaUntil { .. } => true,
-            _ => false
-        }
+            | AlphaUntil { .. }
+        )
     }
 }
\ No newline at end of file
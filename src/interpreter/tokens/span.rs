@@ -0,0 +1,149 @@
+use std::fmt;
+
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct Position {
+    byte: usize,
+    char: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Position {
+    pub fn new(byte:usize, char:usize, line:usize, column:usize) -> Self {
+        Self { byte, char, line, column }
+    }
+
+    pub fn byte(&self) -> usize {
+        self.byte
+    }
+
+    pub fn char(&self) -> usize {
+        self.char
+    }
+
+    /// 1-based line number.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// 1-based column, counted in chars.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Position reached after reading `text` starting from `self`.
+    pub fn advance(&self, text:&str) -> Position {
+        let mut position = *self;
+
+        for c in text.chars() {
+            position.byte += c.len_utf8();
+            position.char += 1;
+
+            if c == '\n' {
+                position.line += 1;
+                position.column = 1;
+            } else {
+                position.column += 1;
+            }
+        }
+
+        position
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Span {
+    start: Position,
+    end: Position,
+}
+
+impl Span {
+    pub fn new(start:Position, end:Position) -> Self {
+        Self { start, end }
+    }
+
+    pub fn start(&self) -> Position {
+        self.start
+    }
+
+    pub fn end(&self) -> Position {
+        self.end
+    }
+
+    /// Smallest span covering both `self` and `other`.
+    pub fn join(&self, other:&Span) -> Span {
+        Span {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+}
+
+impl fmt::Debug for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Span({})", self)
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// Precomputed positions for every char of a source text, so spans can be
+/// built from char offsets without rescanning the code.
+pub struct SourceIndex {
+    positions: Vec<Position>,
+}
+
+impl SourceIndex {
+    pub fn new(code:&str) -> Self {
+        let mut positions = Vec::with_capacity(code.len() + 1);
+        let mut line = 1;
+        let mut column = 1;
+
+        for (char, (byte, c)) in code.char_indices().enumerate() {
+            positions.push(Position::new(byte, char, line, column));
+
+            if c == '\n' {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+        }
+
+        positions.push(Position::new(code.len(), positions.len(), line, column));
+
+        Self { positions }
+    }
+
+    /// Position of the char at `char` offset; offsets past the end clamp to
+    /// the end of the source.
+    pub fn position(&self, char:usize) -> Position {
+        let last = self.positions.len() - 1;
+        self.positions[char.min(last)]
+    }
+
+    /// Span covering the chars in `start..end`.
+    pub fn span(&self, start:usize, end:usize) -> Span {
+        Span::new(self.position(start), self.position(end))
+    }
+
+    pub fn len(&self) -> usize {
+        self.positions.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
@@ -1,35 +1,32 @@
+use serde::Serialize;
+
+use crate::interpreter::ast::expression::Literal;
+
 use super::schema::TokenTypes;
+use super::span::{Position, Span};
 
+#[derive(Serialize)]
 pub struct Token {
-    index: TokenIndex,
+    span: Span,
     value: String,
     count: i32,
-    schema: TokenTypes
-}
-
-#[derive(Clone)]
-pub struct  TokenIndex {
-    start: usize,
-    end: usize
+    schema: TokenTypes,
+    literal: Option<Literal>,
 }
 
-impl TokenIndex {
-    pub fn start(&self) -> usize {
-        self.start
+impl Token {
+    pub fn new(span:Span, value:String, schema:TokenTypes) -> Self {
+        Self { span, value, schema, count: 1, literal: None }
     }
 
-    pub fn end(&self) -> usize {
-        self.end
+    /// Attach the value of a literal token.
+    pub fn with_literal(mut self, literal:Literal) -> Self {
+        self.literal = Some(literal);
+        self
     }
-}
 
-impl Token {
-    pub fn new(index:TokenIndex, value:String, schema:TokenTypes) -> Self {
-        Self { index, value, schema, count: 1 }
-    }
-
-    pub fn index(&self) -> &TokenIndex {
-        &self.index
+    pub fn span(&self) -> &Span {
+        &self.span
     }
 
     pub fn value(&self) -> &String {
@@ -44,18 +41,18 @@ impl Token {
         &self.schema
     }
 
-    pub fn increase_count(&mut self) {
-        self.count += 1;
-        self.index.end += self.value.chars().count();
+    /// Parsed value of integer, real, string and boolean tokens.
+    pub fn literal(&self) -> Option<&Literal> {
+        self.literal.as_ref()
     }
-}
 
-impl TokenIndex {
-    pub fn new(start:usize, end:usize) -> Self {
-        Self { start, end }
+    /// Move the token without changing its text.
+    pub fn set_span(&mut self, span:Span) {
+        self.span = span;
     }
 
-    pub fn to_string(&self) -> String {
-        format!("{}:{}", self.start, self.end)
+    pub fn increase_count(&mut self, end:Position) {
+        self.count += 1;
+        self.span = Span::new(self.span.start(), end);
     }
-}
\ No newline at end of file
+}
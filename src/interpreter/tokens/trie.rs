@@ -0,0 +1,171 @@
+use super::separator::{SeparatorSetting, TokenSeparators};
+
+/// What ends a delimited run such as a string or a comment.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Closer {
+    /// Closed by a line break, which is left out of the run.
+    LineEnd,
+    Text(String),
+}
+
+/// One entry of a [`TokenSeparators`] list: a word, or the opener of a
+/// delimited run when `closer` is set.
+#[derive(Clone)]
+pub struct Separator {
+    value:String,
+    closer:Option<Closer>,
+    skip_content:bool,
+    settings:SeparatorSetting,
+}
+
+impl Separator {
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn closer(&self) -> Option<&Closer> {
+        self.closer.as_ref()
+    }
+
+    pub fn settings(&self) -> &SeparatorSetting {
+        &self.settings
+    }
+
+    /// Whether a delimited run is dropped instead of becoming a token.
+    pub fn skips_content(&self) -> bool {
+        self.skip_content
+    }
+
+    fn flatten(separators:Vec<TokenSeparators>) -> Vec<Separator> {
+        use TokenSeparators::*;
+
+        let simple = |value:String, settings:&SeparatorSetting| Separator {
+            value,
+            closer: None,
+            skip_content: false,
+            settings: settings.clone()
+        };
+        let mut flat = vec![];
+
+        for separator in separators {
+            match separator {
+                Alpha { alpha, settings } => flat.push(simple(alpha.to_string(), &settings)),
+                InAlphaRange { alphas, settings } => {
+                    flat.extend(alphas.iter().map(|a| simple(a.to_string(), &settings)));
+                },
+                Word { word, settings } => flat.push(simple(word, &settings)),
+                InWordRange { words, settings } => {
+                    flat.extend(words.into_iter().map(|w| simple(w, &settings)));
+                },
+                AlphaUntil { alpha, end, skip_content, settings } => flat.push(Separator {
+                    value: alpha.to_string(),
+                    closer: Some(end.map_or(Closer::LineEnd, |c| Closer::Text(c.to_string()))),
+                    skip_content,
+                    settings,
+                }),
+                WordUntil { word, end, skip_content, settings } => flat.push(Separator {
+                    value: word,
+                    closer: Some(end.map_or(Closer::LineEnd, Closer::Text)),
+                    skip_content,
+                    settings,
+                }),
+            }
+        }
+
+        flat.retain(|s| !s.value.is_empty());
+        flat
+    }
+}
+
+#[derive(Default)]
+struct TrieNode {
+    children:Vec<(char, usize)>,
+    /// Separators spelled by the path to this node, in declaration order.
+    accepts:Vec<usize>,
+}
+
+/// The separators of a language compiled into a trie, so the separator at a
+/// position is found by walking the source once instead of comparing every
+/// separator against it. Keys are lowercased when the language ignores case.
+pub struct SeparatorTrie {
+    nodes:Vec<TrieNode>,
+    separators:Vec<Separator>,
+    case_sensitive:bool,
+}
+
+impl SeparatorTrie {
+    pub fn new(separators:Vec<TokenSeparators>, case_sensitive:bool) -> Self {
+        let mut trie = Self {
+            nodes: vec![TrieNode::default()],
+            separators: Separator::flatten(separators),
+            case_sensitive,
+        };
+
+        for index in 0..trie.separators.len() {
+            let value:Vec<char> = trie.separators[index].value.chars().map(|c| trie.fold(c)).collect();
+
+            let mut node = 0;
+            for c in value {
+                node = match trie.nodes[node].children.iter().find(|(key, _)| *key == c) {
+                    Some(&(_, child)) => child,
+                    None => {
+                        trie.nodes.push(TrieNode::default());
+                        let child = trie.nodes.len() - 1;
+                        trie.nodes[node].children.push((c, child));
+                        child
+                    }
+                };
+            }
+            trie.nodes[node].accepts.push(index);
+        }
+
+        trie
+    }
+
+    pub fn separator(&self, index:usize) -> &Separator {
+        &self.separators[index]
+    }
+
+    /// Whether `a` and `b` are the same char for this language.
+    pub fn same(&self, a:char, b:char) -> bool {
+        a == b || self.fold(a) == self.fold(b)
+    }
+
+    fn fold(&self, c:char) -> char {
+        if self.case_sensitive {
+            c
+        } else if c.is_ascii() {
+            c.to_ascii_lowercase()
+        } else {
+            c.to_lowercase().next().unwrap_or(c)
+        }
+    }
+
+    /// Index and length of the longest separator `chars` starts with that
+    /// `accept` agrees to; equally long ones are tried in declaration order.
+    /// Separators asking for case sensitivity only match their exact
+    /// spelling.
+    pub fn longest(&self, chars:&[char], accept:impl Fn(&Separator, usize) -> bool) -> Option<(usize, usize)> {
+        self.walk(0, chars, 0, &accept)
+    }
+
+    fn walk(&self, node:usize, chars:&[char], depth:usize, accept:&impl Fn(&Separator, usize) -> bool) -> Option<(usize, usize)> {
+        if let Some(&c) = chars.get(depth) {
+            let c = self.fold(c);
+            let child = self.nodes[node].children.iter().find(|(key, _)| *key == c);
+
+            if let Some(found) = child.and_then(|&(_, child)| self.walk(child, chars, depth + 1, accept)) {
+                return Some(found);
+            }
+        }
+
+        self.nodes[node].accepts.iter()
+            .find(|&&index| {
+                let separator = &self.separators[index];
+                let exact = !separator.settings.is_case_sensitive() || self.case_sensitive
+                    || separator.value.chars().eq(chars[..depth].iter().copied());
+                exact && accept(separator, depth)
+            })
+            .map(|&index| (index, depth))
+    }
+}
@@ -0,0 +1,65 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use super::ast::types::TypeKind;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Value {
+    Inteiro(i64),
+    Real(f64),
+    Caractere(String),
+    Logico(bool),
+}
+
+impl Value {
+    /// Value a variable of `kind` holds before its first assignment.
+    pub fn default_for(kind:TypeKind) -> Self {
+        match kind {
+            TypeKind::Inteiro => Value::Inteiro(0),
+            TypeKind::Real => Value::Real(0.0),
+            TypeKind::Caractere => Value::Caractere(String::new()),
+            TypeKind::Logico => Value::Logico(false),
+        }
+    }
+
+    pub fn type_kind(&self) -> TypeKind {
+        match self {
+            Value::Inteiro(_) => TypeKind::Inteiro,
+            Value::Real(_) => TypeKind::Real,
+            Value::Caractere(_) => TypeKind::Caractere,
+            Value::Logico(_) => TypeKind::Logico,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            Value::Inteiro(value) => Some(*value as f64),
+            Value::Real(value) => Some(*value),
+            _ => None
+        }
+    }
+
+    /// Convert the value to be stored in a variable of `kind`, promoting
+    /// `inteiro` to `real`. `None` when the types are incompatible.
+    pub fn coerce(self, kind:TypeKind) -> Option<Value> {
+        match (self, kind) {
+            (Value::Inteiro(value), TypeKind::Real) => Some(Value::Real(value as f64)),
+            (value, kind) if value.type_kind() == kind => Some(value),
+            _ => None
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Inteiro(value) => write!(f, "{}", value),
+            Value::Real(value) => write!(f, "{}", value),
+            Value::Caractere(value) => f.write_str(value),
+            Value::Logico(true) => f.write_str("VERDADEIRO"),
+            Value::Logico(false) => f.write_str("FALSO"),
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod app;
+pub mod cli;
+pub mod interpreter;
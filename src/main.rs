@@ -1,23 +1,14 @@
-use interpreter::{interpreter::Interpreter, languages::visualg::VisuAlg};
-
-mod app;
-mod interpreter;
-
-fn main() {
-    if atty::is(atty::Stream::Stdin) {
-        let lang = VisuAlg {};
-
-        let mut int = Interpreter::from_file(
-            String::from("tests/scripts/visualg/script-4.alg")
-        );
-
-        int.interpret(Box::new(lang));
-
-        return;
+use std::process::ExitCode;
+
+use visual_tho::cli::args::{Args, USAGE};
+use visual_tho::cli::commands;
+
+fn main() -> ExitCode {
+    match Args::parse(std::env::args().skip(1)) {
+        Ok(args) => commands::execute(&args),
+        Err(message) => {
+            eprintln!("erro: {}\n\n{}", message, USAGE);
+            ExitCode::from(commands::USAGE_ERROR)
+        }
     }
-
-    let mut window = app::window::Window::default();
-    window.set_title("VisualTho");
-
-    let _ = app::window::run_window(window);
 }
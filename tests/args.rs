@@ -0,0 +1,62 @@
+//! Command-line parsing, valid and not.
+
+use visual_tho::cli::args::{Args, Command};
+use visual_tho::interpreter::runner::MAX_DEPTH_LIMIT;
+
+fn parse(args:&[&str]) -> Result<Args, String> {
+    Args::parse(args.iter().map(|a| a.to_string()))
+}
+
+#[test]
+fn commands_and_options() {
+    let args = parse(&["run", "a.alg", "--lang", "visualg", "--stdin-file", "in.txt", "--seed", "7", "--json"]).expect("valid");
+    assert_eq!(args.command(), &Command::Run { file: String::from("a.alg") });
+    assert_eq!(args.language(), Some("visualg"));
+    assert_eq!(args.stdin_file(), Some("in.txt"));
+    assert_eq!(args.seed(), Some(7));
+    assert!(args.is_json());
+
+    assert_eq!(parse(&[]).expect("valid").command(), &Command::Gui { file: None });
+    assert_eq!(parse(&["check", "--help"]).expect("valid").command(), &Command::Help);
+}
+
+#[test]
+fn unknown_flags_and_commands_are_rejected() {
+    assert_eq!(parse(&["run", "a.alg", "--verbose"]), Err(String::from("opção desconhecida '--verbose'")));
+    assert_eq!(parse(&["compile", "a.alg"]), Err(String::from("comando desconhecido 'compile'")));
+    assert_eq!(parse(&["run", "a.alg", "b.alg"]), Err(String::from("argumento inesperado 'b.alg'")));
+    assert_eq!(parse(&["run"]), Err(String::from("o comando 'run' precisa de um arquivo")));
+}
+
+#[test]
+fn options_need_their_values() {
+    for option in ["--lang", "--stdin-file", "--max-depth", "--seed", "--record-random", "--replay-random", "--break"] {
+        assert_eq!(parse(&["debug", "a.alg", option]), Err(format!("a opção '{}' precisa de um valor", option)));
+    }
+    assert!(parse(&["run", "a.alg", "--seed", "-1"]).is_err());
+    assert!(parse(&["run", "a.alg", "--seed", "1", "--replay-random", "r.log"]).is_err());
+}
+
+#[test]
+fn max_depth_is_capped() {
+    let depth = |value:&str| parse(&["run", "a.alg", "--max-depth", value]).map(|args| args.max_depth());
+
+    assert_eq!(depth("1"), Ok(Some(1)));
+    assert_eq!(depth(&MAX_DEPTH_LIMIT.to_string()), Ok(Some(MAX_DEPTH_LIMIT)));
+    for invalid in ["0", "-3", "dez", &(MAX_DEPTH_LIMIT + 1).to_string()] {
+        assert_eq!(
+            depth(invalid),
+            Err(format!("valor inválido '{}' para '--max-depth', use um inteiro de 1 a {}", invalid, MAX_DEPTH_LIMIT))
+        );
+    }
+    assert_eq!(parse(&["run", "a.alg"]).expect("valid").max_depth(), None);
+}
+
+#[test]
+fn break_repeats_for_debug_only() {
+    let args = parse(&["debug", "a.alg", "--break", "3", "--break", "12 se i = 3"]).expect("valid");
+    assert_eq!(args.breakpoints(), &vec![String::from("3"), String::from("12 se i = 3")]);
+
+    assert_eq!(parse(&["run", "a.alg", "--break", "3"]), Err(String::from("'--break' só vale para o comando 'debug'")));
+    assert!(parse(&["debug", "a.alg", "--json"]).is_err());
+}
@@ -0,0 +1,116 @@
+//! Each checker error, on the smallest VisuAlg program that has it.
+
+use visual_tho::interpreter::diagnostics::codes;
+use visual_tho::interpreter::interpreter::Interpreter;
+use visual_tho::interpreter::languages;
+
+/// Declarations and subprograms every program below may use.
+const DECLARATIONS:&str = "\
+var
+   x: inteiro
+   r: real
+   v: vetor[1..3] de inteiro
+   m: vetor[1..2, 1..2] de inteiro
+procedimento p(a: inteiro)
+inicio
+fimprocedimento
+procedimento q(var a: inteiro)
+inicio
+fimprocedimento
+funcao f(a: inteiro): inteiro
+inicio
+   retorne a
+fimfuncao
+";
+
+/// Codes of the errors checking `code` gives.
+fn errors(code:&str) -> Vec<&'static str> {
+    let mut interpreter = Interpreter::from_code(languages::by_name("visualg").expect("visualg"), code.to_string());
+    match interpreter.check() {
+        Ok(_) => vec![],
+        Err(errors) => errors.iter().map(|e| e.code()).collect(),
+    }
+}
+
+/// Codes of the errors of a program running `body` after [`DECLARATIONS`]
+/// and `extra`, declared after them.
+fn body_errors(extra:&str, body:&str) -> Vec<&'static str> {
+    errors(&format!("algoritmo \"checado\"\n{}{}inicio\n{}\nfimalgoritmo\n", DECLARATIONS, extra, body))
+}
+
+#[test]
+fn declarations_alone_check() {
+    assert_eq!(body_errors("", "x <- f(1)\np(x)\nq(v[1])\nr <- m[1, 2] + x"), Vec::<&str>::new());
+}
+
+#[test]
+fn each_error_has_its_code() {
+    let cases:&[(&str, &str, &str)] = &[
+        ("", "x <- y", codes::UNDECLARED_NAME),
+        ("var\n   x: real\n", "", codes::DUPLICATE_DECLARATION),
+        ("", "x <- \"um\"", codes::INCOMPATIBLE_ASSIGNMENT),
+        ("", "se x entao\nfimse", codes::NON_LOGICAL_CONDITION),
+        ("", "p(1, 2)", codes::ARGUMENT_COUNT),
+        ("", "p(\"um\")", codes::ARGUMENT_TYPE),
+        ("", "q(1)", codes::ARGUMENT_TYPE),
+        ("", "nada(1)", codes::UNDEFINED_SUBPROGRAM),
+        ("", "x <- 1 + verdadeiro", codes::INCOMPATIBLE_OPERANDS),
+        ("", "x <- v[1.5]", codes::NON_INTEGER_EXPRESSION),
+        ("", "para x de 1 ate r faca\nfimpara", codes::NON_INTEGER_EXPRESSION),
+        ("", "x <- p(1)", codes::NO_RETURN_VALUE),
+        ("", "retorne 1", codes::NO_RETURN_VALUE),
+        ("var\n   w: vetor[3..1] de inteiro\n", "", codes::INVALID_BOUNDS),
+        ("var\n   w: vetor[1..2000, 1..2000] de inteiro\n", "", codes::INVALID_BOUNDS),
+        ("", "x[1] <- 2", codes::NOT_AN_ARRAY),
+        ("", "x <- v", codes::INDEX_COUNT),
+        ("", "x <- m[1]", codes::INDEX_COUNT),
+        ("funcao g: inteiro\ninicio\nfimfuncao\n", "", codes::MISSING_RETURN),
+        ("funcao g: inteiro\ninicio\n   retorne\nfimfuncao\n", "", codes::MISSING_RETURN),
+    ];
+
+    for (extra, body, code) in cases {
+        assert_eq!(body_errors(extra, body), vec![*code], "{:?} {:?}", extra, body);
+    }
+}
+
+#[test]
+fn vetor_parameters_have_valid_bounds() {
+    let parameter = |bounds:&str| body_errors(&format!("procedimento s(w: vetor[{}] de inteiro)\ninicio\nfimprocedimento\n", bounds), "");
+
+    assert_eq!(parameter("1..3"), Vec::<&str>::new());
+    assert_eq!(parameter("3..1"), vec![codes::INVALID_BOUNDS]);
+    assert_eq!(parameter("1..2000, 1..2000"), vec![codes::INVALID_BOUNDS]);
+}
+
+/// Subprograms taking the arrays of [`DECLARATIONS`].
+const VETOR_PARAMETERS:&str = "\
+procedimento s(w: vetor[1..3] de inteiro)
+inicio
+fimprocedimento
+procedimento t(var w: vetor[1..3] de inteiro)
+inicio
+fimprocedimento
+procedimento u(w: vetor[1..3] de real)
+inicio
+fimprocedimento
+procedimento n(var w: vetor[1..3] de real)
+inicio
+fimprocedimento
+procedimento d(w: vetor[1..2, 1..2] de inteiro)
+inicio
+fimprocedimento
+";
+
+#[test]
+fn vetor_parameters_take_whole_arrays() {
+    for call in ["s(v)", "t(v)", "u(v)", "d(m)"] {
+        assert_eq!(body_errors(VETOR_PARAMETERS, call), Vec::<&str>::new(), "{}", call);
+    }
+}
+
+#[test]
+fn vetor_parameters_reject_other_arguments() {
+    for call in ["s(x)", "s(v[1])", "s(1)", "s(m)", "t(x)", "d(v)", "d(m[1, 1])", "n(v)"] {
+        assert_eq!(body_errors(VETOR_PARAMETERS, call), vec![codes::ARGUMENT_TYPE], "{}", call);
+    }
+}
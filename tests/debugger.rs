@@ -0,0 +1,255 @@
+//! Runs driven through a `ChannelDebugger` from the test thread, as the
+//! GUI does.
+
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use visual_tho::interpreter::console::memory::MemoryIo;
+use visual_tho::interpreter::debug::breakpoint::{Breakpoint, Breakpoints};
+use visual_tho::interpreter::debug::channel::{ChannelDebugger, DebugChannel};
+use visual_tho::interpreter::debug::debugger::Debugger;
+use visual_tho::interpreter::debug::handler::Command;
+use visual_tho::interpreter::debug::state::{DebugState, Pause};
+use visual_tho::interpreter::diagnostics::codes;
+use visual_tho::interpreter::interpreter::Interpreter;
+use visual_tho::interpreter::languages;
+use visual_tho::interpreter::random::SeededRandom;
+use visual_tho::interpreter::value::Value;
+
+const PROGRAM:&str = "\
+algoritmo \"depurar\"
+var
+   i, total: inteiro
+funcao dobro(n: inteiro): inteiro
+inicio
+   retorne n * 2
+fimfuncao
+inicio
+   total <- 0
+   para i de 1 ate 3 faca
+      total <- total + dobro(i)
+   fimpara
+   escreval(total)
+fimalgoritmo
+";
+
+/// A run of [`PROGRAM`] in the background, and the end that drives it.
+struct Session {
+    channel:DebugChannel,
+    /// Error codes the run ended with, and what it printed.
+    run:JoinHandle<(Vec<&'static str>, String)>,
+}
+
+impl Session {
+    fn start(setup:impl FnOnce(Debugger<'static>) -> Debugger<'static> + Send + 'static) -> Self {
+        let (handler, channel) = ChannelDebugger::new();
+        let run = thread::spawn(move || {
+            let mut interpreter = Interpreter::for_code(PROGRAM.to_string());
+            let mut io = MemoryIo::new("");
+            let debugger = setup(Debugger::new(Box::new(handler)));
+            let errors = match interpreter.debug_with(&mut io, &mut SeededRandom::new(1), debugger) {
+                Ok(_) => vec![],
+                Err(errors) => errors.iter().map(|e| e.code()).collect(),
+            };
+            (errors, io.output().to_string())
+        });
+        Self { channel, run }
+    }
+
+    /// The next pause, `None` once the program ends.
+    fn next(&self) -> Option<DebugState> {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            if let Some(state) = self.channel.poll() {
+                return Some(state);
+            }
+            if self.run.is_finished() {
+                return self.channel.poll();
+            }
+            assert!(Instant::now() < deadline, "o programa não pausou nem terminou");
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    fn paused(&self) -> DebugState {
+        self.next().expect("program paused")
+    }
+
+    /// Go on with `command` and return the next pause.
+    fn then(&self, command:Command) -> Option<DebugState> {
+        assert!(self.channel.send(command));
+        self.next()
+    }
+
+    fn finish(self) -> (Vec<&'static str>, String) {
+        self.run.join().expect("run finished")
+    }
+}
+
+fn breakpoints(texts:&[&str]) -> Breakpoints {
+    let language = languages::by_name("visualg").expect("visualg");
+    let mut breakpoints = Breakpoints::new();
+    for text in texts {
+        breakpoints.add(Breakpoint::parse(text, language.as_ref()).expect("breakpoint"));
+    }
+    breakpoints
+}
+
+/// Value of the variable `name` the paused statement sees.
+fn value(state:&DebugState, name:&str) -> Value {
+    let variable = state.locals().iter().chain(state.globals().iter())
+        .find(|v| v.name() == name)
+        .unwrap_or_else(|| panic!("variável {} em {:?}", name, state));
+    variable.elements()[0].1.clone()
+}
+
+/// Lines of the pauses, with `i` and `total` at each.
+fn stops(session:&Session, command:Command) -> Vec<(usize, Value, Value)> {
+    let mut stops = vec![];
+    let mut state = session.next();
+    while let Some(paused) = state {
+        stops.push((paused.line(), value(&paused, "i"), value(&paused, "total")));
+        state = session.then(command);
+    }
+    stops
+}
+
+#[test]
+fn stepping_into_stops_before_every_statement() {
+    let session = Session::start(|debugger| debugger);
+
+    let start = session.paused();
+    assert_eq!((start.line(), start.pause()), (9, &Pause::Step));
+    assert!(start.calls().is_empty());
+    assert_eq!(start.globals().iter().map(|v| v.elements().clone()).collect::<Vec<_>>(), vec![
+        vec![(String::from("i"), Value::Inteiro(0))],
+        vec![(String::from("total"), Value::Inteiro(0))],
+    ]);
+
+    assert_eq!(session.then(Command::StepInto).map(|s| s.line()), Some(10));
+    assert_eq!(session.then(Command::StepInto).map(|s| s.line()), Some(11));
+
+    let call = session.then(Command::StepInto).expect("inside dobro");
+    assert_eq!(call.line(), 6);
+    assert_eq!(call.calls().iter().map(|c| (c.name(), c.call().start().line())).collect::<Vec<_>>(), vec![("dobro", 11)]);
+    assert_eq!(value(&call, "n"), Value::Inteiro(1));
+    assert_eq!(value(&call, "i"), Value::Inteiro(1));
+
+    let back = session.then(Command::StepInto).expect("back in the loop");
+    assert_eq!((back.line(), value(&back, "i"), value(&back, "total")), (11, Value::Inteiro(2), Value::Inteiro(2)));
+    assert!(back.locals().is_empty());
+
+    assert_eq!(session.then(Command::Continue), None);
+    assert_eq!(session.finish(), (vec![], String::from(" 12\n")));
+}
+
+#[test]
+fn stepping_over_skips_the_calls() {
+    let session = Session::start(|debugger| debugger);
+
+    assert_eq!(stops(&session, Command::StepOver), vec![
+        (9, Value::Inteiro(0), Value::Inteiro(0)),
+        (10, Value::Inteiro(0), Value::Inteiro(0)),
+        (11, Value::Inteiro(1), Value::Inteiro(0)),
+        (11, Value::Inteiro(2), Value::Inteiro(2)),
+        (11, Value::Inteiro(3), Value::Inteiro(6)),
+        (13, Value::Inteiro(4), Value::Inteiro(12)),
+    ]);
+    assert_eq!(session.finish(), (vec![], String::from(" 12\n")));
+}
+
+#[test]
+fn stepping_out_returns_to_the_caller() {
+    let session = Session::start(|debugger| debugger.with_breakpoints(breakpoints(&["6"])).starting_with(Command::Continue));
+
+    let call = session.paused();
+    assert_eq!((call.line(), call.pause()), (6, &Pause::Breakpoint { line: 6 }));
+
+    let caller = session.then(Command::StepOut).expect("back in the caller");
+    assert_eq!((caller.line(), caller.pause()), (11, &Pause::Step));
+    assert!(caller.calls().is_empty());
+    assert_eq!(value(&caller, "total"), Value::Inteiro(2));
+}
+
+#[test]
+fn continuing_stops_at_each_breakpoint() {
+    let session = Session::start(|debugger| debugger.with_breakpoints(breakpoints(&["11", "13"])).starting_with(Command::Continue));
+
+    let first = session.paused();
+    assert_eq!(first.pause(), &Pause::Breakpoint { line: 11 });
+    assert_eq!((value(&first, "i"), value(&first, "total")), (Value::Inteiro(1), Value::Inteiro(0)));
+
+    let second = session.then(Command::Continue).expect("second iteration");
+    assert_eq!((second.line(), value(&second, "i"), value(&second, "total")), (11, Value::Inteiro(2), Value::Inteiro(2)));
+
+    let third = session.then(Command::Continue).expect("third iteration");
+    assert_eq!((third.line(), value(&third, "i")), (11, Value::Inteiro(3)));
+
+    let last = session.then(Command::Continue).expect("after the loop");
+    assert_eq!((last.pause(), value(&last, "total")), (&Pause::Breakpoint { line: 13 }, Value::Inteiro(12)));
+
+    assert_eq!(session.then(Command::Continue), None);
+    assert_eq!(session.finish(), (vec![], String::from(" 12\n")));
+}
+
+#[test]
+fn conditional_breakpoints_stop_where_the_condition_holds() {
+    let session = Session::start(|debugger| {
+        debugger.with_breakpoints(breakpoints(&["11 se i = 3", "6 se n = 2"])).starting_with(Command::Continue)
+    });
+
+    let call = session.paused();
+    assert_eq!((call.pause(), value(&call, "n")), (&Pause::Breakpoint { line: 6 }, Value::Inteiro(2)));
+
+    let loop_end = session.then(Command::Continue).expect("third iteration");
+    assert_eq!(loop_end.pause(), &Pause::Breakpoint { line: 11 });
+    assert_eq!((value(&loop_end, "i"), value(&loop_end, "total")), (Value::Inteiro(3), Value::Inteiro(6)));
+
+    assert_eq!(session.then(Command::Continue), None);
+    assert_eq!(session.finish().0, Vec::<&str>::new());
+}
+
+#[test]
+fn failing_conditions_pause_with_the_reason() {
+    let session = Session::start(|debugger| debugger.with_breakpoints(breakpoints(&["11 se x = 1"])).starting_with(Command::Continue));
+
+    let state = session.paused();
+    assert_eq!(state.line(), 11);
+    assert!(matches!(state.pause(), Pause::ConditionFailed { line: 11, .. }), "{:?}", state.pause());
+    session.then(Command::Stop);
+}
+
+#[test]
+fn breakpoints_set_while_paused_apply_to_the_run() {
+    let session = Session::start(|debugger| debugger);
+    assert_eq!(session.paused().line(), 9);
+
+    assert!(session.channel.set_breakpoints(breakpoints(&["13"])));
+    let state = session.then(Command::Continue).expect("breakpoint set while paused");
+    assert_eq!((state.pause(), value(&state, "total")), (&Pause::Breakpoint { line: 13 }, Value::Inteiro(12)));
+}
+
+#[test]
+fn stopping_ends_the_run() {
+    let session = Session::start(|debugger| debugger);
+    assert_eq!(session.paused().line(), 9);
+
+    assert_eq!(session.then(Command::Stop), None);
+    assert_eq!(session.finish(), (vec![codes::DEBUG_STOPPED], String::new()));
+}
+
+#[test]
+fn breakpoint_parse_rejects_bad_input() {
+    let language = languages::by_name("visualg").expect("visualg");
+    let parse = |text:&str| Breakpoint::parse(text, language.as_ref());
+
+    for text in ["", "0", "-3", "doze", "12.5", "12 i = 3", "12 quando i = 3", "12 se", "12 se i +", "12 se i = 3 4", "12 se (i"] {
+        assert!(parse(text).is_err(), "{:?} aceito", text);
+    }
+
+    let breakpoint = parse(" 12  se i = 3 ").expect("conditional breakpoint");
+    assert_eq!((breakpoint.line(), breakpoint.condition_text()), (12, Some("i = 3")));
+    assert_eq!(breakpoint.to_string(), "12 se i = 3");
+    assert_eq!(parse("7").expect("breakpoint"), Breakpoint::new(7));
+}
+
@@ -0,0 +1,153 @@
+//! Editing code already loaded, as the editor does on each keystroke.
+
+use std::fs;
+
+use visual_tho::interpreter::diagnostics::codes;
+use visual_tho::interpreter::interpreter::Interpreter;
+use visual_tho::interpreter::languages;
+use visual_tho::interpreter::tokenizer::Tokenizer;
+use visual_tho::interpreter::tokens::edit::TextEdit;
+
+const PROGRAM:&str = "\
+algoritmo \"editado\"
+var
+   x: inteiro
+inicio
+   x <- 1
+   escreval(x)
+fimalgoritmo
+";
+
+#[test]
+fn edit_loads_the_file_first() {
+    let path = std::env::temp_dir().join(format!("visual_tho_edit_{}.alg", std::process::id()));
+    fs::write(&path, PROGRAM).expect("temporary file");
+
+    let mut interpreter = Interpreter::from_file(languages::by_name("visualg").expect("visualg"), path.display().to_string());
+    let change = interpreter.edit(&TextEdit::new(53..54, "2"));
+    fs::remove_file(&path).expect("temporary file removed");
+
+    assert_eq!(change, Ok(None));
+    assert_eq!(interpreter.code(), Some(PROGRAM.replacen("<- 1", "<- 2", 1).as_str()));
+}
+
+#[test]
+fn edit_of_an_unreadable_file_fails() {
+    let mut interpreter = Interpreter::from_file(languages::by_name("visualg").expect("visualg"), String::from("nao/existe.alg"));
+
+    let errors = interpreter.edit(&TextEdit::insert(0, "x")).expect_err("file does not exist");
+    assert_eq!(errors[0].code(), codes::FILE_UNREADABLE);
+    assert_eq!(interpreter.code(), None);
+}
+
+#[test]
+fn edit_of_tokenized_code_returns_the_change() {
+    let mut interpreter = Interpreter::from_code(languages::by_name("visualg").expect("visualg"), PROGRAM.to_string());
+    interpreter.tokenize().expect("tokens");
+
+    let change = interpreter.edit(&TextEdit::new(53..54, "23")).expect("loaded").expect("tokenized");
+    assert!(!change.new_range().is_empty());
+    assert!(interpreter.tokens().expect("tokens").iter().any(|t| t.value() == "23"));
+}
+
+/// Programs the random edits start from, with comments and strings for
+/// the edits to land in.
+const SAMPLES:[(&str, &str); 2] = [
+    ("visualg", "\
+algoritmo \"amostra\" // nome
+var
+   nome: caractere
+   i: inteiro
+inicio
+   nome <- \"José \\\"Zé\\\"\"
+   { comentário
+     em várias linhas }
+   para i de 1 ate 3 faca
+      escreval(nome, \" - \", i) // laço
+   fimpara
+fimalgoritmo
+"),
+    ("portugol", "\
+programa
+{
+\t/* comentário
+\t   em várias linhas */
+\tfuncao inicio()
+\t{
+\t\tcadeia nome = \"José \\\"Zé\\\"\" // nome
+\t\tpara (inteiro i = 1; i <= 3; i++)
+\t\t{
+\t\t\tescreva(nome, \" - \", i, \"\\n\")
+\t\t}
+\t}
+}
+"),
+];
+
+/// Texts the random edits insert: delimiters opening and closing
+/// comments and strings, line breaks and multi-byte chars among them.
+const INSERTS:[&str; 14] = [
+    "", "x", "\"", "\\\"", "//", "{", "}", "/*", "*/", "\n", "\n   ", "é", "<- 1 + 2\n", "\"ã\" // ç\n",
+];
+
+/// Linear congruential generator, so failures repeat from their seed.
+struct Lcg(u64);
+
+impl Lcg {
+    fn below(&mut self, bound:usize) -> usize {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((self.0 >> 33) % bound.max(1) as u64) as usize
+    }
+}
+
+/// Tokens as data, to compare two tokenizers.
+fn tokens(tokenizer:&Tokenizer) -> Vec<serde_json::Value> {
+    tokenizer.tokens().iter().map(|token| serde_json::to_value(token).expect("serializable")).collect()
+}
+
+/// Text and type of a token, which survive a move.
+fn shape(token:&serde_json::Value) -> (serde_json::Value, serde_json::Value) {
+    (token["value"].clone(), token["schema"].clone())
+}
+
+#[test]
+fn random_edits_tokenize_like_the_whole_code() {
+    for (language, code) in SAMPLES {
+        for seed in 0..60 {
+            let mut random = Lcg(seed);
+            let mut code = code.to_string();
+            let mut tokenizer = Tokenizer::new(languages::by_name(language).expect("language"));
+            tokenizer.tokenize(&code);
+
+            for step in 0..12 {
+                let length = code.chars().count();
+                let start = random.below(length + 1);
+                let end = (start + random.below(40)).min(length);
+                let edit = TextEdit::new(start..end, INSERTS[random.below(INSERTS.len())]);
+                let context = format!("{} seed {} step {}: {:?} on {:?}", language, seed, step, edit, code);
+
+                let old_tokens = tokens(&tokenizer);
+                let change = tokenizer.edit(&edit);
+                edit.apply(&mut code);
+
+                let mut full = Tokenizer::new(languages::by_name(language).expect("language"));
+                full.tokenize(&code);
+                let new_tokens = tokens(&tokenizer);
+                assert_eq!(new_tokens, tokens(&full), "{}", context);
+                assert_eq!(tokenizer.diagnostics(), full.diagnostics(), "{}", context);
+
+                // Tokens before the change are untouched, the ones after it
+                // only moved.
+                let (old, new) = (change.old(), change.new_range());
+                assert_eq!(old.start, new.start, "{}", context);
+                assert!(old.end <= old_tokens.len() && new.end <= new_tokens.len(), "{}", context);
+                assert_eq!(old_tokens[..old.start], new_tokens[..new.start], "{}", context);
+                assert_eq!(
+                    old_tokens[old.end..].iter().map(shape).collect::<Vec<_>>(),
+                    new_tokens[new.end..].iter().map(shape).collect::<Vec<_>>(),
+                    "{}", context
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Source files decoded to UTF-8 text and written back as they were stored.
+
+use visual_tho::interpreter::encoding::{LineEnding, SourceEncoding, SourceFormat};
+
+#[test]
+fn utf8_bom_is_stripped_and_kept() {
+    let bytes = b"\xEF\xBB\xBFalgoritmo \"a\"\n";
+    let (text, format) = SourceFormat::decode(bytes);
+
+    assert_eq!(text, "algoritmo \"a\"\n");
+    assert!(format.has_bom());
+    assert_eq!(format.encoding(), SourceEncoding::Utf8);
+    assert!(!format.is_transcoded());
+    assert_eq!(format.encode(&text), bytes);
+
+    let (text, format) = SourceFormat::decode(b"inicio\n");
+    assert_eq!(text, "inicio\n");
+    assert!(!format.has_bom());
+    assert_eq!(format.encode(&text), b"inicio\n");
+}
+
+#[test]
+fn crlf_is_read_as_lf_and_written_back() {
+    let bytes = b"inicio\r\n   escreva(1)\r\nfimalgoritmo\r\n";
+    let (text, format) = SourceFormat::decode(bytes);
+
+    assert_eq!(text, "inicio\n   escreva(1)\nfimalgoritmo\n");
+    assert_eq!(format.line_ending(), LineEnding::CrLf);
+    assert_eq!(format.encode(&text), bytes);
+    assert_eq!(format.encode("a\nb\r\nc"), b"a\r\nb\r\nc");
+
+    let (_, format) = SourceFormat::decode(b"inicio\nfimalgoritmo\n");
+    assert_eq!(format.line_ending(), LineEnding::Lf);
+}
+
+#[test]
+fn windows1252_round_trips() {
+    // "ação €" as the classic VisuAlg editor saves it.
+    let bytes = b"escreva(\"a\xE7\xE3o \x80\")\r\n";
+    let (text, format) = SourceFormat::decode(bytes);
+
+    assert_eq!(text, "escreva(\"ação €\")\n");
+    assert_eq!(format.encoding(), SourceEncoding::Windows1252);
+    assert_eq!(format.encoding().name(), "Windows-1252");
+    assert!(format.is_transcoded());
+    assert!(!format.has_bom());
+    assert_eq!(format.encode(&text), bytes);
+
+    assert!(format.can_encode("média"));
+    assert!(!format.can_encode("π"));
+    assert_eq!(format.encode("π"), b"&#960;");
+}
@@ -0,0 +1,223 @@
+//! The grammar each language declares through `Language::syntax` must
+//! accept exactly the programs its hand-written parser accepts.
+
+use std::fs;
+use std::thread;
+
+use visual_tho::interpreter::diagnostics::codes;
+use visual_tho::interpreter::encoding::SourceFormat;
+use visual_tho::interpreter::languages;
+use visual_tho::interpreter::runner;
+use visual_tho::interpreter::syntaxer::Syntaxer;
+use visual_tho::interpreter::tokenizer::Tokenizer;
+
+/// Errors a parser reports for well-formed programs, which no grammar
+/// describes: a library that is not available and a missing `inicio`.
+const BEYOND_GRAMMAR:&[&str] = &[codes::UNSUPPORTED_LIBRARY, codes::MISSING_ENTRY_POINT];
+
+/// Whether the grammar and the parser of `language` accept `code`.
+fn verdicts(language:&str, code:&str) -> (bool, bool) {
+    let mut tokenizer = Tokenizer::new(languages::by_name(language).expect("registered language"));
+    tokenizer.tokenize(code);
+    let syntaxer = Syntaxer::new(&tokenizer);
+
+    let grammar = tokenizer.language().syntax();
+    let parser = match syntaxer.parse() {
+        Ok(_) => true,
+        Err(errors) => errors.iter().all(|e| BEYOND_GRAMMAR.contains(&e.code())),
+    };
+    (syntaxer.validate_syntax(&grammar).is_ok(), parser)
+}
+
+fn assert_agree(language:&str, name:&str, code:&str, accepted:bool) {
+    let (grammar, parser) = verdicts(language, code);
+    assert_eq!(parser, accepted, "{}: o parser deveria {}aceitar", name, if accepted { "" } else { "não " });
+    assert_eq!(grammar, parser, "{}: a gramática {}aceita, o parser não", name, if grammar { "" } else { "não " });
+}
+
+/// Every script of `directory`, decoded as the interpreter loads it.
+fn scripts(directory:&str, extension:&str) -> Vec<(String, String)> {
+    let mut scripts:Vec<_> = fs::read_dir(directory).expect("scripts directory")
+        .map(|entry| entry.expect("script entry").path())
+        .filter(|path| path.extension().is_some_and(|e| e == extension))
+        .collect();
+    scripts.sort();
+
+    scripts.into_iter()
+        .map(|path| {
+            let (code, _) = SourceFormat::decode(&fs::read(&path).expect("readable script"));
+            (path.display().to_string(), code)
+        })
+        .collect()
+}
+
+const VISUALG_VALID:&[(&str, &str)] = &[
+    ("vazio", "algoritmo \"a\"\ninicio\nfimalgoritmo\n"),
+    ("vetor e subprogramas", "\
+algoritmo \"b\"
+var
+   v: vetor[1..3, 0..1] de inteiro
+   i: inteiro
+procedimento mostra(var x: inteiro; y: real)
+inicio
+   escreval(x:3, y:5:2)
+fimprocedimento
+funcao dobro(n: inteiro): inteiro
+inicio
+   retorne n * 2
+fimfuncao
+inicio
+   para i de 1 ate 3 passo 1 faca
+      v[i, 0] <- dobro(i)
+      mostra(v[i, 0], 1.5)
+   fimpara
+fimalgoritmo
+"),
+    ("controle", "\
+algoritmo \"c\"
+var
+   n: inteiro
+   s: caractere
+inicio
+   leia(n, s)
+   se (n > 0) e nao (s = \"x\") entao
+      escreva(\"positivo\")
+   senao
+      escreva(\"outro\")
+   fimse
+   enquanto n > 0 faca
+      n <- n - 1
+      se n = 3 entao
+         interrompa
+      fimse
+   fimenquanto
+   repita
+      n <- n + 1
+   ate n >= 5
+   escolha n
+   caso 1, 2
+      escreval(\"pouco\")
+   caso 3 ate 9
+      escreval(\"médio\")
+   outrocaso
+      escreval(\"muito\")
+   fimescolha
+fimalgoritmo
+"),
+];
+
+const VISUALG_INVALID:&[(&str, &str)] = &[
+    ("sem inicio", "algoritmo \"a\"\nvar\n   x: inteiro\nfimalgoritmo\n"),
+    ("se sem fimse", "algoritmo \"a\"\ninicio\nse verdadeiro entao\nescreva(1)\nfimalgoritmo\n"),
+    ("atribuição sem valor", "algoritmo \"a\"\nvar\n   x: inteiro\ninicio\nx <-\nfimalgoritmo\n"),
+    ("declaração sem tipo", "algoritmo \"a\"\nvar\n   x:\ninicio\nfimalgoritmo\n"),
+    ("para sem ate", "algoritmo \"a\"\nvar\n   i: inteiro\ninicio\npara i de 1 faca\nfimpara\nfimalgoritmo\n"),
+    ("parêntese aberto", "algoritmo \"a\"\ninicio\nescreva((1 + 2)\nfimalgoritmo\n"),
+    ("conteúdo depois do fim", "algoritmo \"a\"\ninicio\nfimalgoritmo\nescreva(1)\n"),
+];
+
+#[test]
+fn visualg_grammar_agrees_with_parser_on_scripts() {
+    for (name, code) in scripts("tests/scripts/visualg", "alg") {
+        let (grammar, parser) = verdicts("visualg", &code);
+        assert_eq!(grammar, parser, "{}: gramática {} e parser {}", name, grammar, parser);
+    }
+}
+
+#[test]
+fn visualg_grammar_agrees_with_parser() {
+    for (name, code) in VISUALG_VALID {
+        assert_agree("visualg", name, code, true);
+    }
+    for (name, code) in VISUALG_INVALID {
+        assert_agree("visualg", name, code, false);
+    }
+}
+
+#[test]
+fn portugol_grammar_agrees_with_parser_on_scripts() {
+    for (name, code) in scripts("tests/scripts/portugol", "por") {
+        let (grammar, parser) = verdicts("portugol", &code);
+        assert_eq!(grammar, parser, "{}: gramática {} e parser {}", name, grammar, parser);
+    }
+}
+
+const PORTUGOL_VALID:&[(&str, &str)] = &[
+    ("vazio", "programa {\n\tfuncao inicio() {\n\t}\n}\n"),
+    ("controle", "\
+programa {
+\tinteiro v[3] = {1, 2, 3}
+\tfuncao inteiro soma(inteiro a, inteiro &b) {
+\t\tretorne a + b
+\t}
+\tfuncao inicio() {
+\t\tpara (inteiro i = 0; i < 3; i++) {
+\t\t\tse (v[i] % 2 == 0 ou nao (i > 1)) {
+\t\t\t\tescreva(soma(i, v[i]), \"\\n\")
+\t\t\t} senao se (i == 2) {
+\t\t\t\tpare
+\t\t\t} senao {
+\t\t\t\tv[i] += 1
+\t\t\t}
+\t\t}
+\t\tescolha (v[0]) {
+\t\t\tcaso 1:
+\t\t\t\tescreva(\"um\")
+\t\t\t\tpare
+\t\t\tcaso contrario:
+\t\t\t\tescreva(\"outro\")
+\t\t}
+\t}
+}
+"),
+];
+
+const PORTUGOL_INVALID:&[(&str, &str)] = &[
+    ("sem chave final", "programa {\n\tfuncao inicio() {\n\t}\n"),
+    ("se sem parênteses", "programa {\n\tfuncao inicio() {\n\t\tse verdadeiro {\n\t\t}\n\t}\n}\n"),
+    ("atribuição sem valor", "programa {\n\tfuncao inicio() {\n\t\tinteiro x\n\t\tx =\n\t}\n}\n"),
+    ("para incompleto", "programa {\n\tfuncao inicio() {\n\t\tpara (inteiro i = 0; i < 3) {\n\t\t}\n\t}\n}\n"),
+];
+
+#[test]
+fn portugol_grammar_agrees_with_parser() {
+    for (name, code) in PORTUGOL_VALID {
+        assert_agree("portugol", name, code, true);
+    }
+    for (name, code) in PORTUGOL_INVALID {
+        assert_agree("portugol", name, code, false);
+    }
+}
+
+/// Codes of the errors matching `code` against its grammar gives, on a
+/// thread with the stack the CLI and the GUI give it.
+fn grammar_errors(language:&'static str, code:String) -> Vec<&'static str> {
+    thread::Builder::new().stack_size(runner::stack_size(1)).spawn(move || {
+        let mut tokenizer = Tokenizer::new(languages::by_name(language).expect("registered language"));
+        tokenizer.tokenize(&code);
+        match Syntaxer::new(&tokenizer).validate_syntax(&tokenizer.language().syntax()) {
+            Ok(_) => vec![],
+            Err(errors) => errors.iter().map(|e| e.code()).collect(),
+        }
+    }).expect("grammar thread").join().expect("grammar finished")
+}
+
+#[test]
+fn grammar_stops_past_the_nesting_limit() {
+    let nested = |depth:usize| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+    let visualg = |e:String| format!("algoritmo \"a\"\nvar\n   x: inteiro\ninicio\nx <- {}\nfimalgoritmo\n", e);
+    let portugol = |e:String| format!("programa {{\n\tfuncao inicio() {{\n\t\tinteiro x = {}\n\t}}\n}}\n", e);
+
+    assert_eq!(grammar_errors("visualg", visualg(nested(100))), Vec::<&str>::new());
+    assert_eq!(grammar_errors("visualg", visualg(nested(2000))), vec![codes::NESTING_TOO_DEEP]);
+    assert_eq!(grammar_errors("portugol", portugol(nested(100))), Vec::<&str>::new());
+    assert_eq!(grammar_errors("portugol", portugol(nested(2000))), vec![codes::NESTING_TOO_DEEP]);
+
+    let blocks = |depth:usize| format!("{}escreva(1){}", "se verdadeiro entao\n".repeat(depth), "\nfimse".repeat(depth));
+    let code = format!("algoritmo \"a\"\ninicio\n{}\nfimalgoritmo\n", blocks(2000));
+    assert_eq!(grammar_errors("visualg", code), vec![codes::NESTING_TOO_DEEP]);
+
+    let blocks = |depth:usize| format!("{}escreva(1){}", "se (verdadeiro) {\n".repeat(depth), "\n}".repeat(depth));
+    let code = format!("programa {{\n\tfuncao inicio() {{\n{}\n\t}}\n}}\n", blocks(2000));
+    assert_eq!(grammar_errors("portugol", code), vec![codes::NESTING_TOO_DEEP]);
+}
@@ -0,0 +1,203 @@
+//! The `--json` reports other tools read. Their fields are the contract
+//! [`FORMAT_VERSION`] versions, so renaming one must fail here first.
+
+use std::collections::BTreeSet;
+
+use serde_json::Value as Json;
+use visual_tho::cli::json::{Report, FORMAT_VERSION};
+use visual_tho::interpreter::console::memory::MemoryIo;
+use visual_tho::interpreter::interpreter::Interpreter;
+use visual_tho::interpreter::languages;
+
+const PROGRAM:&str = "algoritmo \"j\"\nvar\n   x: inteiro\ninicio\n   x <- 2\n   escreval(x)\nfimalgoritmo\n";
+
+fn interpreter(code:&str) -> Interpreter {
+    Interpreter::from_code(languages::by_name("visualg").expect("visualg"), code.to_string())
+}
+
+fn parse(report:&Report) -> Json {
+    serde_json::from_str(&report.to_json()).expect("report is JSON")
+}
+
+/// Every path to a field under `value`, arrays written as `[]`. Spans are
+/// pinned once, by [`SPAN`], rather than under every field that has one.
+fn schema(value:&Json) -> Vec<String> {
+    fn walk(value:&Json, path:String, paths:&mut BTreeSet<String>) {
+        match value {
+            Json::Object(fields) => {
+                for (name, field) in fields {
+                    let path = if path.is_empty() { name.clone() } else { format!("{}.{}", path, name) };
+                    paths.insert(path.clone());
+                    if name != "span" {
+                        walk(field, path, paths);
+                    }
+                }
+            },
+            Json::Array(items) => {
+                for item in items {
+                    walk(item, format!("{}[]", path), paths);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    let mut paths = BTreeSet::new();
+    walk(value, String::new(), &mut paths);
+    paths.into_iter().collect()
+}
+
+/// Fields of every span, wherever one appears.
+const SPAN:&[&str] = &[
+    "end", "end.byte", "end.char", "end.column", "end.line",
+    "start", "start.byte", "start.char", "start.column", "start.line",
+];
+
+/// Fields every report has, whatever the command.
+const HEADER:&[&str] = &["command", "diagnostics", "exit_code", "file", "success", "version"];
+
+/// [`HEADER`] and `fields`, sorted as [`schema`] lists them.
+fn with_header(fields:&[&str]) -> Vec<String> {
+    let mut all:Vec<String> = HEADER.iter().chain(fields).map(|f| f.to_string()).collect();
+    all.sort();
+    all
+}
+
+#[test]
+fn header_carries_the_format_version() {
+    assert_eq!(FORMAT_VERSION, 1);
+
+    let json = parse(&Report::new("check", "j.alg", 0, vec![]));
+    assert_eq!(json["version"], FORMAT_VERSION);
+    assert_eq!(json["command"], "check");
+    assert_eq!(json["file"], "j.alg");
+    assert_eq!(json["success"], true);
+    assert_eq!(json["exit_code"], 0);
+    assert_eq!(schema(&json), with_header(&[]));
+}
+
+#[test]
+fn spans_keep_their_fields() {
+    let mut interpreter = interpreter(PROGRAM);
+    interpreter.tokenize().expect("tokens");
+    let json = parse(&Report::new("tokens", "j.alg", 0, vec![]).with_tokens(interpreter.tokens().map(|t| t.as_slice())));
+    assert_eq!(schema(&json["tokens"][0]["span"]), SPAN);
+}
+
+#[test]
+fn token_fields() {
+    let mut interpreter = interpreter(PROGRAM);
+    interpreter.tokenize().expect("tokens");
+    let json = parse(&Report::new("tokens", "j.alg", 0, vec![]).with_tokens(interpreter.tokens().map(|t| t.as_slice())));
+
+    assert_eq!(schema(&json), with_header(&[
+        "tokens",
+        "tokens[].count",
+        "tokens[].literal",
+        "tokens[].literal.integer",
+        "tokens[].literal.string",
+        "tokens[].schema",
+        "tokens[].span",
+        "tokens[].value",
+    ]));
+}
+
+#[test]
+fn ast_fields() {
+    let mut interpreter = interpreter(PROGRAM);
+    interpreter.parse().expect("program");
+    let json = parse(&Report::new("ast", "j.alg", 0, vec![]).with_ast(interpreter.program()));
+
+    assert_eq!(schema(&json), with_header(&[
+        "ast",
+        "ast.body",
+        "ast.body[].kind",
+        "ast.body[].kind.assign",
+        "ast.body[].kind.assign.target",
+        "ast.body[].kind.assign.target.indices",
+        "ast.body[].kind.assign.target.name",
+        "ast.body[].kind.assign.target.span",
+        "ast.body[].kind.assign.value",
+        "ast.body[].kind.assign.value.kind",
+        "ast.body[].kind.assign.value.kind.literal",
+        "ast.body[].kind.assign.value.kind.literal.integer",
+        "ast.body[].kind.assign.value.span",
+        "ast.body[].kind.write",
+        "ast.body[].kind.write.arguments",
+        "ast.body[].kind.write.arguments[].precision",
+        "ast.body[].kind.write.arguments[].span",
+        "ast.body[].kind.write.arguments[].value",
+        "ast.body[].kind.write.arguments[].value.kind",
+        "ast.body[].kind.write.arguments[].value.kind.variable",
+        "ast.body[].kind.write.arguments[].value.kind.variable.name",
+        "ast.body[].kind.write.arguments[].value.kind.variable.span",
+        "ast.body[].kind.write.arguments[].value.span",
+        "ast.body[].kind.write.arguments[].width",
+        "ast.body[].kind.write.newline",
+        "ast.body[].span",
+        "ast.declarations",
+        "ast.declarations[].names",
+        "ast.declarations[].names[].name",
+        "ast.declarations[].names[].span",
+        "ast.declarations[].span",
+        "ast.declarations[].type_spec",
+        "ast.declarations[].type_spec.dimensions",
+        "ast.declarations[].type_spec.kind",
+        "ast.declarations[].type_spec.span",
+        "ast.name",
+        "ast.span",
+        "ast.subprograms",
+    ]));
+}
+
+#[test]
+fn diagnostic_fields() {
+    // A second declaration, with a secondary label, and a block left open,
+    // with a suggestion.
+    let declared_twice = interpreter("algoritmo \"j\"\nvar\n   x: inteiro\n   x: real\ninicio\nfimalgoritmo\n").check().unwrap_err();
+    let unclosed = interpreter("algoritmo \"j\"\ninicio\nse verdadeiro entao\nfimalgoritmo\n").parse().unwrap_err();
+    let diagnostics = declared_twice.iter().chain(&unclosed).collect();
+    let json = parse(&Report::new("check", "j.alg", 1, diagnostics));
+
+    assert_eq!(json["success"], false);
+    assert_eq!(json["diagnostics"][0]["code"], "E0302");
+    assert_eq!(json["diagnostics"][0]["severity"], "error");
+    assert_eq!(schema(&json), with_header(&[
+        "diagnostics[].code",
+        "diagnostics[].label",
+        "diagnostics[].message",
+        "diagnostics[].notes",
+        "diagnostics[].secondary",
+        "diagnostics[].secondary[].message",
+        "diagnostics[].secondary[].span",
+        "diagnostics[].severity",
+        "diagnostics[].span",
+        "diagnostics[].suggestion",
+        "diagnostics[].suggestion.message",
+        "diagnostics[].suggestion.replacement",
+        "diagnostics[].suggestion.span",
+    ]));
+}
+
+#[test]
+fn run_fields() {
+    let mut interpreter = interpreter(PROGRAM);
+    let mut io = MemoryIo::new("");
+    let outcome = interpreter.run_with(&mut io).expect("run");
+    let json = parse(&Report::new("run", "j.alg", 0, vec![]).with_run(io.output(), Some(&outcome)));
+
+    assert_eq!(json["run"]["output"], " 2\n");
+    assert_eq!(json["run"]["variables"][0]["name"], "x");
+    assert_eq!(json["run"]["variables"][0]["value"]["inteiro"], 2);
+    assert_eq!(schema(&json), with_header(&[
+        "run",
+        "run.output",
+        "run.variables",
+        "run.variables[].name",
+        "run.variables[].value",
+        "run.variables[].value.inteiro",
+    ]));
+
+    let json = parse(&Report::new("run", "j.alg", 1, vec![]).with_run("", None));
+    assert_eq!(json["run"]["variables"], Json::Null);
+}
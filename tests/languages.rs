@@ -0,0 +1,59 @@
+//! Which language a program is read as, from its file and its first words.
+
+use visual_tho::interpreter::language::Language;
+use visual_tho::interpreter::languages;
+
+fn name(language:Option<Box<dyn Language>>) -> Option<String> {
+    language.map(|l| l.name().to_string())
+}
+
+#[test]
+fn registered_names() {
+    assert_eq!(languages::names(), vec!["visualg", "portugol"]);
+    for registered in languages::names() {
+        let language = languages::by_name(&registered.to_uppercase()).expect("registered language");
+        assert_eq!(name(languages::instance_of(language.as_ref())), Some(language.name().to_string()));
+    }
+    assert!(languages::by_name("pascal").is_none());
+}
+
+#[test]
+fn detection_by_extension() {
+    let visualg = name(languages::by_name("visualg"));
+    let portugol = name(languages::by_name("portugol"));
+
+    assert_eq!(name(languages::by_extension("soma.alg")), visualg);
+    assert_eq!(name(languages::by_extension("pasta/SOMA.ALG")), visualg);
+    assert_eq!(name(languages::by_extension("soma.por")), portugol);
+    for shared in ["soma.txt", "soma", "soma.alg.bak", ".alg"] {
+        assert_eq!(name(languages::by_extension(shared)), None, "{}", shared);
+    }
+}
+
+#[test]
+fn detection_by_content() {
+    let visualg = name(languages::by_name("visualg"));
+    let portugol = name(languages::by_name("portugol"));
+
+    assert_eq!(name(languages::by_content("algoritmo \"a\"\ninicio\nfimalgoritmo\n")), visualg);
+    assert_eq!(name(languages::by_content("// soma\n\n   ALGORITMO \"a\"")), visualg);
+    assert_eq!(name(languages::by_content("programa {\n}")), portugol);
+    assert_eq!(name(languages::by_content("/* exemplo\n*/ programa{ }")), portugol);
+    for unknown in ["", "// só comentário", "Programa {", "programa x", "inicio\nfimalgoritmo"] {
+        assert_eq!(name(languages::by_content(unknown)), None, "{:?}", unknown);
+    }
+}
+
+#[test]
+fn content_wins_over_extension() {
+    let visualg = name(languages::by_name("visualg"));
+    let portugol = name(languages::by_name("portugol"));
+    let detect = |path:Option<&str>, code:&str| Some(languages::detect(path, code).name().to_string());
+
+    assert_eq!(detect(Some("exercicio.txt"), "programa {\n}"), portugol);
+    assert_eq!(detect(Some("exercicio.alg"), "programa {\n}"), portugol);
+    assert_eq!(detect(Some("exercicio.por"), ""), portugol);
+    assert_eq!(detect(Some("exercicio.txt"), ""), visualg);
+    assert_eq!(detect(None, "algoritmo \"a\""), visualg);
+    assert_eq!(detect(None, ""), visualg);
+}
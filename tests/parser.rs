@@ -0,0 +1,66 @@
+//! Parser limits, on a thread with the stack the CLI and the GUI give it.
+
+use std::thread;
+
+use visual_tho::interpreter::diagnostics::codes;
+use visual_tho::interpreter::languages;
+use visual_tho::interpreter::runner;
+use visual_tho::interpreter::syntaxer::Syntaxer;
+use visual_tho::interpreter::tokenizer::Tokenizer;
+
+/// Codes of the errors parsing `code` gives.
+fn errors(language:&'static str, code:String) -> Vec<&'static str> {
+    thread::Builder::new().stack_size(runner::stack_size(1)).spawn(move || {
+        let mut tokenizer = Tokenizer::new(languages::by_name(language).expect("language"));
+        tokenizer.tokenize(&code);
+        match Syntaxer::new(&tokenizer).parse() {
+            Ok(_) => vec![],
+            Err(errors) => errors.iter().map(|e| e.code()).collect(),
+        }
+    }).expect("parser thread").join().expect("parser finished")
+}
+
+fn visualg(body:&str) -> String {
+    format!("algoritmo \"aninhado\"\nvar\n   x: inteiro\ninicio\n{}\nfimalgoritmo\n", body)
+}
+
+#[test]
+fn visualg_nested_expressions_stop_at_the_limit() {
+    let nested = |depth:usize| visualg(&format!("x <- {}1{}", "(".repeat(depth), ")".repeat(depth)));
+
+    assert_eq!(errors("visualg", nested(50)), Vec::<&str>::new());
+    assert_eq!(errors("visualg", nested(20_000)), vec![codes::NESTING_TOO_DEEP]);
+    assert_eq!(errors("visualg", visualg(&format!("x <- {}1", "-".repeat(20_000)))), vec![codes::NESTING_TOO_DEEP]);
+    assert_eq!(errors("visualg", visualg(&format!("x <- {}2", "2^".repeat(20_000)))), vec![codes::NESTING_TOO_DEEP]);
+}
+
+#[test]
+fn visualg_nested_blocks_stop_at_the_limit() {
+    let nested = |depth:usize| visualg(&format!("{}{}", "se x = 1 entao\n".repeat(depth), "fimse\n".repeat(depth)));
+
+    assert_eq!(errors("visualg", nested(50)), Vec::<&str>::new());
+    assert_eq!(errors("visualg", nested(20_000)), vec![codes::NESTING_TOO_DEEP]);
+}
+
+fn portugol(body:&str) -> String {
+    format!("programa\n{{\n\tfuncao inicio()\n\t{{\n\t\tinteiro x = 1\n{}\n\t}}\n}}\n", body)
+}
+
+#[test]
+fn portugol_nested_expressions_stop_at_the_limit() {
+    let nested = |depth:usize| portugol(&format!("x = {}1{}", "(".repeat(depth), ")".repeat(depth)));
+
+    assert_eq!(errors("portugol", nested(50)), Vec::<&str>::new());
+    assert_eq!(errors("portugol", nested(20_000)), vec![codes::NESTING_TOO_DEEP]);
+    assert_eq!(errors("portugol", portugol(&format!("x = {}1", "- ".repeat(20_000)))), vec![codes::NESTING_TOO_DEEP]);
+}
+
+#[test]
+fn portugol_nested_blocks_stop_at_the_limit() {
+    let braces = |depth:usize| portugol(&format!("{}{}", "se (x == 1) {\n".repeat(depth), "}\n".repeat(depth)));
+    let bodies = |depth:usize| portugol(&format!("{}x = 2", "enquanto (x == 1)\n".repeat(depth)));
+
+    assert_eq!(errors("portugol", braces(50)), Vec::<&str>::new());
+    assert_eq!(errors("portugol", braces(20_000)), vec![codes::NESTING_TOO_DEEP]);
+    assert_eq!(errors("portugol", bodies(20_000)), vec![codes::NESTING_TOO_DEEP]);
+}
@@ -0,0 +1,85 @@
+//! Random numbers recorded by one run and replayed by another, through the
+//! library and through `--record-random` and `--replay-random`.
+
+use std::env;
+use std::fs;
+use std::process::{Command, Stdio};
+
+use visual_tho::interpreter::console::memory::MemoryIo;
+use visual_tho::interpreter::interpreter::Interpreter;
+use visual_tho::interpreter::languages;
+use visual_tho::interpreter::random::{Draw, RandomSource, Recorder, Replay, SeededRandom};
+
+const PROGRAM:&str = "\
+algoritmo \"sorteio\"
+var
+   i: inteiro
+inicio
+   para i de 1 ate 3 faca
+      escreval(randi(100), rand)
+   fimpara
+fimalgoritmo
+";
+
+/// What [`PROGRAM`] prints drawing from `random`.
+fn output(random:&mut dyn RandomSource) -> String {
+    let mut interpreter = Interpreter::from_code(languages::by_name("visualg").expect("visualg"), PROGRAM.to_string());
+    let mut io = MemoryIo::new("");
+    if let Err(errors) = interpreter.run_with_random(&mut io, random) {
+        panic!("{:?}", errors);
+    }
+    io.output().to_string()
+}
+
+#[test]
+fn replay_repeats_a_recorded_run() {
+    let mut recorder = Recorder::new(SeededRandom::new(7));
+    let recorded = output(&mut recorder);
+    assert_eq!(recorder.draws().len(), 6);
+
+    let mut replay = Replay::parse(&recorder.log()).expect("log parses");
+    assert_eq!(output(&mut replay), recorded);
+    assert!(replay.real().is_err(), "a gravação deveria ter acabado");
+}
+
+#[test]
+fn replay_rejects_values_past_the_limit() {
+    let mut replay = Replay::new(vec![Draw::Integer { limit: 10, value: 10 }, Draw::Integer { limit: 10, value: 9 }]);
+    assert!(replay.below(10).is_err());
+    assert_eq!(replay.below(10), Ok(9));
+
+    let mut replay = Replay::new(vec![Draw::Integer { limit: 10, value: 3 }]);
+    assert!(replay.below(20).is_err());
+    assert!(Replay::new(vec![Draw::Real(0.5)]).below(10).is_err());
+}
+
+/// Run the CLI on `arguments`, returning its status and what it printed.
+fn cli(arguments:&[&str]) -> (bool, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_visual_tho"))
+        .args(arguments)
+        .stdin(Stdio::null())
+        .output()
+        .expect("CLI runs");
+    (output.status.success(), String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[test]
+fn cli_replays_what_it_recorded() {
+    let directory = env::temp_dir().join(format!("visual_tho_random_{}", std::process::id()));
+    fs::create_dir_all(&directory).expect("temporary directory");
+    let program = directory.join("sorteio.alg");
+    let log = directory.join("sorteio.log");
+    fs::write(&program, PROGRAM).expect("program written");
+    let (program, log) = (program.to_str().expect("UTF-8 path"), log.to_str().expect("UTF-8 path"));
+
+    let (recorded, printed) = cli(&["run", program, "--record-random", log]);
+    assert!(recorded, "{}", printed);
+    assert_eq!(Replay::parse(&fs::read_to_string(log).expect("log written")).map(|_| ()), Ok(()));
+
+    assert_eq!(cli(&["run", program, "--replay-random", log]), (true, printed));
+
+    fs::write(log, "inteiro 100 100\n").expect("log rewritten");
+    assert!(!cli(&["run", program, "--replay-random", log]).0);
+
+    fs::remove_dir_all(&directory).expect("temporary directory removed");
+}
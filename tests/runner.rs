@@ -0,0 +1,152 @@
+//! Small programs run through `MemoryIo`, checking what they print.
+
+use visual_tho::interpreter::console::memory::MemoryIo;
+use visual_tho::interpreter::diagnostics::codes;
+use visual_tho::interpreter::interpreter::Interpreter;
+use visual_tho::interpreter::languages;
+
+/// Codes of the errors running `code` stops with, and what it printed.
+fn run(language:&str, code:&str) -> (Vec<&'static str>, String) {
+    let mut interpreter = Interpreter::from_code(languages::by_name(language).expect("language"), code.to_string());
+    let mut io = MemoryIo::new("");
+    let errors = match interpreter.run_with(&mut io) {
+        Ok(_) => vec![],
+        Err(errors) => errors.iter().map(|e| e.code()).collect(),
+    };
+    (errors, io.output().to_string())
+}
+
+/// What `code` prints when run.
+fn output(language:&str, code:&str) -> String {
+    match run(language, code) {
+        (errors, output) if errors.is_empty() => output,
+        (errors, _) => panic!("{:?}", errors),
+    }
+}
+
+#[test]
+fn visualg_pads_only_non_negative_numbers() {
+    let code = "\
+algoritmo \"sinais\"
+var
+   i: inteiro
+   r: real
+inicio
+   i <- -2
+   r <- -1.5
+   escreval(i, 3, r, 0.5)
+   escreval(-i, -r, -0.0)
+   escreval(i:3, \"|\", r:5:1)
+fimalgoritmo
+";
+    assert_eq!(output("visualg", code), "-2 3-1.5 0.5\n 2 1.5-0\n -2| -1.5\n");
+}
+
+/// Arrays and subprograms taking them, then `body`.
+fn arrays(body:&str) -> String {
+    format!("\
+algoritmo \"vetores\"
+var
+   v: vetor[1..3] de inteiro
+   longo: vetor[1..5] de inteiro
+   m: vetor[1..2, 1..2] de real
+procedimento mostra(w: vetor[1..3] de inteiro)
+inicio
+   w[1] <- 100
+   escreval(w[1], w[2], w[3])
+fimprocedimento
+procedimento muda(var w: vetor[1..3] de inteiro)
+inicio
+   w[2] <- 50
+fimprocedimento
+procedimento reais(w: vetor[1..3] de real)
+inicio
+   escreval(w[1] + 0.5)
+fimprocedimento
+funcao soma(a: vetor[1..2, 1..2] de real): real
+var
+   i, j: inteiro
+   total: real
+inicio
+   para i de 1 ate 2 faca
+      para j de 1 ate 2 faca
+         total <- total + a[i, j]
+      fimpara
+   fimpara
+   retorne total
+fimfuncao
+inicio
+   v[1] <- 7
+   v[2] <- 8
+   v[3] <- 9
+{}
+fimalgoritmo
+", body)
+}
+
+#[test]
+fn vetor_parameters_copy_the_whole_array() {
+    assert_eq!(output("visualg", &arrays("   mostra(v)\n   escreval(v[1], v[2], v[3])")), " 100 8 9\n 7 8 9\n");
+    assert_eq!(output("visualg", &arrays("   reais(v)")), " 7.5\n");
+    assert_eq!(output("visualg", &arrays("   m[1, 1] <- 1.5\n   m[2, 2] <- 2\n   escreval(soma(m))")), " 3.5\n");
+}
+
+#[test]
+fn var_vetor_parameters_share_the_array() {
+    assert_eq!(output("visualg", &arrays("   muda(v)\n   escreval(v[1], v[2], v[3])")), " 7 50 9\n");
+}
+
+#[test]
+fn vetor_parameters_need_the_same_bounds() {
+    assert_eq!(run("visualg", &arrays("   mostra(longo)")), (vec![codes::ASSIGNMENT_MISMATCH], String::new()));
+    assert_eq!(run("visualg", &arrays("   muda(longo)")).0, vec![codes::ASSIGNMENT_MISMATCH]);
+}
+
+#[test]
+fn vetor_parameters_reject_single_values() {
+    for call in ["mostra(v[1])", "mostra(v[1] + 1)", "muda(v[2])", "soma(m[1, 1])"] {
+        assert_eq!(run("visualg", &arrays(&format!("   {}", call))).0, vec![codes::ARGUMENT_TYPE], "{}", call);
+    }
+}
+
+#[test]
+fn negating_the_smallest_inteiro_wraps() {
+    let code = "algoritmo \"n\"\nvar\n   x: inteiro\ninicio\n   x <- -9223372036854775807 - 1\n   escreval(-x)\nfimalgoritmo\n";
+    assert_eq!(output("visualg", code), "-9223372036854775808\n");
+
+    let code = "programa {\n   funcao inicio() {\n      inteiro x = -9223372036854775807 - 1\n      escreva(-x)\n   }\n}\n";
+    assert_eq!(output("portugol", code), "-9223372036854775808");
+}
+
+#[test]
+fn para_ends_at_the_largest_inteiro() {
+    let code = "\
+algoritmo \"p\"
+var
+   i: inteiro
+inicio
+   para i de 9223372036854775806 ate 9223372036854775807 faca
+      escreval(i)
+   fimpara
+   escreval(i)
+   para i de -9223372036854775807 ate -9223372036854775807 - 1 passo -1 faca
+      escreva(i)
+   fimpara
+fimalgoritmo
+";
+    assert_eq!(
+        output("visualg", code),
+        " 9223372036854775806\n 9223372036854775807\n 9223372036854775807\n-9223372036854775807-9223372036854775808"
+    );
+}
+
+#[test]
+fn huge_widths_and_precisions_are_capped() {
+    use visual_tho::interpreter::runner::MAX_FORMAT_WIDTH;
+
+    let code = "algoritmo \"w\"\ninicio\n   escreval(1:9000000000000)\n   escreval(1.5:1:9000000000000)\nfimalgoritmo\n";
+    let printed = output("visualg", code);
+    let lines:Vec<&str> = printed.lines().collect();
+    assert_eq!(lines[0], format!("{:>1$}", 1, MAX_FORMAT_WIDTH));
+    assert_eq!(lines[1], format!("{:.1$}", 1.5, MAX_FORMAT_WIDTH));
+}
@@ -0,0 +1,62 @@
+//! Runs every program under `tests/scripts/<language>/` and compares what it
+//! prints with the `.out` file beside it. A `.in` file answers `leia`, one
+//! line per read, and a `.err` file lists the codes of the errors the
+//! program must stop with, one per line.
+
+use std::fs;
+use std::path::Path;
+
+use visual_tho::interpreter::console::memory::MemoryIo;
+use visual_tho::interpreter::interpreter::Interpreter;
+use visual_tho::interpreter::random::SeededRandom;
+
+/// Seed every script runs with, so `rand` and `randi` repeat.
+const SEED:u64 = 42;
+
+/// Differences from the expected files, one message per script.
+fn run_scripts(directory:&str, extension:&str) -> Vec<String> {
+    let mut scripts:Vec<_> = fs::read_dir(directory).expect("scripts directory")
+        .map(|entry| entry.expect("script entry").path())
+        .filter(|path| path.extension().is_some_and(|e| e == extension))
+        .collect();
+    scripts.sort();
+    assert!(!scripts.is_empty(), "nenhum script em {}", directory);
+
+    scripts.iter().filter_map(|script| check_script(script)).collect()
+}
+
+fn check_script(script:&Path) -> Option<String> {
+    let read = |extension:&str| fs::read_to_string(script.with_extension(extension)).ok();
+    let expected_output = read("out").unwrap_or_else(|| panic!("{} sem arquivo .out", script.display()));
+    let expected_errors:Vec<String> = read("err").unwrap_or_default().lines().map(String::from).collect();
+
+    let mut interpreter = Interpreter::for_file(script.display().to_string());
+    let mut io = MemoryIo::new(&read("in").unwrap_or_default());
+    let errors:Vec<String> = match interpreter.run_with_random(&mut io, &mut SeededRandom::new(SEED)) {
+        Ok(_) => vec![],
+        Err(errors) => errors.iter().map(|e| e.code().to_string()).collect(),
+    };
+
+    if io.output() != expected_output {
+        return Some(format!(
+            "{}: saída diferente\n--- esperada\n{}\n--- obtida\n{}",
+            script.display(), expected_output, io.output()
+        ));
+    }
+    if errors != expected_errors {
+        return Some(format!("{}: erros {:?}, esperados {:?}", script.display(), errors, expected_errors));
+    }
+    None
+}
+
+#[test]
+fn visualg_scripts_print_expected_output() {
+    let failures = run_scripts("tests/scripts/visualg", "alg");
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
+
+#[test]
+fn portugol_scripts_print_expected_output() {
+    let failures = run_scripts("tests/scripts/portugol", "por");
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
@@ -0,0 +1,282 @@
+//! Spans and delimited runs of the tokenizer.
+
+use std::fs;
+
+use visual_tho::interpreter::ast::expression::{Expression, Literal};
+use visual_tho::interpreter::ast::program::Program;
+use visual_tho::interpreter::builtin::Builtin;
+use visual_tho::interpreter::diagnostics::codes;
+use visual_tho::interpreter::diagnostics::diagnostic::Diagnostic;
+use visual_tho::interpreter::language::Language;
+use visual_tho::interpreter::languages;
+use visual_tho::interpreter::rules::rule::SyntaxGroup;
+use visual_tho::interpreter::tokenizer::Tokenizer;
+use visual_tho::interpreter::tokens::schema::TokenTypes;
+use visual_tho::interpreter::tokens::separator::{SeparatorSetting, TokenSeparators};
+use visual_tho::interpreter::tokens::span::{Position, Span};
+use visual_tho::interpreter::tokens::token::Token;
+use visual_tho::interpreter::value::Value;
+
+fn tokenize(language:&str, code:&str) -> Tokenizer {
+    let mut tokenizer = Tokenizer::new(languages::by_name(language).expect("language"));
+    tokenizer.tokenize(code);
+    tokenizer
+}
+
+fn find<'a>(tokenizer:&'a Tokenizer, value:&str) -> &'a Token {
+    tokenizer.tokens().iter().find(|t| t.value() == value).unwrap_or_else(|| panic!("token {:?}", value))
+}
+
+fn span(start:(usize, usize, usize, usize), end:(usize, usize, usize, usize)) -> Span {
+    Span::new(Position::new(start.0, start.1, start.2, start.3), Position::new(end.0, end.1, end.2, end.3))
+}
+
+#[test]
+fn spans_count_bytes_chars_lines_and_columns() {
+    let tokenizer = tokenize("visualg", "nome <- \"ação\" // é\nsaída <- 1\n");
+
+    let string = find(&tokenizer, "\"ação\"");
+    assert_eq!(*string.schema(), TokenTypes::String);
+    assert_eq!(*string.span(), span((8, 8, 1, 9), (16, 14, 1, 15)));
+
+    let comment = find(&tokenizer, "// é");
+    assert_eq!(*comment.schema(), TokenTypes::LineComment);
+    assert_eq!(*comment.span(), span((17, 15, 1, 16), (22, 19, 1, 20)));
+
+    assert_eq!(*find(&tokenizer, "saída").span(), span((23, 20, 2, 1), (29, 25, 2, 6)));
+    assert_eq!(*find(&tokenizer, "1").span(), span((33, 29, 2, 10), (34, 30, 2, 11)));
+}
+
+/// Every token covers the source text it holds, at the position counted
+/// from the start of the code.
+fn assert_spans_match(code:&str, tokenizer:&Tokenizer) {
+    for token in tokenizer.tokens() {
+        let (start, end) = (token.span().start(), token.span().end());
+        let text = &code[start.byte()..end.byte()];
+        assert_eq!(text, token.value().repeat(*token.count() as usize), "{:?}", token.span());
+
+        let before = &code[..start.byte()];
+        assert_eq!(start.char(), before.chars().count(), "{:?}", token.value());
+        assert_eq!(start.line(), before.matches('\n').count() + 1, "{:?}", token.value());
+        assert_eq!(start.column(), before.rsplit('\n').next().unwrap_or("").chars().count() + 1, "{:?}", token.value());
+        assert_eq!(end, start.advance(text), "{:?}", token.value());
+    }
+}
+
+#[test]
+fn spans_match_the_source_of_every_script() {
+    for (directory, language) in [("tests/scripts/visualg", "visualg"), ("tests/scripts/portugol", "portugol")] {
+        for entry in fs::read_dir(directory).expect("scripts directory") {
+            let path = entry.expect("script entry").path();
+            if path.extension().is_some_and(|e| e == "alg" || e == "por") {
+                let code = fs::read_to_string(&path).expect("script");
+                assert_spans_match(&code, &tokenize(language, &code));
+            }
+        }
+    }
+}
+
+#[test]
+fn spans_match_the_source_with_multi_byte_chars() {
+    let code = "algoritmo \"çãé 🦀\"\nvar\n   ação, 変数: inteiro // 注釈 ✓\ninicio\n   ação <- 1 + 変数\n   escreval(\"→\", ação)\nfimalgoritmo\n";
+    assert_spans_match(code, &tokenize("visualg", code));
+
+    let code = "programa\n{\n\t/* çã\n\t   🦀 */\n\tfuncao inicio()\n\t{\n\t\tcadeia s = \"→ \\\"é\\\"\" // ✓\n\t\tescreva(s, 'ç')\n\t}\n}\n";
+    assert_spans_match(code, &tokenize("portugol", code));
+}
+
+/// VisuAlg with its `/*` separator rewritten, for delimiter settings no
+/// shipped language uses.
+struct Reseparated {
+    language: Box<dyn Language>,
+    comment: fn(String, Option<String>, bool) -> TokenSeparators,
+}
+
+impl Language for Reseparated {
+    fn escape(&self) -> &str {
+        self.language.escape()
+    }
+
+    fn unescape(&self, escaped:char) -> char {
+        self.language.unescape(escaped)
+    }
+
+    fn name(&self) -> &str {
+        "visualg modificado"
+    }
+
+    fn is_case_sensitive(&self) -> bool {
+        self.language.is_case_sensitive()
+    }
+
+    fn boolean(&self, word:&str) -> Option<bool> {
+        self.language.boolean(word)
+    }
+
+    fn write(&self, value:&Value) -> String {
+        self.language.write(value)
+    }
+
+    fn separators(&self) -> Vec<TokenSeparators> {
+        self.language.separators().into_iter().map(|separator| match separator {
+            TokenSeparators::WordUntil { word, end, skip_content, .. } if word == "/*" => (self.comment)(word, end, skip_content),
+            other => other,
+        }).collect()
+    }
+
+    fn syntax(&self) -> Vec<SyntaxGroup> {
+        self.language.syntax()
+    }
+
+    fn builtins(&self) -> Vec<Box<dyn Builtin>> {
+        self.language.builtins()
+    }
+
+    fn parse(&self, tokens:&[Token]) -> Result<Program, Vec<Diagnostic>> {
+        self.language.parse(tokens)
+    }
+
+    fn parse_expression(&self, tokens:&[Token]) -> Result<Expression, Vec<Diagnostic>> {
+        self.language.parse_expression(tokens)
+    }
+}
+
+fn reseparated(comment:fn(String, Option<String>, bool) -> TokenSeparators, code:&str) -> Tokenizer {
+    let language = Reseparated { language: languages::by_name("visualg").expect("visualg"), comment };
+    let mut tokenizer = Tokenizer::new(Box::new(language));
+    tokenizer.tokenize(code);
+    tokenizer
+}
+
+/// VisuAlg with `/*` comments that nest.
+fn nesting(code:&str) -> Tokenizer {
+    reseparated(|word, end, skip_content| TokenSeparators::WordUntil {
+        word,
+        end,
+        skip_content,
+        settings: SeparatorSetting::new(TokenTypes::GroupComment).nestable(),
+    }, code)
+}
+
+/// VisuAlg with `/*` comments dropped instead of tokenized.
+fn skipping(code:&str) -> Tokenizer {
+    reseparated(|word, end, _| TokenSeparators::WordUntil {
+        word,
+        end,
+        skip_content: true,
+        settings: SeparatorSetting::new(TokenTypes::GroupComment),
+    }, code)
+}
+
+fn values(tokenizer:&Tokenizer, schema:TokenTypes) -> Vec<&str> {
+    tokenizer.tokens().iter().filter(|t| *t.schema() == schema).map(|t| t.value().as_str()).collect()
+}
+
+#[test]
+fn unterminated_string_is_reported_at_its_opening_quote() {
+    let code = "x <- \"ação\ny <- 1\n";
+    let tokenizer = tokenize("visualg", code);
+
+    let [diagnostic] = tokenizer.diagnostics().as_slice() else {
+        panic!("{:?}", tokenizer.diagnostics());
+    };
+    assert_eq!(diagnostic.code(), codes::UNTERMINATED_DELIMITER);
+    assert_eq!(*diagnostic.span(), span((5, 5, 1, 6), (6, 6, 1, 7)));
+    let suggestion = diagnostic.suggestion().expect("suggestion");
+    assert_eq!(suggestion.replacement(), "\"");
+    assert_eq!(*suggestion.span(), span((20, 18, 3, 1), (20, 18, 3, 1)));
+
+    // The rest of the code becomes the string, not stray tokens.
+    let last = tokenizer.tokens().last().expect("tokens");
+    assert_eq!(*last.schema(), TokenTypes::String);
+    assert_eq!(*last.span(), span((5, 5, 1, 6), (20, 18, 3, 1)));
+}
+
+#[test]
+fn unterminated_block_comment_is_reported_at_its_opener() {
+    let code = "programa\n{\n\t/* é\n\tfuncao inicio() { }\n}\n";
+    let tokenizer = tokenize("portugol", code);
+
+    let [diagnostic] = tokenizer.diagnostics().as_slice() else {
+        panic!("{:?}", tokenizer.diagnostics());
+    };
+    assert_eq!(diagnostic.code(), codes::UNTERMINATED_DELIMITER);
+    assert_eq!(*diagnostic.span(), span((12, 12, 3, 2), (14, 14, 3, 4)));
+    assert_eq!(diagnostic.suggestion().expect("suggestion").replacement(), "*/");
+    assert_eq!(values(&tokenizer, TokenTypes::GroupComment), vec!["/* é\n\tfuncao inicio() { }\n}\n"]);
+}
+
+#[test]
+fn line_comment_at_the_end_of_the_code_is_closed() {
+    let tokenizer = tokenize("visualg", "x <- 1 // fim");
+
+    assert!(tokenizer.diagnostics().is_empty(), "{:?}", tokenizer.diagnostics());
+    assert_eq!(values(&tokenizer, TokenTypes::LineComment), vec!["// fim"]);
+}
+
+#[test]
+fn escaped_quotes_do_not_close_strings() {
+    let literal = |tokenizer:&Tokenizer| -> Vec<Literal> {
+        tokenizer.tokens().iter().filter_map(|t| t.literal().cloned()).collect()
+    };
+
+    let tokenizer = tokenize("portugol", r#"s = "a\"b\n" + "c\\""#);
+    assert!(tokenizer.diagnostics().is_empty(), "{:?}", tokenizer.diagnostics());
+    assert_eq!(values(&tokenizer, TokenTypes::String), vec![r#""a\"b\n""#, r#""c\\""#]);
+    assert_eq!(literal(&tokenizer), vec![Literal::String(String::from("a\"b\n")), Literal::String(String::from("c\\"))]);
+
+    let tokenizer = tokenize("visualg", r#"s <- "a\"b""#);
+    assert_eq!(literal(&tokenizer), vec![Literal::String(String::from("a\"b"))]);
+
+    let tokenizer = tokenize("visualg", r#"s <- "a\""#);
+    assert_eq!(tokenizer.diagnostics().iter().map(|d| d.code()).collect::<Vec<_>>(), vec![codes::UNTERMINATED_DELIMITER]);
+}
+
+#[test]
+fn nested_comments_close_with_their_outermost_opener() {
+    let tokenizer = nesting("x /* a /* b */ c */ y");
+    assert!(tokenizer.diagnostics().is_empty(), "{:?}", tokenizer.diagnostics());
+    assert_eq!(values(&tokenizer, TokenTypes::GroupComment), vec!["/* a /* b */ c */"]);
+    assert_eq!(values(&tokenizer, TokenTypes::Identifier), vec!["x", "y"]);
+
+    // Without nesting the first closer ends the comment.
+    let tokenizer = tokenize("visualg", "x /* a /* b */ c */ y");
+    assert_eq!(values(&tokenizer, TokenTypes::GroupComment), vec!["/* a /* b */"]);
+
+    // Quotes inside a comment do not hide an opener.
+    let tokenizer = nesting("/* \"/*\" */ */ z");
+    assert_eq!(values(&tokenizer, TokenTypes::GroupComment), vec!["/* \"/*\" */ */"]);
+}
+
+#[test]
+fn unclosed_nested_comment_points_at_the_inner_opener() {
+    let tokenizer = nesting("x /* a /* b\ny");
+
+    let [diagnostic] = tokenizer.diagnostics().as_slice() else {
+        panic!("{:?}", tokenizer.diagnostics());
+    };
+    assert_eq!(diagnostic.code(), codes::UNTERMINATED_DELIMITER);
+    assert_eq!(*diagnostic.span(), span((2, 2, 1, 3), (4, 4, 1, 5)));
+    assert_eq!(diagnostic.secondary().iter().map(|l| *l.span()).collect::<Vec<_>>(), vec![span((7, 7, 1, 8), (9, 9, 1, 10))]);
+    assert_eq!(values(&tokenizer, TokenTypes::GroupComment), vec!["/* a /* b\ny"]);
+
+    // A nested comment that closes leaves only the outer one open.
+    let tokenizer = nesting("x /* a /* b */ c\ny");
+    assert!(tokenizer.diagnostics()[0].secondary().is_empty());
+}
+
+#[test]
+fn skipped_content_leaves_no_token() {
+    let code = "x /* a \"b\" 1 */ y";
+    let tokenizer = skipping(code);
+    assert!(tokenizer.diagnostics().is_empty(), "{:?}", tokenizer.diagnostics());
+    assert_eq!(values(&tokenizer, TokenTypes::GroupComment), Vec::<&str>::new());
+    assert_eq!(values(&tokenizer, TokenTypes::String), Vec::<&str>::new());
+    assert_eq!(values(&tokenizer, TokenTypes::Identifier), vec!["x", "y"]);
+    assert_eq!(*find(&tokenizer, "y").span(), span((16, 16, 1, 17), (17, 17, 1, 18)));
+
+    // Unclosed, the run still reaches the end and is still reported.
+    let tokenizer = skipping("x /* a\ny");
+    assert_eq!(tokenizer.diagnostics().iter().map(|d| d.code()).collect::<Vec<_>>(), vec![codes::UNTERMINATED_DELIMITER]);
+    assert_eq!(values(&tokenizer, TokenTypes::Identifier), vec!["x"]);
+}